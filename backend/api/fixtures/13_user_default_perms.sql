@@ -17,3 +17,6 @@ insert into "session" (user_id, token, scope_mask) values ('1f241e1b-b537-493f-a
 
 -- 8 is "Manage Own Jigs"
 insert into "user_scope" (user_id, scope) values ('1f241e1b-b537-493f-a230-075cb16315be', 8);
+
+-- 10 is "Manage Own Classes"
+insert into "user_scope" (user_id, scope) values ('1f241e1b-b537-493f-a230-075cb16315be', 10);
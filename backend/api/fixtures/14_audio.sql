@@ -1,3 +1,7 @@
 insert into audio_metadata (id, name, description, is_premium, created_at, looping, kind)
 values
     ('65f6f260-af1d-11eb-97df-8366e4dbf88e', 'test', 'testest', false, '2021-05-07 10:15:48.042789+00', false, 0);
+
+-- uploaded before owners were recorded, so nobody can update it.
+insert into user_audio_library (id, created_at)
+values ('9b2ff0e6-ee1d-11eb-8d1a-5b1a42b2e4b5', '2021-05-07 10:15:48.042789+00');
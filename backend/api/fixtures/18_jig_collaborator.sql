@@ -0,0 +1,15 @@
+insert into "user" (id, created_at) values ('6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46', '2020-08-08T00:11:21Z'::timestamptz);
+
+insert into user_email (user_id, email, created_at) values ('6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46', 'author@test.test', '2020-08-08T00:11:21Z'::timestamptz);
+
+-- 8 is "Manage Own Jigs"
+insert into "user_scope" (user_id, scope) values ('6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46', 8);
+
+insert into jig (id, creator_id, author_id, created_at, language, description, is_public)
+values ('7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0', '6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46',
+        '6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46', '2021-03-04 00:46:26.134651+00', 'en', 'shared jig', true);
+
+-- the test user has a pending invite to edit the jig
+insert into jig_collaborator (jig_id, user_id, role, invited_by)
+values ('7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0', '1f241e1b-b537-493f-a230-075cb16315be', 0,
+        '6c9f4ec4-e2d4-11eb-9fcd-7b0a2bff8d46');
@@ -12,4 +12,10 @@ values ('1f241e1b-b537-493f-a230-075cb16315be', '8cca6f3a-c4bb-11eb-8edf-13c7567
        ('1f241e1b-b537-493f-a230-075cb16315be', '8cca719c-c4bb-11eb-8edf-f7accb638a15', 1, '2021-06-03 22:30:46.451362'),
        ('1f241e1b-b537-493f-a230-075cb16315be', '8cca720a-c4bb-11eb-8edf-63da1d86939c', 1, '2021-06-03 22:30:47.451362');
 
-insert into image_upload (image_id) values ('8cca720a-c4bb-11eb-8edf-63da1d86939c');
\ No newline at end of file
+insert into image_upload (image_id) values ('8cca720a-c4bb-11eb-8edf-63da1d86939c');
+
+-- processed images, `test` and `test1` look alike (their hashes differ by 3 bits), `test2` looks nothing like them.
+insert into image_upload (image_id, uploaded_at, processed_at, processing_result, perceptual_hash)
+values ('3095d05e-f2c7-11ea-89c3-3b621dd74a1f', '2020-09-09T18:06:31.575087Z', '2020-09-09T18:06:31.575087Z', true, 0),
+       ('8cca6f3a-c4bb-11eb-8edf-13c75672da8f', '2021-05-01T18:06:31.575087Z', '2021-05-01T18:06:31.575087Z', true, 7),
+       ('8cca7124-c4bb-11eb-8edf-7b42383ed8f5', '2021-05-01T18:06:31.575087Z', '2021-05-01T18:06:31.575087Z', true, -1);
\ No newline at end of file
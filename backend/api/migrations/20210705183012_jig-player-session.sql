@@ -0,0 +1,24 @@
+-- `jig_id` and `module_id` intentionally have no foreign keys: publishing a draft deletes the live jig row
+-- (and its modules) before moving the draft over, which would otherwise take every recorded session with it.
+-- Other tables that have to outlive a publish reference the jig the same way, and point back here.
+create table jig_player_session
+(
+    id         uuid primary key not null default uuid_generate_v1mc(),
+    jig_id     uuid             not null,
+    user_id    uuid references "user" (id) on delete set null,
+    started_at timestamptz      not null default now()
+);
+
+create index jig_player_session_jig_id_idx on jig_player_session (jig_id, started_at desc);
+
+create table jig_player_session_module
+(
+    session_id uuid        not null references jig_player_session (id) on delete cascade,
+    module_id  uuid        not null,
+    score      int4        not null check (score >= 0),
+    attempts   int4        not null check (attempts >= 0),
+    time_spent int4        not null check (time_spent >= 0),
+    created_at timestamptz not null default now()
+);
+
+create index jig_player_session_module_session_id_idx on jig_player_session_module (session_id);
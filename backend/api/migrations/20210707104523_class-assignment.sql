@@ -0,0 +1,51 @@
+create table class
+(
+    id         uuid primary key not null default uuid_generate_v1mc(),
+    name       text             not null,
+    creator_id uuid             not null references "user" (id) on delete cascade,
+    created_at timestamptz      not null default now(),
+    updated_at timestamptz
+);
+
+create index class_creator_id_idx on class (creator_id);
+
+-- no foreign key on `jig_id`, see `jig_player_session`.
+create table assignment
+(
+    id         uuid primary key not null default uuid_generate_v1mc(),
+    class_id   uuid             not null references class (id) on delete cascade,
+    jig_id     uuid             not null,
+    code       text             not null unique,
+    created_at timestamptz      not null default now(),
+    updated_at timestamptz,
+    due_at     timestamptz
+);
+
+create index assignment_class_id_idx on assignment (class_id);
+create index assignment_jig_id_idx on assignment (jig_id);
+
+create table assignment_student
+(
+    id            uuid primary key not null default uuid_generate_v1mc(),
+    assignment_id uuid             not null references assignment (id) on delete cascade,
+    name          text             not null,
+    joined_at     timestamptz      not null default now()
+);
+
+create index assignment_student_assignment_id_idx on assignment_student (assignment_id);
+
+-- students joining an assignment get a session without having a user.
+alter table session
+    alter column user_id drop not null,
+    add column assignment_student_id uuid references assignment_student (id) on delete cascade,
+    add constraint session_user_or_assignment_student check (num_nonnulls(user_id, assignment_student_id) = 1);
+
+alter table jig_player_session
+    add column assignment_student_id uuid references assignment_student (id) on delete cascade;
+
+create index jig_player_session_assignment_student_id_idx on jig_player_session (assignment_student_id);
+
+-- All users start out with the ability to create classes for themselves.
+insert into user_scope (user_id, scope)
+select id as user_id, 10 as scope
+from "user";
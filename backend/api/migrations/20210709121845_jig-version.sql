@@ -0,0 +1,36 @@
+-- Immutable snapshots of a jig, written every time a draft is published.
+-- no foreign key on `jig_id`, see `jig_player_session`.
+create table jig_version
+(
+    id                      uuid primary key not null default uuid_generate_v1mc(),
+    jig_id                  uuid             not null,
+    version                 int4             not null check (version > 0),
+    published_at            timestamptz      not null default now(),
+    published_by            uuid references "user" (id) on delete set null,
+    display_name            text             not null,
+    language                text             not null,
+    description             text             not null,
+    direction               smallint         not null,
+    display_score           bool             not null,
+    theme                   smallint         not null,
+    audio_background        smallint,
+    audio_feedback_positive smallint[]       not null,
+    audio_feedback_negative smallint[]       not null,
+    goals                   uuid[]           not null,
+    categories              uuid[]           not null,
+    affiliations            uuid[]           not null,
+    age_ranges              uuid[]           not null,
+    additional_resources    text[]           not null,
+    unique (jig_id, version)
+);
+
+create table jig_version_module
+(
+    version_id  uuid     not null references jig_version (id) on delete cascade,
+    module_id   uuid     not null,
+    "index"     smallint not null,
+    kind        smallint not null,
+    contents    jsonb    not null,
+    is_complete bool     not null,
+    primary key (version_id, "index")
+);
@@ -0,0 +1,13 @@
+-- no foreign key on `jig_id`, see `jig_player_session`.
+create table jig_collaborator
+(
+    jig_id      uuid        not null,
+    user_id     uuid        not null references "user" (id) on delete cascade,
+    role        smallint    not null,
+    invited_by  uuid references "user" (id) on delete set null,
+    invited_at  timestamptz not null default now(),
+    accepted_at timestamptz,
+    primary key (jig_id, user_id)
+);
+
+create index jig_collaborator_user_id_idx on jig_collaborator (user_id);
@@ -0,0 +1,9 @@
+-- bumped on every update, so that editors can detect (and not overwrite) each other's changes.
+alter table jig
+    add column revision int4 not null default 0;
+
+alter table jig_module
+    add column revision int4 not null default 0;
+
+alter table jig_version_module
+    add column revision int4 not null default 0;
@@ -0,0 +1,33 @@
+-- The images and audio files referenced by each module's body, kept in sync with `jig_module.contents` by the api.
+-- `kind` is 0 for images and 1 for audio, `library` is a `MediaLibrary`.
+create table module_media_ref
+(
+    module_id uuid     not null references jig_module (id) on delete cascade,
+    kind      smallint not null,
+    library   smallint not null,
+    media_id  uuid     not null,
+    primary key (module_id, kind, library, media_id)
+);
+
+create index module_media_ref_media_id_idx on module_media_ref (media_id);
+
+-- backfill existing modules, using the same keys as the api (`image`/`Image` for images, `audio` for audio).
+insert into module_media_ref (module_id, kind, library, media_id)
+select distinct jig_module.id,
+                media.kind,
+                case media.ref ->> 'lib' when 'Global' then 0 when 'User' then 1 when 'Web' then 2 end,
+                (media.ref ->> 'id')::uuid
+from jig_module
+         cross join lateral (
+    select 0 as kind, ref
+    from jsonb_path_query(jig_module.contents, 'lax $.**.image') as ref
+    union all
+    select 0 as kind, ref
+    from jsonb_path_query(jig_module.contents, 'lax $.**.Image') as ref
+    union all
+    select 1 as kind, ref
+    from jsonb_path_query(jig_module.contents, 'lax $.**.audio') as ref
+) as media
+where jsonb_typeof(media.ref) = 'object'
+  and media.ref ->> 'lib' in ('Global', 'User', 'Web')
+  and media.ref ->> 'id' ~* '^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$';
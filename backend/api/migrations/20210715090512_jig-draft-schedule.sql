@@ -0,0 +1,11 @@
+-- drafts that should be published to their live jig at `publish_at`, by the api's scheduler.
+-- deleted along with the live jig, which includes publishing the draft by hand.
+create table jig_draft_schedule
+(
+    live_id      uuid primary key references jig (id) on delete cascade,
+    publish_at   timestamptz not null,
+    scheduled_by uuid        not null references "user" (id) on delete cascade,
+    created_at   timestamptz not null default now()
+);
+
+create index jig_draft_schedule_publish_at_idx on jig_draft_schedule (publish_at);
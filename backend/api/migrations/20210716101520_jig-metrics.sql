@@ -0,0 +1,30 @@
+-- no foreign keys on `jig_id`, see `jig_player_session`. they're cleaned up when the jig is deleted.
+
+-- one like per user per jig.
+create table jig_like
+(
+    jig_id     uuid        not null,
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (jig_id, user_id)
+);
+
+-- one view per user per jig.
+create table jig_view
+(
+    jig_id     uuid        not null,
+    user_id    uuid        not null references "user" (id) on delete cascade,
+    created_at timestamptz not null default now(),
+    primary key (jig_id, user_id)
+);
+
+-- `updated_at` is when any of the jig's counters (including likes and views) last changed, for syncing to algolia.
+create table jig_metrics
+(
+    jig_id     uuid primary key,
+    plays      bigint      not null default 0,
+    updated_at timestamptz not null default now()
+);
+
+create index jig_like_user_id_idx on jig_like (user_id);
+create index jig_view_user_id_idx on jig_view (user_id);
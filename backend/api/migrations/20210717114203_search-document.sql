@@ -0,0 +1,12 @@
+-- full text search documents, for searching without algolia.
+-- `simple` since content isn't only english (it doesn't stem or drop stop words).
+alter table jig
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', display_name || ' ' || description)) stored;
+
+alter table image_metadata
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', name || ' ' || description)) stored;
+
+create index jig_search_document_idx on jig using gin (search_document);
+create index image_metadata_search_document_idx on image_metadata using gin (search_document);
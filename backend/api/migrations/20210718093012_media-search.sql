@@ -0,0 +1,17 @@
+alter table animation_metadata
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', name || ' ' || description)) stored;
+
+create index animation_metadata_search_document_idx on animation_metadata using gin (search_document);
+
+-- user uploads are only ever searched by their owner, and are never sent to algolia.
+alter table user_image_library
+    add column name text   not null default '',
+    add column tags text[] not null default '{}';
+
+alter table user_audio_library
+    add column name text   not null default '',
+    add column tags text[] not null default '{}';
+
+create index user_image_library_user_id_idx on user_image_library (user_id);
+create index user_audio_library_user_id_idx on user_audio_library (user_id);
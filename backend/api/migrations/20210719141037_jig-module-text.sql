@@ -0,0 +1,14 @@
+-- the text shown by a jig's modules, extracted by the api (see `module_text`).
+-- null if it hasn't been extracted yet.
+alter table jig
+    add column module_text text;
+
+-- generated columns can't be altered, so `search_document` has to be re-created to include the module text.
+alter table jig
+    drop column search_document;
+
+alter table jig
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', display_name || ' ' || description || ' ' || coalesce(module_text, ''))) stored;
+
+create index jig_search_document_idx on jig using gin (search_document);
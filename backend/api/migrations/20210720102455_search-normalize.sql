@@ -0,0 +1,45 @@
+-- normalizes text for search, has to be kept in sync with `shared::search::normalize`:
+-- hebrew punctuation becomes a space, niqqud, cantillation marks, geresh and gershayim are removed,
+-- and final letter forms are replaced by the regular ones (lowercasing is left to `to_tsvector`).
+create function search_normalize(value text) returns text
+    language sql
+    immutable
+    strict
+    parallel safe
+as
+$$
+select translate(
+               regexp_replace(
+                       regexp_replace(value, '[\u05BE\u05C0\u05C3\u05C6]', ' ', 'g'),
+                       '[\u0591-\u05C7\u05F3\u05F4]', '', 'g'
+                   ),
+               'ךםןףץ', 'כמנפצ'
+           )
+$$;
+
+-- generated columns can't be altered, so the search documents have to be re-created.
+alter table jig
+    drop column search_document;
+
+alter table jig
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', search_normalize(
+                display_name || ' ' || description || ' ' || coalesce(module_text, '')))) stored;
+
+alter table image_metadata
+    drop column search_document;
+
+alter table image_metadata
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', search_normalize(name || ' ' || description))) stored;
+
+alter table animation_metadata
+    drop column search_document;
+
+alter table animation_metadata
+    add column search_document tsvector not null
+        generated always as (to_tsvector('simple', search_normalize(name || ' ' || description))) stored;
+
+create index jig_search_document_idx on jig using gin (search_document);
+create index image_metadata_search_document_idx on image_metadata using gin (search_document);
+create index animation_metadata_search_document_idx on animation_metadata using gin (search_document);
@@ -0,0 +1,11 @@
+-- searches made by users, for suggestions (see `search::Suggest`).
+-- only the most recent ones are kept, the rest are deleted as new ones are made.
+create table user_recent_search
+(
+    user_id     uuid        not null references "user" (id) on delete cascade,
+    query       text        not null,
+    searched_at timestamptz not null default now(),
+    primary key (user_id, query)
+);
+
+create index user_recent_search_searched_at_idx on user_recent_search (user_id, searched_at desc);
@@ -0,0 +1,28 @@
+-- jig searches saved by users, see `user::CreateSavedSearch`.
+create table user_saved_search
+(
+    id              uuid primary key     default uuid_generate_v1mc(),
+    user_id         uuid        not null references "user" (id) on delete cascade,
+    name            text        not null,
+    -- a serialized `JigSearchQuery`.
+    query           jsonb       not null,
+    email_alerts    bool        not null,
+    created_at      timestamptz not null default now(),
+    updated_at      timestamptz,
+    -- jigs published after this are new to the search.
+    last_checked_at timestamptz not null default now()
+);
+
+select trigger_updated_at('user_saved_search');
+
+create index user_saved_search_user_id_idx on user_saved_search (user_id);
+
+-- the jigs that have been included in digests, so that they're only ever sent once per search.
+-- no foreign key on `jig_id`, see `jig_player_session`.
+create table user_saved_search_sent
+(
+    saved_search_id uuid        not null references user_saved_search (id) on delete cascade,
+    jig_id          uuid        not null,
+    sent_at         timestamptz not null default now(),
+    primary key (saved_search_id, jig_id)
+);
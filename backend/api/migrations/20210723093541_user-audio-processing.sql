@@ -0,0 +1,3 @@
+alter table user_audio_library
+    add column duration_ms int check (duration_ms >= 0),
+    add column waveform    real[] not null default '{}';
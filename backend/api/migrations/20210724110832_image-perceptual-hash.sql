@@ -0,0 +1,18 @@
+-- a 64 bit difference hash of the processed image, see `image_ops::perceptual_hash`.
+-- `null` until the image is (re)processed.
+alter table image_upload
+    add column perceptual_hash bigint;
+
+alter table user_image_upload
+    add column perceptual_hash bigint;
+
+-- how many bits two perceptual hashes differ by, the lower, the more similar the images are.
+create function hash_distance(a bigint, b bigint) returns int
+    language sql
+    immutable
+    strict
+    parallel safe
+as
+$$
+select length(replace((a # b)::bit(64)::text, '0', ''))
+$$;
@@ -0,0 +1,33 @@
+-- The images and audio files referenced by the modules of each jig version, copied from `module_media_ref` on publish.
+-- Unlike the modules of a jig, versions can't be edited, so these never change once inserted.
+create table jig_version_media_ref
+(
+    version_id uuid     not null references jig_version (id) on delete cascade,
+    kind       smallint not null,
+    library    smallint not null,
+    media_id   uuid     not null,
+    primary key (version_id, kind, library, media_id)
+);
+
+create index jig_version_media_ref_media_id_idx on jig_version_media_ref (media_id);
+
+-- backfill existing versions, the same way `module_media_ref` was.
+insert into jig_version_media_ref (version_id, kind, library, media_id)
+select distinct jig_version_module.version_id,
+                media.kind,
+                case media.ref ->> 'lib' when 'Global' then 0 when 'User' then 1 when 'Web' then 2 end,
+                (media.ref ->> 'id')::uuid
+from jig_version_module
+         cross join lateral (
+    select 0 as kind, ref
+    from jsonb_path_query(jig_version_module.contents, 'lax $.**.image') as ref
+    union all
+    select 0 as kind, ref
+    from jsonb_path_query(jig_version_module.contents, 'lax $.**.Image') as ref
+    union all
+    select 1 as kind, ref
+    from jsonb_path_query(jig_version_module.contents, 'lax $.**.audio') as ref
+) as media
+where jsonb_typeof(media.ref) = 'object'
+  and media.ref ->> 'lib' in ('Global', 'User', 'Web')
+  and media.ref ->> 'id' ~* '^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$';
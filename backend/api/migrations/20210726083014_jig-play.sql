@@ -0,0 +1,9 @@
+-- when each player (a user, or an assignment student) last had a play of a jig counted, so that replaying it
+-- (or calling the endpoint in a loop) doesn't inflate its plays. no foreign key to the jig, see `jig_player_session`.
+create table jig_play
+(
+    jig_id    uuid        not null,
+    player_id uuid        not null,
+    played_at timestamptz not null default now(),
+    primary key (jig_id, player_id)
+);
@@ -0,0 +1,4 @@
+-- the text of each module is extracted when the module changes, and the jig's `module_text` is put together from it.
+-- it's null until extracted, the modules of existing jigs are extracted by the backfill task.
+alter table jig_module
+    add column module_text text;
@@ -0,0 +1,35 @@
+-- whether content is waiting to be synced to the search index,
+-- shared by the algolia sync task and the admin search health so that they can't disagree.
+create function search_sync_pending(last_synced_at timestamptz, updated_at timestamptz, publish_at timestamptz)
+    returns bool
+    language sql
+    stable
+    parallel safe
+as
+$$
+select last_synced_at is null
+           or (updated_at is not null and last_synced_at < updated_at)
+           or (publish_at < now() is true and last_synced_at < publish_at)
+$$;
+
+-- jigs also have metrics, which change a lot, so they're only synced every so often.
+create function jig_search_sync_pending(jig_id uuid, last_synced_at timestamptz, updated_at timestamptz,
+                                        publish_at timestamptz)
+    returns bool
+    language sql
+    stable
+    parallel safe
+as
+$$
+select search_sync_pending(last_synced_at, updated_at, publish_at)
+           or (last_synced_at < now() - interval '5 minutes' and
+               last_synced_at < (select updated_at from jig_metrics where jig_metrics.jig_id = $1))
+$$;
+
+-- kinds of content (see `SearchIndexEntity`) that were fully reindexed, whose index has to be checked for
+-- records of content that doesn't exist anymore (deleting it from the index is best-effort).
+create table search_orphan_check
+(
+    entity       smallint primary key,
+    requested_at timestamptz not null default now()
+);
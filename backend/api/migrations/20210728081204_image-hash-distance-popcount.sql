@@ -0,0 +1,17 @@
+-- counts the differing bits with integer arithmetic (rather than by formatting them as text),
+-- the near-duplicate report calls this for every candidate pair.
+--
+-- each half is counted as a (non-negative) 32 bit number so nothing overflows:
+-- the bits are summed in pairs, then nibbles, then bytes, and the multiplication adds the bytes up into the top one.
+create or replace function hash_distance(a bigint, b bigint) returns int
+    language sql
+    immutable
+    strict
+    parallel safe
+as
+$$
+select sum(((((v + (v >> 4)) & 252645135) * 16843009) >> 24) & 255)::int
+from (select (v & 858993459) + ((v >> 2) & 858993459) as v
+      from (select v - ((v >> 1) & 1431655765) as v
+            from (values ((a # b) & 4294967295), (((a # b) >> 32) & 4294967295)) as half(v)) as pairs) as nibbles
+$$;
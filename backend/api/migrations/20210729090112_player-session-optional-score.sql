@@ -0,0 +1,3 @@
+-- Some modules (posters, flashcards) are only played through and have nothing to score.
+alter table jig_player_session_module
+    alter column score drop not null;
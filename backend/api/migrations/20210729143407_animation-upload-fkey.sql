@@ -0,0 +1,11 @@
+-- `global_animation_upload` was pointed at `image_metadata` by mistake, so no animation could ever have an upload row
+-- (and without one, animations can't be uploaded).
+alter table global_animation_upload
+    drop constraint global_animation_upload_animation_id_fkey,
+    add constraint global_animation_upload_animation_id_fkey
+        foreign key (animation_id) references animation_metadata (id) on delete restrict;
+
+insert into global_animation_upload (animation_id)
+select id
+from animation_metadata
+on conflict (animation_id) do nothing;
@@ -8,23 +8,30 @@ use anyhow::Context;
 use chrono::Utc;
 use core::settings::AlgoliaSettings;
 use futures::TryStreamExt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use shared::{
     domain::{
+        admin::SearchIndexEntity,
+        animation::{AnimationId, AnimationKind},
         category::CategoryId,
-        image::{ImageId, ImageKind},
-        jig::JigId,
+        image::{ImageId, ImageKind, ImageSearchFacets},
+        jig::{JigId, JigSearchFacets, JigSort, ModuleKind},
         meta::AffiliationId,
         meta::AgeRangeId,
-        meta::{GoalId, ImageStyleId, TagId},
+        meta::{AnimationStyleId, GoalId, ImageStyleId, TagId},
     },
     media::MediaGroupKind,
+    search::normalize,
 };
 use sqlx::PgPool;
-use std::{convert::TryInto, time::Duration, time::Instant};
+use std::{collections::HashMap, convert::TryInto, hash::Hash, time::Duration, time::Instant};
 use tokio::task::JoinHandle;
 use uuid::Uuid;
 
+use crate::{
+    db,
+    search::{IndexCounts, SearchResults},
+};
 use migration::ResyncKind;
 
 mod migration;
@@ -36,7 +43,9 @@ const HAS_AUTHOR_TAG: &'static str = "hasAuthor";
 #[derive(Serialize)]
 struct BatchJig<'a> {
     name: &'a str,
-    // language: &'a str,
+    language: &'a str,
+    module_kinds: Vec<&'static str>,
+    module_text: &'a str,
     age_ranges: &'a [Uuid],
     age_range_names: &'a [String],
     affiliations: &'a [Uuid],
@@ -46,6 +55,10 @@ struct BatchJig<'a> {
     categories: &'a [Uuid],
     category_names: &'a [String],
     author: Option<Uuid>,
+    last_edited: Option<i64>,
+    plays: i64,
+    likes: i64,
+    views: i64,
     #[serde(rename = "_tags")]
     tags: Vec<&'static str>,
 }
@@ -69,11 +82,56 @@ struct BatchImage<'a> {
     tags: Vec<&'static str>,
 }
 
+#[derive(Serialize)]
+struct BatchAnimation<'a> {
+    name: &'a str,
+    description: &'a str,
+    styles: &'a [Uuid],
+    style_names: &'a [String],
+    media_subkind: &'a str,
+    #[serde(rename = "_tags")]
+    tags: Vec<&'static str>,
+}
+
 #[derive(Serialize)]
 #[serde(tag = "media_kind")]
 #[serde(rename_all = "camelCase")]
 enum BatchMedia<'a> {
     Image(BatchImage<'a>),
+    Animation(BatchAnimation<'a>),
+}
+
+/// Every way jig searches can be sorted, see [`jig_sort_replica`].
+const JIG_SORTS: [JigSort; 4] = [
+    JigSort::Recent,
+    JigSort::Plays,
+    JigSort::Likes,
+    JigSort::Views,
+];
+
+/// Returns the replica of `jig_index` that ranks jigs by `sort`, and the attribute it ranks by.
+fn jig_sort_replica(jig_index: &str, sort: JigSort) -> (String, &'static str) {
+    let attribute = match sort {
+        JigSort::Recent => "last_edited",
+        JigSort::Plays => "plays",
+        JigSort::Likes => "likes",
+        JigSort::Views => "views",
+    };
+
+    (format!("{}_{}", jig_index, attribute), attribute)
+}
+
+#[derive(Deserialize)]
+struct BrowseHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+}
+
+#[derive(Deserialize)]
+struct BrowseResponse {
+    hits: Vec<BrowseHit>,
+    /// Where the next page starts, there are no more pages without it.
+    cursor: Option<String>,
 }
 
 pub struct Manager {
@@ -81,6 +139,10 @@ pub struct Manager {
     pub inner: Inner,
     pub media_index: String,
     pub jig_index: String,
+    // `algolia` doesn't support replicas, rankings or browsing, so those are done by hand.
+    http: reqwest::Client,
+    app_id: String,
+    key: String,
 }
 
 impl Manager {
@@ -100,10 +162,13 @@ impl Manager {
         };
 
         Ok(Some(Self {
-            inner: Inner::new(AppId::new(app_id), ApiKey(key))?,
+            inner: Inner::new(AppId::new(app_id.clone()), ApiKey(key.clone()))?,
             media_index,
             jig_index,
             db,
+            http: reqwest::Client::new(),
+            app_id,
+            key,
         }))
     }
 
@@ -117,15 +182,24 @@ impl Manager {
             loop {
                 let iteration_start = Instant::now();
 
-                let res = if turn_modulus % 2 == 0 {
-                    self.update_images()
+                let res = match turn_modulus {
+                    0 => self
+                        .update_images()
+                        .await
+                        .context("update images task errored"),
+                    1 => self
+                        .update_animations()
+                        .await
+                        .context("update animations task errored"),
+                    2 => self.update_jigs().await.context("update jigs task errored"),
+                    _ => self
+                        .delete_orphans()
                         .await
-                        .context("update images task errored")
-                } else {
-                    self.update_jigs().await.context("update jigs task errored")
+                        .map(|()| true)
+                        .context("delete orphans task errored"),
                 };
 
-                turn_modulus = (turn_modulus + 1) % 2;
+                turn_modulus = (turn_modulus + 1) % 4;
 
                 match res {
                     Ok(true) => {}
@@ -146,6 +220,15 @@ impl Manager {
     }
 
     pub async fn migrate(&self) -> anyhow::Result<()> {
+        self.run_migrations().await?;
+
+        // after the migrations, so that the replicas get any new settings.
+        self.set_jig_sort_replicas()
+            .await
+            .context("error while setting up the jig sort replicas")
+    }
+
+    async fn run_migrations(&self) -> anyhow::Result<()> {
         let mut txn = self.db.begin().await?;
 
         let algolia_version = sqlx::query!(
@@ -194,6 +277,24 @@ select algolia_index_version as "algolia_index_version!" from "settings"
                 sqlx::query!("update image_metadata set last_synced_at = null")
                     .execute(&mut txn)
                     .await?;
+
+                sqlx::query!("update animation_metadata set last_synced_at = null")
+                    .execute(&mut txn)
+                    .await?;
+
+                sqlx::query!("update jig set last_synced_at = null")
+                    .execute(&mut txn)
+                    .await?;
+
+                db::search::request_orphan_check(
+                    &mut txn,
+                    &[
+                        SearchIndexEntity::Image,
+                        SearchIndexEntity::Animation,
+                        SearchIndexEntity::Jig,
+                    ],
+                )
+                .await?;
             }
             ResyncKind::None => {}
         }
@@ -210,6 +311,239 @@ select algolia_index_version as "algolia_index_version!" from "settings"
         Ok(())
     }
 
+    /// Sets up a replica of the jig index for each [`JigSort`], which ranks jigs by it before relevance.
+    ///
+    /// Replicas don't follow changes to the jig index's settings, so its searchable and faceting attributes
+    /// are copied over every time, which makes this safe to run on every start.
+    async fn set_jig_sort_replicas(&self) -> anyhow::Result<()> {
+        let replicas: Vec<_> = JIG_SORTS
+            .iter()
+            .map(|&sort| jig_sort_replica(&self.jig_index, sort).0)
+            .collect();
+
+        self.set_settings(
+            &self.jig_index,
+            &serde_json::json!({ "replicas": replicas }),
+        )
+        .await?;
+
+        let settings: serde_json::Value = self
+            .http
+            .get(&self.settings_url(&self.jig_index))
+            .header("X-Algolia-Application-Id", &self.app_id)
+            .header("X-Algolia-API-Key", &self.key)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        for &sort in &JIG_SORTS {
+            let (replica, attribute) = jig_sort_replica(&self.jig_index, sort);
+
+            let settings = serde_json::json!({
+                "searchableAttributes": settings["searchableAttributes"],
+                "attributesForFaceting": settings["attributesForFaceting"],
+                "ranking": [
+                    format!("desc({})", attribute),
+                    "typo",
+                    "geo",
+                    "words",
+                    "filters",
+                    "proximity",
+                    "attribute",
+                    "exact",
+                    "custom",
+                ],
+            });
+
+            self.set_settings(&replica, &settings).await?;
+        }
+
+        Ok(())
+    }
+
+    fn settings_url(&self, index: &str) -> String {
+        format!(
+            "https://{}.algolia.net/1/indexes/{}/settings",
+            self.app_id,
+            percent_encoding::utf8_percent_encode(index, percent_encoding::NON_ALPHANUMERIC)
+        )
+    }
+
+    async fn set_settings(&self, index: &str, settings: &serde_json::Value) -> anyhow::Result<()> {
+        self.http
+            .put(&self.settings_url(index))
+            .header("X-Algolia-Application-Id", &self.app_id)
+            .header("X-Algolia-API-Key", &self.key)
+            .json(settings)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Lists the ids of every record in `index` (of the `kind` of media, for the media index).
+    async fn browse_ids(
+        &self,
+        index: &str,
+        kind: Option<MediaGroupKind>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let url = format!(
+            "https://{}-dsn.algolia.net/1/indexes/{}/browse",
+            self.app_id,
+            percent_encoding::utf8_percent_encode(index, percent_encoding::NON_ALPHANUMERIC)
+        );
+
+        let mut body = serde_json::json!({
+            "attributesToRetrieve": ["objectID"],
+            "hitsPerPage": 1000,
+        });
+
+        if let Some(kind) = kind {
+            body["filters"] = format!("media_kind:{}", kind.to_str()).into();
+        }
+
+        let mut ids = Vec::new();
+
+        loop {
+            let response: BrowseResponse = self
+                .http
+                .post(&url)
+                .header("X-Algolia-Application-Id", &self.app_id)
+                .header("X-Algolia-API-Key", &self.key)
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            // records that aren't content (like the old "batch" object) are left alone.
+            ids.extend(
+                response
+                    .hits
+                    .iter()
+                    .filter_map(|hit| Uuid::parse_str(&hit.object_id).ok()),
+            );
+
+            match response.cursor {
+                Some(cursor) => body = serde_json::json!({ "cursor": cursor }),
+                None => return Ok(ids),
+            }
+        }
+    }
+
+    /// Deletes the records with the given ids from `index`, with a batch request per thousand.
+    async fn delete_objects(&self, index: &str, ids: &[Uuid]) -> anyhow::Result<()> {
+        let url = format!(
+            "https://{}.algolia.net/1/indexes/{}/batch",
+            self.app_id,
+            percent_encoding::utf8_percent_encode(index, percent_encoding::NON_ALPHANUMERIC)
+        );
+
+        for chunk in ids.chunks(1000) {
+            let requests: Vec<_> = chunk
+                .iter()
+                .map(|id| {
+                    serde_json::json!({
+                        "action": "deleteObject",
+                        "body": { "objectID": id.to_string() },
+                    })
+                })
+                .collect();
+
+            self.http
+                .post(&url)
+                .header("X-Algolia-Application-Id", &self.app_id)
+                .header("X-Algolia-API-Key", &self.key)
+                .json(&serde_json::json!({ "requests": requests }))
+                .send()
+                .await?
+                .error_for_status()?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the records of content that doesn't exist anymore from the indexes that were fully reindexed,
+    /// since a reindex only updates the records of content that does (see [`db::search::request_orphan_check`]).
+    async fn delete_orphans(&self) -> anyhow::Result<()> {
+        let entities = [
+            (
+                SearchIndexEntity::Image,
+                &self.media_index,
+                Some(MediaGroupKind::Image),
+            ),
+            (
+                SearchIndexEntity::Animation,
+                &self.media_index,
+                Some(MediaGroupKind::Animation),
+            ),
+            (SearchIndexEntity::Jig, &self.jig_index, None),
+        ];
+
+        for &(entity, index, kind) in &entities {
+            let entity_kind = db::search::entity_kind(entity);
+
+            // nothing is locked while the index is browsed, if two instances happen to check at once,
+            // they both delete the same records.
+            let requested_at = sqlx::query!(
+                "select requested_at from search_orphan_check where entity = $1",
+                entity_kind
+            )
+            .fetch_optional(&self.db)
+            .await?
+            .map(|it| it.requested_at);
+
+            let requested_at = match requested_at {
+                Some(requested_at) => requested_at,
+                None => continue,
+            };
+
+            let ids = self.browse_ids(index, kind).await?;
+
+            let orphans: Vec<Uuid> = sqlx::query!(
+                r#"
+select id as "id!"
+from unnest($2::uuid[]) as t(id)
+where not case $1::smallint
+              when 0 then exists(select 1 from image_metadata where image_metadata.id = t.id)
+              when 1 then exists(select 1 from animation_metadata where animation_metadata.id = t.id)
+              else exists(select 1 from jig where jig.id = t.id)
+    end
+"#,
+                entity_kind,
+                &ids
+            )
+            .fetch_all(&self.db)
+            .await?
+            .into_iter()
+            .map(|it| it.id)
+            .collect();
+
+            self.delete_objects(index, &orphans).await?;
+
+            // a reindex that was requested in the meantime gets checked again.
+            sqlx::query!(
+                "delete from search_orphan_check where entity = $1 and requested_at = $2",
+                entity_kind,
+                requested_at
+            )
+            .execute(&self.db)
+            .await?;
+
+            log::info!(
+                "deleted {} orphaned record(s) from algolia index {}",
+                orphans.len(),
+                index
+            );
+        }
+
+        Ok(())
+    }
+
     async fn batch_media(&self, batch: BatchWriteRequests) -> anyhow::Result<Vec<Uuid>> {
         let resp = self.inner.batch(&self.media_index, &batch).await?;
 
@@ -255,6 +589,9 @@ select algolia_index_version as "algolia_index_version!" from "settings"
             r#"
 select id,
     display_name as "name",
+    language,
+    coalesce(module_text, '') as "module_text!",
+    array((select distinct row (kind) from jig_module where jig_id = jig.id)) as "module_kinds!: Vec<(ModuleKind,)>",
     array((select affiliation_id from jig_affiliation where jig_id = jig.id)) as "affiliations!",
     array((select affiliation.display_name
            from affiliation
@@ -276,12 +613,13 @@ select id,
                     inner join jig_category on category.id = jig_category.category_id
            where jig_category.jig_id = jig.id))                               as "category_names!",
     (publish_at < now() is true) as "is_published!",
-    author_id as "author"
+    author_id as "author",
+    extract(epoch from updated_at)::int8 as "last_edited",
+    coalesce((select plays from jig_metrics where jig_id = jig.id), 0) as "plays!",
+    (select count(*) from jig_like where jig_id = jig.id) as "likes!",
+    (select count(*) from jig_view where jig_id = jig.id) as "views!"
 from jig
-where
-    last_synced_at is null or
-    (updated_at is not null and last_synced_at < updated_at) or
-    (publish_at < now() is true and last_synced_at < publish_at)
+where jig_search_sync_pending(id, last_synced_at, updated_at, publish_at)
 limit 100
 for no key update skip locked;
      "#
@@ -299,16 +637,23 @@ for no key update skip locked;
 
             algolia::request::BatchWriteRequest::UpdateObject {
             body: match serde_json::to_value(&BatchJig {
-                name: &row.name,
+                name: &normalize(&row.name),
+                language: &row.language,
+                module_text: &normalize(&row.module_text),
+                module_kinds: row.module_kinds.iter().map(|(kind,)| kind.as_str()).collect(),
                 goals: &row.goals,
-                goal_names: &row.goal_names,
+                goal_names: &normalize_all(&row.goal_names),
                 age_ranges: &row.age_ranges,
-                age_range_names: &row.age_range_names,
+                age_range_names: &normalize_all(&row.age_range_names),
                 affiliations: &row.affiliations,
-                affiliation_names: &row.affiliation_names,
+                affiliation_names: &normalize_all(&row.affiliation_names),
                 categories: &row.categories,
-                category_names: &row.category_names,
+                category_names: &normalize_all(&row.category_names),
                 author: row.author,
+                last_edited: row.last_edited,
+                plays: row.plays,
+                likes: row.likes,
+                views: row.views,
                 tags
             })
             .expect("failed to serialize BatchImage to json")
@@ -394,10 +739,7 @@ select id,
     (publish_at < now() is true) as "is_published!",
     is_premium
 from image_metadata
-where 
-    last_synced_at is null or
-    (updated_at is not null and last_synced_at < updated_at) or
-    (publish_at < now() is true and last_synced_at < publish_at)
+where search_sync_pending(last_synced_at, updated_at, publish_at)
 limit 100
 for no key update skip locked;
      "#
@@ -416,18 +758,18 @@ for no key update skip locked;
             algolia::request::BatchWriteRequest::UpdateObject {
             body: match serde_json::to_value(&BatchMedia::Image(BatchImage {
                 media_subkind: &row.kind.to_str(),
-                name: &row.name,
-                description: &row.description,
+                name: &normalize(&row.name),
+                description: &normalize(&row.description),
                 styles: &row.styles,
-                style_names: &row.style_names,
+                style_names: &normalize_all(&row.style_names),
                 age_ranges: &row.age_ranges,
-                age_range_names: &row.age_range_names,
+                age_range_names: &normalize_all(&row.age_range_names),
                 affiliations: &row.affiliations,
-                affiliation_names: &row.affiliation_names,
+                affiliation_names: &normalize_all(&row.affiliation_names),
                 image_tags: &row.tags,
-                image_tag_names: &row.tag_names,
+                image_tag_names: &normalize_all(&row.tag_names),
                 categories: &row.categories,
-                category_names: &row.category_names,
+                category_names: &normalize_all(&row.category_names),
                 tags
             }))
             .expect("failed to serialize BatchImage to json")
@@ -462,6 +804,94 @@ for no key update skip locked;
 
         Ok(true)
     }
+
+    async fn update_animations(&self) -> anyhow::Result<bool> {
+        let mut txn = self.db.begin().await?;
+
+        let is_outdated = sqlx::query!(
+            r#"select algolia_index_version != $1 as "outdated!" from settings"#,
+            migration::INDEX_VERSION
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .outdated;
+
+        if is_outdated {
+            return Ok(false);
+        }
+
+        let requests: Vec<_> = sqlx::query!(
+            r#"
+select id,
+    name,
+    kind as "kind: AnimationKind",
+    description,
+    array((select style_id from animation_style where animation_id = animation_metadata.id)) as "styles!",
+    array((select style.display_name
+           from style
+                    inner join animation_style on style.id = animation_style.style_id
+           where animation_style.animation_id = animation_metadata.id))                      as "style_names!",
+    (publish_at < now() is true) as "is_published!",
+    is_premium
+from animation_metadata
+where search_sync_pending(last_synced_at, updated_at, publish_at)
+limit 100
+for no key update skip locked;
+     "#
+        )
+        .fetch(&mut txn)
+        .map_ok(|row| {
+            let mut tags = Vec::new();
+            if row.is_published {
+                tags.push(PUBLISHED_TAG);
+            }
+
+            if row.is_premium {
+                tags.push(PREMIUM_TAG);
+            }
+
+            algolia::request::BatchWriteRequest::UpdateObject {
+                body: match serde_json::to_value(&BatchMedia::Animation(BatchAnimation {
+                    media_subkind: row.kind.to_str(),
+                    name: &normalize(&row.name),
+                    description: &normalize(&row.description),
+                    styles: &row.styles,
+                    style_names: &normalize_all(&row.style_names),
+                    tags,
+                }))
+                .expect("failed to serialize BatchAnimation to json")
+                {
+                    serde_json::Value::Object(map) => map,
+                    _ => panic!("failed to serialize BatchAnimation to json map"),
+                },
+                object_id: row.id.to_string(),
+            }
+        })
+        .try_collect()
+        .await?;
+
+        if requests.is_empty() {
+            return Ok(true);
+        }
+
+        log::debug!("Updating a batch of {} animation(s)", requests.len());
+
+        let request = algolia::request::BatchWriteRequests { requests };
+        let ids = self.batch_media(request).await?;
+
+        log::debug!("Updated a batch of {} animation(s)", ids.len());
+
+        sqlx::query!(
+            "update animation_metadata set last_synced_at = now() where id = any($1)",
+            &ids
+        )
+        .execute(&mut txn)
+        .await?;
+
+        txn.commit().await?;
+
+        Ok(true)
+    }
 }
 
 #[derive(Clone)]
@@ -523,6 +953,24 @@ fn filters_for_ids<T: Into<Uuid> + Copy>(
 //     }
 // }
 
+fn normalize_all(names: &[String]) -> Vec<String> {
+    names.iter().map(|name| normalize(name)).collect()
+}
+
+/// Appends the `transliteration` to the `query`, with only the transliterated words made optional.
+fn with_transliteration<'a>(
+    query: &'a str,
+    transliteration: Option<&'a str>,
+) -> (String, Vec<&'a str>) {
+    match transliteration {
+        Some(transliteration) => (
+            format!("{} {}", query, transliteration),
+            transliteration.split_whitespace().collect(),
+        ),
+        None => (query.to_owned(), Vec::new()),
+    }
+}
+
 fn media_filter(kind: MediaGroupKind, invert: bool) -> CommonFilter<FacetFilter> {
     CommonFilter {
         filter: FacetFilter {
@@ -533,9 +981,35 @@ fn media_filter(kind: MediaGroupKind, invert: bool) -> CommonFilter<FacetFilter>
     }
 }
 
+/// A [`SearchResponse`] with facet counts (facet name -> facet value -> count).
+#[derive(Deserialize)]
+struct FacetedSearchResponse {
+    #[serde(flatten)]
+    response: SearchResponse,
+
+    #[serde(default)]
+    facets: HashMap<String, HashMap<String, u64>>,
+}
+
+impl FacetedSearchResponse {
+    /// Takes the counts for `facet_name`, skipping values that aren't ids.
+    fn take_facet<T: Hash + Eq>(&mut self, facet_name: &str, f: fn(Uuid) -> T) -> HashMap<T, u64> {
+        self.facets
+            .remove(facet_name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(value, count)| Some((f(value.parse().ok()?), count)))
+            .collect()
+    }
+}
+
 #[derive(Clone)]
 pub struct Client {
     inner: Inner,
+    // `algolia` doesn't support requesting facets, so faceted searches are done by hand.
+    http: reqwest::Client,
+    app_id: String,
+    search_key: String,
     media_index: String,
     jig_index: String,
 }
@@ -543,21 +1017,22 @@ pub struct Client {
 impl Client {
     pub fn new(settings: Option<AlgoliaSettings>) -> anyhow::Result<Option<Self>> {
         if let Some(settings) = settings {
-            let app_id = algolia::AppId::new(settings.application_id);
+            let app_id = settings.application_id;
 
-            let (inner, media_index, jig_index) = match (
+            let (search_key, media_index, jig_index) = match (
                 settings.backend_search_key,
                 settings.media_index,
                 settings.jig_index,
             ) {
-                (Some(key), Some(media_index), Some(jig_index)) => {
-                    (Inner::new(app_id, ApiKey(key))?, media_index, jig_index)
-                }
+                (Some(key), Some(media_index), Some(jig_index)) => (key, media_index, jig_index),
                 _ => return Ok(None),
             };
 
             Ok(Some(Self {
-                inner,
+                inner: Inner::new(AppId::new(app_id.clone()), ApiKey(search_key.clone()))?,
+                http: reqwest::Client::new(),
+                app_id,
+                search_key,
                 media_index,
                 jig_index,
             }))
@@ -566,10 +1041,51 @@ impl Client {
         }
     }
 
+    /// Like [`Inner::search`], but also requests counts for `facets`.
+    ///
+    /// Hits only need to match one of the `optional_words` in the query.
+    async fn search_faceted(
+        &self,
+        index: &str,
+        query: SearchQuery<'_>,
+        facets: &[&str],
+        optional_words: &[&str],
+    ) -> anyhow::Result<FacetedSearchResponse> {
+        let mut body = serde_json::to_value(&query)?;
+        if let serde_json::Value::Object(body) = &mut body {
+            body.insert("facets".to_owned(), facets.into());
+
+            if !optional_words.is_empty() {
+                body.insert("optionalWords".to_owned(), optional_words.into());
+            }
+        }
+
+        let url = format!(
+            "https://{}-dsn.algolia.net/1/indexes/{}/query",
+            self.app_id,
+            percent_encoding::utf8_percent_encode(index, percent_encoding::NON_ALPHANUMERIC)
+        );
+
+        let response = self
+            .http
+            .post(&url)
+            .header("X-Algolia-Application-Id", &self.app_id)
+            .header("X-Algolia-API-Key", &self.search_key)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
+
     // todo: return ImageId (can't because of repr issues in sqlx)
     pub async fn search_image(
         &self,
         query: &str,
+        transliteration: Option<&str>,
         page: Option<u32>,
         is_premium: Option<bool>,
         is_published: Option<bool>,
@@ -578,7 +1094,7 @@ impl Client {
         affiliations: &[AffiliationId],
         categories: &[CategoryId],
         tags: &[TagId],
-    ) -> anyhow::Result<Option<(Vec<Uuid>, u32, u64)>> {
+    ) -> anyhow::Result<Option<SearchResults<ImageSearchFacets>>> {
         let mut filters = algolia::filter::AndFilter {
             filters: vec![Box::new(media_filter(MediaGroupKind::Image, false))],
         };
@@ -603,30 +1119,54 @@ impl Client {
         filters_for_ids(&mut filters.filters, "categories", categories);
         filters_for_ids(&mut filters.filters, "image_tags", tags);
 
-        let results: SearchResponse = self
-            .inner
-            .search(
+        let (query, optional_words) = with_transliteration(query, transliteration);
+
+        let mut results = self
+            .search_faceted(
                 &self.media_index,
                 SearchQuery {
-                    query: Some(query),
+                    query: Some(&query),
                     page,
                     get_ranking_info: true,
                     filters: Some(filters),
                     hits_per_page: None,
                 },
+                &[
+                    "styles",
+                    "age_ranges",
+                    "affiliations",
+                    "categories",
+                    "image_tags",
+                ],
+                &optional_words,
             )
             .await?;
 
+        let facets = ImageSearchFacets {
+            styles: results.take_facet("styles", ImageStyleId),
+            age_ranges: results.take_facet("age_ranges", AgeRangeId),
+            affiliations: results.take_facet("affiliations", AffiliationId),
+            categories: results.take_facet("categories", CategoryId),
+            tags: results.take_facet("image_tags", TagId),
+        };
+
+        let results = results.response;
+
         let pages = results.page_count.try_into()?;
         let total_hits = results.hit_count as u64;
 
-        let results = results
+        let ids = results
             .hits
             .into_iter()
             .map(|hit| hit.object_id.parse())
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Some((results, pages, total_hits)))
+        Ok(Some(SearchResults {
+            ids,
+            pages,
+            total_hits,
+            facets,
+        }))
     }
 
     pub async fn delete_image(&self, id: ImageId) {
@@ -647,17 +1187,110 @@ impl Client {
         Ok(())
     }
 
+    pub async fn search_animation(
+        &self,
+        query: &str,
+        kind: Option<AnimationKind>,
+        page: Option<u32>,
+        is_premium: Option<bool>,
+        is_published: Option<bool>,
+        styles: &[AnimationStyleId],
+    ) -> anyhow::Result<Option<SearchResults<()>>> {
+        let mut filters = algolia::filter::AndFilter {
+            filters: vec![Box::new(media_filter(MediaGroupKind::Animation, false))],
+        };
+
+        if let Some(kind) = kind {
+            filters.filters.push(Box::new(CommonFilter {
+                filter: FacetFilter {
+                    facet_name: "media_subkind".to_owned(),
+                    value: kind.to_str().to_owned(),
+                },
+                invert: false,
+            }))
+        }
+
+        if let Some(is_published) = is_published {
+            filters.filters.push(Box::new(CommonFilter {
+                filter: TagFilter(PUBLISHED_TAG.to_owned()),
+                invert: !is_published,
+            }))
+        }
+
+        if let Some(is_premium) = is_premium {
+            filters.filters.push(Box::new(CommonFilter {
+                filter: TagFilter(PREMIUM_TAG.to_owned()),
+                invert: !is_premium,
+            }))
+        }
+
+        filters_for_ids(&mut filters.filters, "styles", styles);
+
+        let results: SearchResponse = self
+            .inner
+            .search(
+                &self.media_index,
+                SearchQuery {
+                    query: Some(query),
+                    page,
+                    get_ranking_info: true,
+                    filters: Some(filters),
+                    hits_per_page: None,
+                },
+            )
+            .await?;
+
+        let pages = results.page_count.try_into()?;
+        let total_hits = results.hit_count as u64;
+
+        let ids = results
+            .hits
+            .into_iter()
+            .map(|hit| hit.object_id.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Some(SearchResults {
+            ids,
+            pages,
+            total_hits,
+            facets: (),
+        }))
+    }
+
+    pub async fn delete_animation(&self, id: AnimationId) {
+        if let Err(e) = self.try_delete_animation(id).await {
+            log::warn!(
+                "failed to delete animation with id {} from algolia: {}",
+                id.0.to_hyphenated(),
+                e
+            );
+        }
+    }
+
+    pub async fn try_delete_animation(&self, AnimationId(id): AnimationId) -> anyhow::Result<()> {
+        self.inner
+            .delete_object(&self.media_index, &id.to_string())
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn search_jig(
         &self,
         query: &str,
+        transliteration: Option<&str>,
         page: Option<u32>,
         is_published: Option<bool>,
         age_ranges: &[AgeRangeId],
         affiliations: &[AffiliationId],
         categories: &[CategoryId],
         goals: &[GoalId],
+        language: Option<&str>,
+        module_kinds: &[ModuleKind],
         author: Option<Uuid>,
-    ) -> anyhow::Result<Option<(Vec<Uuid>, u32, u64)>> {
+        sort: Option<JigSort>,
+        hits_per_page: Option<u16>,
+    ) -> anyhow::Result<Option<SearchResults<JigSearchFacets>>> {
         let mut filters = algolia::filter::AndFilter {
             filters: vec![Box::new(media_filter(MediaGroupKind::Image, false))],
         };
@@ -689,30 +1322,185 @@ impl Client {
         filters_for_ids(&mut filters.filters, "categories", categories);
         filters_for_ids(&mut filters.filters, "goals", goals);
 
-        let results: SearchResponse = self
-            .inner
-            .search(
-                &self.jig_index,
+        if let Some(language) = language {
+            filters.filters.push(Box::new(CommonFilter {
+                filter: FacetFilter {
+                    facet_name: "language".to_owned(),
+                    value: language.to_owned(),
+                },
+                invert: false,
+            }))
+        }
+
+        for kind in module_kinds {
+            filters.filters.push(Box::new(CommonFilter {
+                filter: FacetFilter {
+                    facet_name: "module_kinds".to_owned(),
+                    value: kind.as_str().to_owned(),
+                },
+                invert: false,
+            }))
+        }
+
+        let (query, optional_words) = with_transliteration(query, transliteration);
+
+        let index = match sort {
+            Some(sort) => jig_sort_replica(&self.jig_index, sort).0,
+            None => self.jig_index.clone(),
+        };
+
+        let mut results = self
+            .search_faceted(
+                &index,
                 SearchQuery {
-                    query: Some(query),
+                    query: Some(&query),
                     page,
                     get_ranking_info: true,
                     filters: Some(filters),
-                    hits_per_page: None,
+                    hits_per_page,
                 },
+                &["age_ranges", "affiliations", "categories", "goals"],
+                &optional_words,
             )
             .await?;
 
+        let facets = JigSearchFacets {
+            age_ranges: results.take_facet("age_ranges", AgeRangeId),
+            affiliations: results.take_facet("affiliations", AffiliationId),
+            categories: results.take_facet("categories", CategoryId),
+            goals: results.take_facet("goals", GoalId),
+        };
+
+        let results = results.response;
+
         let pages = results.page_count.try_into()?;
         let total_hits = results.hit_count as u64;
 
-        let results = results
+        let ids = results
             .hits
             .into_iter()
             .map(|hit| hit.object_id.parse())
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Some((results, pages, total_hits)))
+        Ok(Some(SearchResults {
+            ids,
+            pages,
+            total_hits,
+            facets,
+        }))
+    }
+
+    /// Searches the `index` for at most `limit` suggestions, relying on Algolia matching the last word as a prefix.
+    async fn suggest(
+        &self,
+        index: &str,
+        query: &str,
+        filters: algolia::filter::AndFilter,
+        limit: u16,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let results: SearchResponse = self
+            .inner
+            .search(
+                index,
+                SearchQuery {
+                    query: Some(query),
+                    page: None,
+                    get_ranking_info: false,
+                    filters: Some(filters),
+                    hits_per_page: Some(limit),
+                },
+            )
+            .await?;
+
+        let ids = results
+            .hits
+            .into_iter()
+            .map(|hit| hit.object_id.parse())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(ids)
+    }
+
+    pub async fn suggest_jigs(&self, query: &str, limit: u16) -> anyhow::Result<Option<Vec<Uuid>>> {
+        let filters = algolia::filter::AndFilter {
+            filters: vec![Box::new(CommonFilter {
+                filter: TagFilter(PUBLISHED_TAG.to_owned()),
+                invert: false,
+            })],
+        };
+
+        self.suggest(&self.jig_index, query, filters, limit)
+            .await
+            .map(Some)
+    }
+
+    pub async fn suggest_images(
+        &self,
+        query: &str,
+        limit: u16,
+    ) -> anyhow::Result<Option<Vec<Uuid>>> {
+        let filters = algolia::filter::AndFilter {
+            filters: vec![
+                Box::new(media_filter(MediaGroupKind::Image, false)),
+                Box::new(CommonFilter {
+                    filter: TagFilter(PUBLISHED_TAG.to_owned()),
+                    invert: false,
+                }),
+            ],
+        };
+
+        self.suggest(&self.media_index, query, filters, limit)
+            .await
+            .map(Some)
+    }
+
+    /// Counts the records in `index` that match `filters` (all of them if there are none).
+    async fn count(
+        &self,
+        index: &str,
+        filters: Option<algolia::filter::AndFilter>,
+    ) -> anyhow::Result<u64> {
+        let results = self
+            .search_faceted(
+                index,
+                SearchQuery {
+                    query: Some(""),
+                    page: None,
+                    get_ranking_info: false,
+                    filters,
+                    hits_per_page: Some(0),
+                },
+                &[],
+                &[],
+            )
+            .await?;
+
+        Ok(results.response.hit_count as u64)
+    }
+
+    /// Counts the records in each index.
+    pub async fn index_counts(&self) -> anyhow::Result<IndexCounts> {
+        let media_count = |kind| {
+            self.count(
+                &self.media_index,
+                Some(algolia::filter::AndFilter {
+                    filters: vec![Box::new(media_filter(kind, false))],
+                }),
+            )
+        };
+
+        let (images, animations, jigs) = futures::future::try_join3(
+            media_count(MediaGroupKind::Image),
+            media_count(MediaGroupKind::Animation),
+            self.count(&self.jig_index, None),
+        )
+        .await?;
+
+        Ok(IndexCounts {
+            images,
+            animations,
+            jigs,
+        })
     }
 
     pub async fn delete_jig(&self, id: JigId) {
@@ -103,6 +103,16 @@ fn set_searchable_fields_v3<'a>(
 }
 
 fn set_attributes_for_faceting_v5<'a>(
+    client: &'a super::Inner,
+    media_index: &'a str,
+    jig_index: &'a str,
+) -> BoxFuture<'a, anyhow::Result<()>> {
+    // superceeded by `set_attributes_for_faceting_v6`
+    empty(client, media_index, jig_index)
+}
+
+// facets that are returned with search results can't be `filter_only`.
+fn set_attributes_for_faceting_v6<'a>(
     client: &'a super::Inner,
     media_index: &'a str,
     _jig_index: &'a str,
@@ -110,10 +120,11 @@ fn set_attributes_for_faceting_v5<'a>(
     let settings = SetSettings {
         searchable_attributes: None,
         attributes_for_faceting: Some(vec![
-            FacetAttribute::filter_only(Attribute("styles".to_owned())),
-            FacetAttribute::filter_only(Attribute("age_ranges".to_owned())),
-            FacetAttribute::filter_only(Attribute("affiliations".to_owned())),
-            FacetAttribute::filter_only(Attribute("categories".to_owned())),
+            FacetAttribute::searchable(Attribute("styles".to_owned())),
+            FacetAttribute::searchable(Attribute("age_ranges".to_owned())),
+            FacetAttribute::searchable(Attribute("affiliations".to_owned())),
+            FacetAttribute::searchable(Attribute("categories".to_owned())),
+            FacetAttribute::searchable(Attribute("image_tags".to_owned())),
             FacetAttribute::filter_only(Attribute("media_kind".to_owned())),
             FacetAttribute::filter_only(Attribute("media_subkind".to_owned())),
         ]),
@@ -125,6 +136,65 @@ fn set_attributes_for_faceting_v5<'a>(
     })
 }
 
+fn set_jig_attributes_for_faceting_v2<'a>(
+    client: &'a super::Inner,
+    media_index: &'a str,
+    jig_index: &'a str,
+) -> BoxFuture<'a, anyhow::Result<()>> {
+    // superceeded by `set_jig_attributes_for_faceting_v3`
+    empty(client, media_index, jig_index)
+}
+
+fn set_jig_attributes_for_faceting_v3<'a>(
+    client: &'a super::Inner,
+    _media_index: &'a str,
+    jig_index: &'a str,
+) -> BoxFuture<'a, anyhow::Result<()>> {
+    let settings = SetSettings {
+        searchable_attributes: None,
+        attributes_for_faceting: Some(vec![
+            FacetAttribute::searchable(Attribute("goals".to_owned())),
+            FacetAttribute::searchable(Attribute("age_ranges".to_owned())),
+            FacetAttribute::searchable(Attribute("affiliations".to_owned())),
+            FacetAttribute::searchable(Attribute("categories".to_owned())),
+            FacetAttribute::filter_only(Attribute("language".to_owned())),
+            FacetAttribute::filter_only(Attribute("module_kinds".to_owned())),
+        ]),
+    };
+
+    Box::pin(async move {
+        client.set_settings(jig_index, &settings).await?;
+        Ok(())
+    })
+}
+
+fn set_jig_searchable_fields_v1<'a>(
+    client: &'a super::Inner,
+    _media_index: &'a str,
+    jig_index: &'a str,
+) -> BoxFuture<'a, anyhow::Result<()>> {
+    let settings = SetSettings {
+        searchable_attributes: Some(
+            SearchableAttributes::build()
+                .single(Attribute("name".to_owned()))
+                .multi(vec![
+                    Attribute("category_names".to_owned()),
+                    Attribute("age_range_names".to_owned()),
+                    Attribute("affiliation_names".to_owned()),
+                    Attribute("goal_names".to_owned()),
+                ])
+                .single(Attribute("module_text".to_owned()))
+                .finish(),
+        ),
+        attributes_for_faceting: None,
+    };
+
+    Box::pin(async move {
+        client.set_settings(jig_index, &settings).await?;
+        Ok(())
+    })
+}
+
 fn add_jig_index<'a>(
     client: &'a super::Inner,
     _media_index: &'a str,
@@ -182,6 +252,16 @@ pub const INDEXING_MIGRATIONS: &[(ResyncKind, MigrateFunction)] = &[
     (ResyncKind::None, set_searchable_fields_v3),
     (ResyncKind::None, set_attributes_for_faceting_v5),
     (ResyncKind::None, add_jig_index),
+    (ResyncKind::None, set_attributes_for_faceting_v6),
+    (ResyncKind::None, set_jig_attributes_for_faceting_v2),
+    (ResyncKind::Complete, set_jig_attributes_for_faceting_v3),
+    (ResyncKind::Complete, set_jig_searchable_fields_v1),
+    // records are normalized with `shared::search::normalize`.
+    (ResyncKind::Complete, empty),
+    // records have `last_edited`, for the jig sort replicas.
+    (ResyncKind::Complete, empty),
+    // category, goal, age range, affiliation, style and tag names are normalized too.
+    (ResyncKind::Complete, empty),
 ];
 
 pub const INDEX_VERSION: i16 = INDEXING_MIGRATIONS.len() as i16;
@@ -0,0 +1,173 @@
+//! Transcodes uploaded audio to MP3 with `ffmpeg` (which needs to be installed).
+//!
+//! Only the containers in [`INPUT_FORMATS`] are accepted: WAV, OGG, WebM/Opus (what browsers record), M4A and MP3.
+//! `ffmpeg` can read a lot more than that, but every other demuxer is attack surface we don't need.
+
+use std::{process::Stdio, time::Duration};
+
+use tokio::{io::AsyncWriteExt, process::Command};
+use uuid::Uuid;
+
+use crate::error;
+
+/// Trims leading and trailing silence (trailing silence is trimmed as the leading silence of the reversed audio),
+/// then normalizes loudness to -16 LUFS (a common target for speech on the web).
+const FILTERS: &str = "silenceremove=start_periods=1:start_threshold=-50dB,areverse,\
+                       silenceremove=start_periods=1:start_threshold=-50dB,areverse,\
+                       loudnorm=I=-16:TP=-1.5:LRA=11";
+
+/// The demuxers `ffmpeg` may use for uploads (`matroska,webm` and `mov,mp4,m4a,3gp,3g2,mj2` are single demuxers).
+const INPUT_FORMATS: &str = "wav,ogg,matroska,webm,mov,mp4,mp3";
+
+/// The sample rate audio is decoded at to be measured, plenty for a waveform.
+const MEASURE_SAMPLE_RATE: u64 = 8000;
+
+pub struct ProcessedAudio {
+    pub mp3: Vec<u8>,
+    pub duration_ms: u32,
+    pub waveform: Vec<f32>,
+}
+
+pub async fn process(data: &[u8]) -> Result<ProcessedAudio, error::Upload> {
+    // some containers (like M4A) can't be read from a pipe, since their index can be at the end.
+    let input = std::env::temp_dir().join(format!("audio-{}", Uuid::new_v4().to_hyphenated()));
+
+    tokio::fs::write(&input, data).await?;
+
+    let mut command = ffmpeg(INPUT_FORMATS);
+    command.arg("-i").arg(&input).args(&[
+        "-vn",
+        "-af",
+        FILTERS,
+        "-codec:a",
+        "libmp3lame",
+        "-q:a",
+        "4",
+        "-f",
+        "mp3",
+        "pipe:1",
+    ]);
+
+    let mp3 = run(command, None).await;
+
+    if let Err(e) = tokio::fs::remove_file(&input).await {
+        log::warn!("failed to remove {}: {}", input.display(), e);
+    }
+
+    let mp3 = mp3?;
+
+    let mut command = ffmpeg("mp3");
+    command
+        .args(&["-i", "pipe:0", "-f", "s16le", "-ac", "1", "-ar"])
+        .arg(MEASURE_SAMPLE_RATE.to_string())
+        .arg("pipe:1");
+
+    let pcm = run(command, Some(&mp3)).await?;
+
+    let samples: Vec<i16> = pcm
+        .chunks_exact(2)
+        .map(|it| i16::from_le_bytes([it[0], it[1]]))
+        .collect();
+
+    // nothing but silence.
+    if samples.is_empty() {
+        return Err(error::Upload::InvalidMedia);
+    }
+
+    let duration_ms = (samples.len() as u64 * 1000 / MEASURE_SAMPLE_RATE) as u32;
+
+    Ok(ProcessedAudio {
+        mp3,
+        duration_ms,
+        waveform: waveform(&samples),
+    })
+}
+
+/// Splits the samples into (at most) [`AUDIO_WAVEFORM_POINTS`](config::AUDIO_WAVEFORM_POINTS) slices,
+/// and returns the peak of each (from 0 to 1).
+fn waveform(samples: &[i16]) -> Vec<f32> {
+    let points = config::AUDIO_WAVEFORM_POINTS.min(samples.len());
+
+    (0..points)
+        .map(|point| {
+            let slice =
+                &samples[point * samples.len() / points..(point + 1) * samples.len() / points];
+
+            let peak = slice
+                .iter()
+                .map(|sample| i32::from(*sample).abs())
+                .max()
+                .unwrap_or(0);
+
+            peak as f32 / 32768.0
+        })
+        .collect()
+}
+
+/// An `ffmpeg` command that can only read its input with the `formats` demuxers, from a file or a pipe.
+fn ffmpeg(formats: &str) -> Command {
+    let mut command = Command::new("ffmpeg");
+    command
+        .args(&["-hide_banner", "-loglevel", "error"])
+        .args(&["-protocol_whitelist", "file,pipe"])
+        .arg("-format_whitelist")
+        .arg(formats);
+    command
+}
+
+/// Runs the `ffmpeg` command, writing `input` to its stdin, and returns what it wrote to stdout.
+///
+/// Fails with [`InvalidMedia`](error::Upload::InvalidMedia) if `ffmpeg` fails, which is almost always because it can't read the input,
+/// or if it takes longer than [`AUDIO_PROCESSING_TIMEOUT_SECS`](config::AUDIO_PROCESSING_TIMEOUT_SECS) (it's killed).
+async fn run(mut command: Command, input: Option<&[u8]>) -> Result<Vec<u8>, error::Upload> {
+    let mut child = command
+        .kill_on_drop(true)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    let stdin = child.stdin.take();
+
+    let write = async move {
+        if let (Some(mut stdin), Some(input)) = (stdin, input) {
+            // ffmpeg stops reading when it fails, which is reported by its exit status instead.
+            let _ = stdin.write_all(input).await;
+        }
+    };
+
+    // dropping the child (when it times out) kills it.
+    let output = tokio::time::timeout(
+        Duration::from_secs(config::AUDIO_PROCESSING_TIMEOUT_SECS),
+        futures::future::join(child.wait_with_output(), write),
+    )
+    .await;
+
+    let output = match output {
+        Ok((output, ())) => output?,
+        Err(_) => {
+            log::warn!(
+                "ffmpeg took longer than {}s, killed it",
+                config::AUDIO_PROCESSING_TIMEOUT_SECS
+            );
+
+            return Err(error::Upload::InvalidMedia);
+        }
+    };
+
+    if !output.status.success() {
+        log::debug!(
+            "ffmpeg failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        return Err(error::Upload::InvalidMedia);
+    }
+
+    Ok(output.stdout)
+}
@@ -45,8 +45,8 @@ use ji_cloud_api::{
         event_arc::{self, audit_log, EventResource, EventSource},
         notifications, uploads, ServiceData,
     },
+    storage::CloudStorage,
 };
-use shared::media::{FileKind, MediaLibrary, PngImageFile};
 use sqlx::PgPool;
 use std::convert::TryFrom;
 use std::str::FromStr;
@@ -57,7 +57,7 @@ async fn main() -> anyhow::Result<()> {
 
     logger::init()?;
 
-    let (s3, event_arc, notifications, db_pool, runtime_settings) = {
+    let (storage, event_arc, notifications, db_pool, runtime_settings) = {
         log::trace!("initializing settings and processes");
         let remote_target = settings::read_remote_target()?;
 
@@ -70,11 +70,12 @@ async fn main() -> anyhow::Result<()> {
         )
         .await?;
 
-        let s3 = settings
+        let storage = settings
             .s3_settings()
             .await?
             .map(s3::Client::new)
-            .transpose()?;
+            .transpose()?
+            .map(|s3| CloudStorage::new(s3, None));
 
         let event_arc = settings
             .google_cloud_eventarc_settings()
@@ -90,11 +91,11 @@ async fn main() -> anyhow::Result<()> {
 
         let runtime_settings = settings.runtime_settings().await?;
 
-        (s3, event_arc, notifications, db_pool, runtime_settings)
+        (storage, event_arc, notifications, db_pool, runtime_settings)
     };
 
     let handle = std::thread::spawn(|| {
-        build_and_run_media_watch(db_pool, runtime_settings, s3, event_arc, notifications)
+        build_and_run_media_watch(db_pool, runtime_settings, storage, event_arc, notifications)
     });
 
     log::info!("media watch started!");
@@ -110,11 +111,11 @@ async fn main() -> anyhow::Result<()> {
 pub async fn build_and_run_media_watch(
     db_pool: PgPool,
     runtime_settings: RuntimeSettings,
-    s3: Option<s3::Client>,
+    storage: Option<CloudStorage>,
     event_arc: Option<event_arc::Client>,
     notifications: Option<notifications::Client>,
 ) -> anyhow::Result<()> {
-    let app = build_media_watch(runtime_settings, db_pool, s3, event_arc, notifications)?;
+    let app = build_media_watch(runtime_settings, db_pool, storage, event_arc, notifications)?;
     app.run_until_stopped().await?;
 
     Ok(())
@@ -123,14 +124,14 @@ pub async fn build_and_run_media_watch(
 fn build_media_watch(
     runtime_settings: RuntimeSettings,
     db_pool: PgPool,
-    s3: Option<s3::Client>,
+    storage: Option<CloudStorage>,
     event_arc: Option<event_arc::Client>,
     notifications: Option<notifications::Client>,
 ) -> anyhow::Result<Application> {
     // let local_insecure = runtime_settings.is_local();
     let media_watch_port = runtime_settings.media_watch_port;
 
-    let s3 = s3.map(ServiceData::new);
+    let storage = storage.map(ServiceData::new);
     let event_arc = event_arc.map(ServiceData::new);
     let notifications = notifications.map(ServiceData::new);
 
@@ -142,8 +143,8 @@ fn build_media_watch(
             .data(db_pool.clone())
             .data(runtime_settings.clone());
 
-        let server = match s3.clone() {
-            Some(s3) => server.app_data(s3),
+        let server = match storage.clone() {
+            Some(storage) => server.app_data(storage),
             None => server,
         };
 
@@ -190,7 +191,7 @@ fn build_media_watch(
 #[post("/v1/media-watch")]
 async fn process_uploaded_media_trigger(
     db: Data<PgPool>,
-    s3: ServiceData<s3::Client>,
+    storage: ServiceData<CloudStorage>,
     fcm: ServiceData<notifications::Client>,
     event_arc: ServiceData<event_arc::Client>,
     event: Event,
@@ -216,7 +217,7 @@ async fn process_uploaded_media_trigger(
     }
 
     let event_data: audit_log::Data = event.try_decode_event_payload()?;
-    if event_data.resource.labels.bucket_name != s3.processing_bucket() {
+    if event_data.resource.labels.bucket_name != storage.processing_bucket() {
         return Err(Error::InvalidEventSource);
     }
 
@@ -224,21 +225,16 @@ async fn process_uploaded_media_trigger(
         EventResource::from_str(&event_data.proto_payload.resource_name)?;
 
     // TODO: use gcs instead of S3
-    let res = match event_resource.file_kind {
-        FileKind::ImagePng(PngImageFile::Original) => match event_resource.library {
-            MediaLibrary::Global => uploads::process_image(&db, &s3, event_resource.id)
-                .await
-                .map_err(|_| Error::NotProcessed)?,
-            MediaLibrary::User => uploads::process_user_image(&db, &s3, event_resource.id)
-                .await
-                .map_err(|_| Error::NotProcessed)?,
-            _ => return Err(Error::InvalidEventResource),
-        },
-        FileKind::AnimationGif => uploads::process_animation(&db, &s3, event_resource.id)
-            .await
-            .map_err(|_| Error::NotProcessed)?,
-        _ => return Err(Error::InvalidEventResource),
-    };
+    let res = uploads::process(
+        &db,
+        &**storage,
+        event_resource.library,
+        event_resource.id,
+        event_resource.file_kind,
+    )
+    .await
+    .map_err(|_| Error::NotProcessed)?
+    .ok_or(Error::InvalidEventResource)?;
 
     if res == true {
         uploads::finalize_upload(
@@ -2,11 +2,19 @@ pub(crate) mod additional_resource;
 pub(crate) mod animation;
 pub(crate) mod audio;
 pub(crate) mod category;
+pub(crate) mod class;
 pub(crate) mod image;
 pub(crate) mod jig;
+pub(crate) mod jig_collaborator;
+pub(crate) mod jig_version;
 pub(crate) mod locale;
 pub(crate) mod meta;
 pub(crate) mod module;
+pub(crate) mod module_media_ref;
+pub(crate) mod module_text;
+pub(crate) mod player_session;
+pub mod saved_search;
+pub(crate) mod search;
 pub(crate) mod session;
 pub(crate) mod user;
 
@@ -33,6 +41,16 @@ pub async fn get_pool(connect_options: PgConnectOptions) -> anyhow::Result<PgPoo
     Ok(pool)
 }
 
+/// The outcome of an update that can be conditional on the current revision of what's being updated.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum RevisionUpdate {
+    /// The update was applied, giving this new revision.
+    Updated(u32),
+
+    /// The current revision wasn't one of the expected revisions, so nothing was changed.
+    Conflict,
+}
+
 trait Metadata: Into<Uuid> + Copy {
     const TABLE: &'static str;
 }
@@ -1,10 +1,19 @@
 use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
 use shared::domain::animation::{AnimationId, AnimationKind, AnimationMetadata};
 use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
 
 pub async fn delete(db: &PgPool, animation: AnimationId) -> sqlx::Result<Option<AnimationKind>> {
     let mut conn = db.begin().await?;
 
+    sqlx::query!(
+        "delete from global_animation_upload where animation_id = $1",
+        animation.0
+    )
+    .execute(&mut conn)
+    .await?;
+
     let res = sqlx::query!(
         r#"delete from animation_metadata where id = $1 returning kind as "kind: AnimationKind""#,
         animation.0
@@ -38,10 +47,17 @@ returning id as "id: AnimationId"
         kind as i16,
         is_looping,
     )
-    .fetch_one(conn)
+    .fetch_one(&mut *conn)
     .await?
     .id;
 
+    sqlx::query!(
+        "insert into global_animation_upload (animation_id) values ($1)",
+        id.0
+    )
+    .execute(&mut *conn)
+    .await?;
+
     Ok(id)
 }
 
@@ -65,3 +81,24 @@ where id = $1
     .fetch_optional(db)
     .await
 }
+
+pub fn get<'a>(db: &'a PgPool, ids: &'a [Uuid]) -> BoxStream<'a, sqlx::Result<AnimationMetadata>> {
+    sqlx::query_as(
+        r#"
+select  id,
+        name,
+        description,
+        is_premium,
+        publish_at,
+        created_at,
+        updated_at,
+        kind,
+        looping         as is_looping,
+        array((select row (style_id) from animation_style where animation_id = animation_metadata.id)) as styles
+from animation_metadata
+inner join unnest($1::uuid[]) with ordinality t(id, ord) USING (id)
+order by t.ord
+"#)
+    .bind(ids)
+    .fetch(db)
+}
@@ -1,14 +1,16 @@
 pub mod user {
-    use futures::stream::BoxStream;
+    use futures::stream::{BoxStream, StreamExt};
     use shared::domain::audio::{user::UserAudio, AudioId};
-    use sqlx::PgPool;
+    use sqlx::{PgConnection, PgPool};
+    use uuid::Uuid;
 
-    pub async fn create(conn: &PgPool) -> sqlx::Result<AudioId> {
+    pub async fn create(conn: &PgPool, user_id: Uuid) -> sqlx::Result<AudioId> {
         let id: AudioId = sqlx::query!(
             r#"
-insert into user_audio_library default values
+insert into user_audio_library (user_id) values ($1)
 returning id as "id: AudioId"
 "#,
+            user_id
         )
         .fetch_one(conn)
         .await?
@@ -17,6 +19,25 @@ returning id as "id: AudioId"
         Ok(id)
     }
 
+    /// Marks an audio file as uploaded, along with what was measured while processing it.
+    pub async fn set_uploaded(
+        conn: &mut PgConnection,
+        audio: AudioId,
+        duration_ms: u32,
+        waveform: &[f32],
+    ) -> sqlx::Result<()> {
+        sqlx::query!(
+            "update user_audio_library set uploaded_at = now(), duration_ms = $2, waveform = $3 where id = $1",
+            audio.0,
+            duration_ms as i32,
+            waveform,
+        )
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
     pub async fn delete(db: &PgPool, image: AudioId) -> sqlx::Result<()> {
         sqlx::query!("delete from user_audio_library where id = $1", image.0)
             .execute(db)
@@ -25,20 +46,118 @@ returning id as "id: AudioId"
     }
 
     pub async fn get(db: &PgPool, image: AudioId) -> sqlx::Result<Option<UserAudio>> {
-        sqlx::query_as!(
-            UserAudio,
-            r#"select id as "id: AudioId" from user_audio_library where id = $1"#,
+        sqlx::query!(
+            r#"select id as "id: AudioId", name, tags, duration_ms, waveform from user_audio_library where id = $1"#,
             image.0
         )
         .fetch_optional(db)
         .await
+        .map(|it| {
+            it.map(|row| UserAudio {
+                id: row.id,
+                name: row.name,
+                tags: row.tags,
+                duration_ms: row.duration_ms.map(|it| it as u32),
+                waveform: row.waveform,
+            })
+        })
     }
 
     pub fn list(db: &PgPool) -> BoxStream<'_, sqlx::Result<UserAudio>> {
-        sqlx::query_as!(
-            UserAudio,
-            r#"select id as "id: AudioId" from user_audio_library order by created_at desc"#,
+        sqlx::query!(
+            r#"select id as "id: AudioId", name, tags, duration_ms, waveform from user_audio_library order by created_at desc"#,
         )
         .fetch(db)
+        .map(|it| {
+            it.map(|row| UserAudio {
+                id: row.id,
+                name: row.name,
+                tags: row.tags,
+                duration_ms: row.duration_ms.map(|it| it as u32),
+                waveform: row.waveform,
+            })
+        })
+        .boxed()
+    }
+
+    /// Returns `false` if the audio file doesn't exist, or `user_id` didn't upload it.
+    pub async fn update(
+        db: &PgPool,
+        user_id: Uuid,
+        audio: AudioId,
+        name: Option<&str>,
+        tags: Option<&[String]>,
+    ) -> sqlx::Result<bool> {
+        sqlx::query!(
+            r#"
+update user_audio_library
+set name = coalesce($3, name),
+    tags = coalesce($4, tags)
+where id = $1 and user_id = $2
+"#,
+            audio.0,
+            user_id,
+            name,
+            tags,
+        )
+        .execute(db)
+        .await
+        .map(|it| it.rows_affected() > 0)
+    }
+
+    /// Searches the audio files `user_id` created by name and tags, returns a page of audio files and the total number of hits.
+    pub async fn search(
+        db: &PgPool,
+        user_id: Uuid,
+        query: &str,
+        page: u32,
+        page_size: u32,
+    ) -> sqlx::Result<(Vec<UserAudio>, u64)> {
+        let mut txn = db.begin().await?;
+
+        let audio_files = sqlx::query!(
+            r#"
+select id as "id: AudioId", name, tags, duration_ms, waveform
+from user_audio_library
+where user_id = $1
+  and ($2 = '' or to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))) @@ websearch_to_tsquery('simple', $2))
+order by ts_rank(to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))), websearch_to_tsquery('simple', $2)) desc,
+         created_at desc
+limit $3 offset $4
+"#,
+            user_id,
+            query,
+            i64::from(page_size),
+            i64::from(page * page_size),
+        )
+        .fetch_all(&mut txn)
+        .await?
+        .into_iter()
+        .map(|row| UserAudio {
+            id: row.id,
+            name: row.name,
+            tags: row.tags,
+            duration_ms: row.duration_ms.map(|it| it as u32),
+            waveform: row.waveform,
+        })
+        .collect();
+
+        let total = sqlx::query!(
+            r#"
+select count(*) as "count!"
+from user_audio_library
+where user_id = $1
+  and ($2 = '' or to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))) @@ websearch_to_tsquery('simple', $2))
+"#,
+            user_id,
+            query,
+        )
+        .fetch_one(&mut txn)
+        .await?
+        .count;
+
+        txn.rollback().await?;
+
+        Ok((audio_files, total as u64))
     }
 }
@@ -0,0 +1,123 @@
+use shared::domain::{
+    class::{Class, ClassId},
+    user::UserScope,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error;
+
+pub mod assignment;
+
+pub async fn create(pool: &PgPool, name: &str, creator_id: Uuid) -> sqlx::Result<ClassId> {
+    sqlx::query!(
+        r#"insert into class (name, creator_id) values ($1, $2) returning id as "id: ClassId""#,
+        name,
+        creator_id,
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.id)
+}
+
+pub async fn get(pool: &PgPool, id: ClassId) -> sqlx::Result<Option<Class>> {
+    sqlx::query_as!(
+        Class,
+        r#"
+select id as "id: ClassId",
+       name,
+       creator_id,
+       created_at,
+       updated_at
+from class
+where id = $1
+"#,
+        id.0
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Returns `false` if the class doesn't exist.
+pub async fn update(pool: &PgPool, id: ClassId, name: Option<&str>) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"
+update class
+set name = coalesce($2, name),
+    updated_at = case when $2 is not null and $2 is distinct from name then now() else updated_at end
+where id = $1
+"#,
+        id.0,
+        name,
+    )
+    .execute(pool)
+    .await
+    .map(|it| it.rows_affected() != 0)
+}
+
+pub async fn delete(pool: &PgPool, id: ClassId) -> sqlx::Result<()> {
+    sqlx::query!("delete from class where id = $1", id.0)
+        .execute(pool)
+        .await
+        .map(drop)
+}
+
+pub async fn list(pool: &PgPool, creator_id: Uuid, page: i32) -> sqlx::Result<Vec<Class>> {
+    sqlx::query_as!(
+        Class,
+        r#"
+select id as "id: ClassId",
+       name,
+       creator_id,
+       created_at,
+       updated_at
+from class
+where creator_id = $1
+order by created_at desc
+limit 20 offset 20 * $2
+"#,
+        creator_id,
+        page,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+pub async fn count(pool: &PgPool, creator_id: Uuid) -> sqlx::Result<u64> {
+    sqlx::query!(
+        r#"select count(*) as "count!" from class where creator_id = $1"#,
+        creator_id
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.count as u64)
+}
+
+/// Checks that the user can manage the given class.
+///
+/// Classes that don't exist are allowed through, so that they can be reported as such.
+pub async fn authz(pool: &PgPool, user_id: Uuid, class_id: ClassId) -> Result<(), error::Auth> {
+    let authed = sqlx::query!(
+        r#"
+select exists (
+    select 1 from user_scope where user_id = $1 and scope = $2
+) or (
+    exists (select 1 from user_scope where user_id = $1 and scope = $3) and
+    not exists (select 1 from class where class.id = $4 and class.creator_id <> $1)
+) as "authed!"
+"#,
+        user_id,
+        UserScope::Admin as i16,
+        UserScope::ManageClass as i16,
+        class_id.0
+    )
+    .fetch_one(pool)
+    .await?
+    .authed;
+
+    if !authed {
+        return Err(error::Auth::Forbidden);
+    }
+
+    Ok(())
+}
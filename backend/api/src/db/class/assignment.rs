@@ -0,0 +1,253 @@
+use chrono::{DateTime, Utc};
+use shared::domain::{
+    class::{
+        assignment::{Assignment, AssignmentId, AssignmentStudent, AssignmentStudentId},
+        ClassId,
+    },
+    jig::JigId,
+};
+use sqlx::{PgConnection, PgPool};
+
+use crate::error;
+
+/// Characters that can't easily be confused with each other when read aloud or written on a board.
+const CODE_CHARSET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LEN: usize = 6;
+
+/// How many times to try generating a code before giving up.
+const CODE_ATTEMPTS: usize = 5;
+
+#[must_use]
+fn generate_code() -> String {
+    use rand::Rng;
+
+    let mut rng = rand::thread_rng();
+
+    (0..CODE_LEN)
+        .map(|_| CODE_CHARSET[rng.gen_range(0..CODE_CHARSET.len())] as char)
+        .collect()
+}
+
+pub async fn create(
+    pool: &PgPool,
+    class_id: ClassId,
+    jig_id: JigId,
+    due_at: Option<DateTime<Utc>>,
+) -> Result<AssignmentId, error::NotFound> {
+    let mut txn = pool.begin().await?;
+
+    let exists = sqlx::query!(
+        r#"
+select exists(select 1 from class where id = $1) and exists(select 1 from jig where id = $2) as "exists!"
+"#,
+        class_id.0,
+        jig_id.0
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .exists;
+
+    if !exists {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    for _ in 0..CODE_ATTEMPTS {
+        let id = sqlx::query!(
+            r#"
+insert into assignment (class_id, jig_id, code, due_at)
+values ($1, $2, $3, $4)
+on conflict (code) do nothing
+returning id as "id: AssignmentId"
+"#,
+            class_id.0,
+            jig_id.0,
+            generate_code(),
+            due_at,
+        )
+        .fetch_optional(&mut txn)
+        .await?;
+
+        if let Some(it) = id {
+            txn.commit().await?;
+            return Ok(it.id);
+        }
+    }
+
+    Err(anyhow::anyhow!("failed to generate a unique assignment code").into())
+}
+
+pub async fn get(
+    pool: &PgPool,
+    class_id: ClassId,
+    id: AssignmentId,
+) -> sqlx::Result<Option<Assignment>> {
+    sqlx::query_as!(
+        Assignment,
+        r#"
+select id as "id: AssignmentId",
+       class_id as "class_id: ClassId",
+       jig_id as "jig_id: JigId",
+       code,
+       created_at,
+       due_at
+from assignment
+where id = $1 and class_id = $2
+"#,
+        id.0,
+        class_id.0,
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+pub async fn list(pool: &PgPool, class_id: ClassId) -> sqlx::Result<Vec<Assignment>> {
+    sqlx::query_as!(
+        Assignment,
+        r#"
+select id as "id: AssignmentId",
+       class_id as "class_id: ClassId",
+       jig_id as "jig_id: JigId",
+       code,
+       created_at,
+       due_at
+from assignment
+where class_id = $1
+order by created_at desc
+"#,
+        class_id.0,
+    )
+    .fetch_all(pool)
+    .await
+}
+
+/// Returns `false` if the assignment doesn't exist.
+pub async fn update(
+    pool: &PgPool,
+    class_id: ClassId,
+    id: AssignmentId,
+    due_at: Option<Option<DateTime<Utc>>>,
+) -> sqlx::Result<bool> {
+    let mut txn = pool.begin().await?;
+
+    let exists = sqlx::query!(
+        r#"select exists(select 1 from assignment where id = $1 and class_id = $2 for update) as "exists!""#,
+        id.0,
+        class_id.0,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .exists;
+
+    if !exists {
+        return Ok(false);
+    }
+
+    if let Some(due_at) = due_at {
+        sqlx::query!(
+            r#"
+update assignment
+set due_at = $2, updated_at = now()
+where id = $1 and $2 is distinct from due_at
+"#,
+            id.0,
+            due_at,
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(true)
+}
+
+pub async fn delete(pool: &PgPool, class_id: ClassId, id: AssignmentId) -> sqlx::Result<()> {
+    sqlx::query!(
+        "delete from assignment where id = $1 and class_id = $2",
+        id.0,
+        class_id.0,
+    )
+    .execute(pool)
+    .await
+    .map(drop)
+}
+
+/// Adds a student to the assignment with the given code.
+///
+/// Codes are matched case insensitively, since students will be typing them in by hand.
+pub async fn join(
+    conn: &mut PgConnection,
+    code: &str,
+    name: &str,
+) -> sqlx::Result<Option<(AssignmentStudentId, AssignmentId, JigId)>> {
+    sqlx::query!(
+        r#"
+with student as (
+    insert into assignment_student (assignment_id, name)
+    select id, $2 from assignment where code = upper(trim($1))
+    returning id, assignment_id
+)
+select student.id as "student_id!: AssignmentStudentId",
+       assignment.id as "assignment_id!: AssignmentId",
+       assignment.jig_id as "jig_id!: JigId"
+from student
+inner join assignment on assignment.id = student.assignment_id
+"#,
+        code,
+        name,
+    )
+    .fetch_optional(conn)
+    .await
+    .map(|it| it.map(|it| (it.student_id, it.assignment_id, it.jig_id)))
+}
+
+/// Lists the students who joined an assignment, along with their play sessions.
+pub async fn results(pool: &PgPool, id: AssignmentId) -> sqlx::Result<Vec<AssignmentStudent>> {
+    let students = sqlx::query!(
+        r#"
+select id as "id: AssignmentStudentId",
+       name,
+       joined_at
+from assignment_student
+where assignment_id = $1
+order by joined_at
+"#,
+        id.0,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut sessions = super::super::player_session::list_for_assignment(pool, id).await?;
+
+    Ok(students
+        .into_iter()
+        .map(|student| AssignmentStudent {
+            id: student.id,
+            name: student.name,
+            joined_at: student.joined_at,
+            sessions: sessions.remove(&student.id.0).unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Checks that a student who joined an assignment is playing the jig they were assigned.
+pub async fn authz_student(
+    pool: &PgPool,
+    assignment_id: AssignmentId,
+    jig_id: JigId,
+) -> Result<(), error::Auth> {
+    let authed = sqlx::query!(
+        r#"select exists(select 1 from assignment where id = $1 and jig_id = $2) as "authed!""#,
+        assignment_id.0,
+        jig_id.0,
+    )
+    .fetch_one(pool)
+    .await?
+    .authed;
+
+    if !authed {
+        return Err(error::Auth::Forbidden);
+    }
+
+    Ok(())
+}
@@ -219,6 +219,123 @@ order by t.ord
     .fetch(db)
 }
 
+/// Gets the ids of (at most `limit`) published images related to the image, most related first.
+///
+/// Shared tags count more than shared styles, images that have neither in common with it are left out.
+pub async fn related(db: &PgPool, id: ImageId, limit: u16) -> sqlx::Result<Vec<Uuid>> {
+    sqlx::query!(
+        r#"
+select image_metadata.id as "id!"
+from image_metadata,
+     lateral (
+         select 2 * (select count(*)
+                     from image_tag_join
+                     where image_id = image_metadata.id
+                       and tag_id in (select tag_id from image_tag_join where image_id = $1)) +
+                (select count(*)
+                 from image_style
+                 where image_id = image_metadata.id
+                   and style_id in (select style_id from image_style where image_id = $1)) as score
+     ) as related
+where image_metadata.id <> $1
+  and image_metadata.publish_at < now()
+  and related.score > 0
+order by related.score desc, image_metadata.publish_at desc
+limit $2
+"#,
+        id.0,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.id).collect())
+}
+
+/// Gets the ids of (at most `limit`) images whose perceptual hash is within `max_distance` bits of the image's, most similar first.
+///
+/// Images that haven't been hashed yet are left out (and have no similar images).
+pub async fn similar(
+    db: &PgPool,
+    id: ImageId,
+    max_distance: u32,
+    limit: u16,
+) -> sqlx::Result<Vec<Uuid>> {
+    sqlx::query!(
+        r#"
+select image_id as "id!"
+from image_upload,
+     lateral (select hash_distance(perceptual_hash,
+                                   (select perceptual_hash from image_upload where image_id = $1)) as distance
+     ) as hash
+where image_id <> $1
+  and hash.distance <= $2
+order by hash.distance, image_id
+limit $3
+"#,
+        id.0,
+        max_distance as i32,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.id).collect())
+}
+
+/// Gets (at most `limit`) pairs of images whose perceptual hashes are within `max_distance` bits of each other, closest first.
+///
+/// Rather than comparing every pair of images, the hashes are split into more segments than `max_distance`,
+/// so a pair of hashes within it has at least one segment in common, and only images sharing a segment are compared.
+pub async fn duplicates(
+    db: &PgPool,
+    max_distance: u32,
+    limit: u16,
+) -> sqlx::Result<Vec<(ImageId, ImageId, u32)>> {
+    // every segment is at least 1 bit, and at most 32.
+    let segments = (max_distance.min(63) + 1).max(2);
+
+    sqlx::query!(
+        r#"
+with segment as (
+    select image_id,
+           perceptual_hash                                                  as hash,
+           s.index,
+           (perceptual_hash >> (s.index * 64 / $3))
+               & ((1::bigint << ((s.index + 1) * 64 / $3 - s.index * 64 / $3)) - 1) as value
+    from image_upload,
+         generate_series(0, $3 - 1) as s(index)
+    where perceptual_hash is not null
+),
+pair as (
+    select distinct image.image_id                          as image_id,
+                    duplicate.image_id                      as duplicate_id,
+                    hash_distance(image.hash, duplicate.hash) as distance
+    from segment as image
+             inner join segment as duplicate
+                        on image.index = duplicate.index
+                            and image.value = duplicate.value
+                            and image.image_id < duplicate.image_id
+)
+select image_id     as "image_id!: ImageId",
+       duplicate_id as "duplicate_id!: ImageId",
+       distance     as "distance!"
+from pair
+where distance <= $1
+order by distance, image_id, duplicate_id
+limit $2
+"#,
+        max_distance as i32,
+        i64::from(limit),
+        segments as i32,
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|it| (it.image_id, it.duplicate_id, it.distance as u32))
+            .collect()
+    })
+}
+
 pub async fn delete(db: &PgPool, image: ImageId) -> sqlx::Result<()> {
     let mut conn = db.begin().await?;
 
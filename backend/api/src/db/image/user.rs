@@ -1,14 +1,16 @@
 use futures::stream::BoxStream;
 use shared::domain::image::{user::UserImage, ImageId};
 use sqlx::PgPool;
+use uuid::Uuid;
 
-pub async fn create(pool: &PgPool) -> sqlx::Result<ImageId> {
+pub async fn create(pool: &PgPool, user_id: Uuid) -> sqlx::Result<ImageId> {
     let mut txn = pool.begin().await?;
     let id: ImageId = sqlx::query!(
         r#"
-insert into user_image_library default values
+insert into user_image_library (user_id) values ($1)
 returning id as "id: ImageId"
 "#,
+        user_id
     )
     .fetch_one(&mut txn)
     .await?
@@ -23,17 +25,37 @@ returning id as "id: ImageId"
     Ok(id)
 }
 
+/// Marks an image as uploaded and processed, for images whose files were generated without going through the upload processor.
+pub async fn set_processed(db: &PgPool, image: ImageId, perceptual_hash: i64) -> sqlx::Result<()> {
+    sqlx::query!(
+        "update user_image_upload set uploaded_at = now(), processed_at = now(), processing_result = true, perceptual_hash = $2 where image_id = $1",
+        image.0,
+        perceptual_hash
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
 pub async fn delete(db: &PgPool, image: ImageId) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!("delete from user_image_upload where image_id = $1", image.0)
+        .execute(&mut txn)
+        .await?;
+
     sqlx::query!("delete from user_image_library where id = $1", image.0)
-        .execute(db)
-        .await
-        .map(drop)
+        .execute(&mut txn)
+        .await?;
+
+    txn.commit().await
 }
 
 pub async fn get(db: &PgPool, image: ImageId) -> sqlx::Result<Option<UserImage>> {
     sqlx::query_as!(
         UserImage,
-        r#"select id as "id: ImageId" from user_image_library where id = $1"#,
+        r#"select id as "id: ImageId", name, tags from user_image_library where id = $1"#,
         image.0
     )
     .fetch_optional(db)
@@ -43,7 +65,113 @@ pub async fn get(db: &PgPool, image: ImageId) -> sqlx::Result<Option<UserImage>>
 pub fn list(db: &PgPool) -> BoxStream<'_, sqlx::Result<UserImage>> {
     sqlx::query_as!(
         UserImage,
-        r#"select id as "id: ImageId" from user_image_library order by created_at desc"#,
+        r#"select id as "id: ImageId", name, tags from user_image_library order by created_at desc"#,
     )
     .fetch(db)
 }
+
+/// Returns `false` if the image doesn't exist, or `user_id` didn't upload it.
+pub async fn update(
+    db: &PgPool,
+    user_id: Uuid,
+    image: ImageId,
+    name: Option<&str>,
+    tags: Option<&[String]>,
+) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"
+update user_image_library
+set name = coalesce($3, name),
+    tags = coalesce($4, tags)
+where id = $1 and user_id = $2
+"#,
+        image.0,
+        user_id,
+        name,
+        tags,
+    )
+    .execute(db)
+    .await
+    .map(|it| it.rows_affected() > 0)
+}
+
+/// Gets (at most `limit`) of the images `user_id` created whose perceptual hash is within `max_distance` bits of the image's,
+/// most similar first.
+pub async fn similar(
+    db: &PgPool,
+    user_id: Uuid,
+    image: ImageId,
+    max_distance: u32,
+    limit: u16,
+) -> sqlx::Result<Vec<UserImage>> {
+    sqlx::query_as!(
+        UserImage,
+        r#"
+select id as "id: ImageId", name, tags
+from user_image_library
+         inner join user_image_upload on user_image_library.id = user_image_upload.image_id,
+     lateral (select hash_distance(perceptual_hash,
+                                   (select perceptual_hash from user_image_upload where image_id = $2)) as distance
+     ) as hash
+where user_id = $1
+  and id <> $2
+  and hash.distance <= $3
+order by hash.distance, created_at desc
+limit $4
+"#,
+        user_id,
+        image.0,
+        max_distance as i32,
+        i64::from(limit),
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Searches the images `user_id` created by name and tags, returns a page of images and the total number of hits.
+pub async fn search(
+    db: &PgPool,
+    user_id: Uuid,
+    query: &str,
+    page: u32,
+    page_size: u32,
+) -> sqlx::Result<(Vec<UserImage>, u64)> {
+    let mut txn = db.begin().await?;
+
+    let images = sqlx::query_as!(
+        UserImage,
+        r#"
+select id as "id: ImageId", name, tags
+from user_image_library
+where user_id = $1
+  and ($2 = '' or to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))) @@ websearch_to_tsquery('simple', $2))
+order by ts_rank(to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))), websearch_to_tsquery('simple', $2)) desc,
+         created_at desc
+limit $3 offset $4
+"#,
+        user_id,
+        query,
+        i64::from(page_size),
+        i64::from(page * page_size),
+    )
+    .fetch_all(&mut txn)
+    .await?;
+
+    let total = sqlx::query!(
+        r#"
+select count(*) as "count!"
+from user_image_library
+where user_id = $1
+  and ($2 = '' or to_tsvector('simple', search_normalize(name || ' ' || array_to_string(tags, ' '))) @@ websearch_to_tsquery('simple', $2))
+"#,
+        user_id,
+        query,
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .count;
+
+    txn.rollback().await?;
+
+    Ok((images, total as u64))
+}
@@ -4,19 +4,21 @@ use shared::domain::{
     category::CategoryId,
     jig::{
         additional_resource::AdditionalResourceId,
+        collaborator::JigCollaboratorRole,
         module::{
             body::{cover, ThemeId},
-            ModuleBody, ModuleId,
+            Module, ModuleBody, ModuleId,
         },
         AudioBackground, AudioEffects, AudioFeedbackNegative, AudioFeedbackPositive, Jig, JigId,
-        LiteModule, ModuleKind, TextDirection,
+        JigSort, LiteModule, ModuleKind, TextDirection,
     },
     meta::{AffiliationId, AgeRangeId, GoalId},
     user::UserScope,
 };
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
 
+use super::RevisionUpdate;
 use crate::error;
 
 pub async fn create(
@@ -118,6 +120,10 @@ select
     direction as "direction: TextDirection",
     display_score,
     theme as "theme: ThemeId",
+    revision,
+    coalesce((select plays from jig_metrics where jig_id = jig.id), 0) as "plays!",
+    (select count(*) from jig_like where jig_id = jig.id) as "likes!",
+    (select count(*) from jig_view where jig_id = jig.id) as "views!",
     audio_background as "audio_background!: Option<AudioBackground>",
     array(select row(unnest(audio_feedback_positive))) as "audio_feedback_positive!: Vec<(AudioFeedbackPositive,)>", -- TODO: fix ugly!
     array(select row(unnest(audio_feedback_negative))) as "audio_feedback_negative!: Vec<(AudioFeedbackNegative,)>",
@@ -167,6 +173,10 @@ order by t.ord
                 .into_iter()
                 .map(|(it,)| it)
                 .collect(),
+            revision: row.revision as u32,
+            plays: row.plays as u64,
+            likes: row.likes as u64,
+            views: row.views as u64,
             audio_background: row.audio_background,
             audio_effects: AudioEffects {
                 feedback_positive: row
@@ -202,6 +212,10 @@ select
     direction as "direction: TextDirection",
     display_score,
     theme as "theme: ThemeId",
+    revision,
+    coalesce((select plays from jig_metrics where jig_id = jig.id), 0) as "plays!",
+    (select count(*) from jig_like where jig_id = jig.id) as "likes!",
+    (select count(*) from jig_view where jig_id = jig.id) as "views!",
     audio_background as "audio_background!: Option<AudioBackground>",
     array(select row(unnest(audio_feedback_positive))) as "audio_feedback_positive!: Vec<(AudioFeedbackPositive,)>", -- TODO: fix ugly!
     array(select row(unnest(audio_feedback_negative))) as "audio_feedback_negative!: Vec<(AudioFeedbackNegative,)>",
@@ -245,6 +259,10 @@ where id = $1"#,
             age_ranges: row.age_ranges.into_iter().map(|(it, )| it).collect(),
             affiliations: row.affiliations.into_iter().map(|(it, )| it).collect(),
             additional_resources: row.additional_resources.into_iter().map(|(it, )| it).collect(),
+            revision: row.revision as u32,
+            plays: row.plays as u64,
+            likes: row.likes as u64,
+            views: row.views as u64,
             audio_background: row.audio_background,
             audio_effects: AudioEffects {
                 feedback_positive: row.audio_feedback_positive.into_iter().map(|(it,)| it).collect(),
@@ -273,19 +291,26 @@ pub async fn update(
     theme: Option<ThemeId>,
     audio_background: Option<Option<AudioBackground>>,
     audio_effects: Option<AudioEffects>,
-) -> Result<(), error::UpdateWithMetadata> {
+    if_revision: Option<&[u32]>,
+) -> Result<RevisionUpdate, error::UpdateWithMetadata> {
     let mut transaction = pool.begin().await?;
-    if !sqlx::query!(
-        r#"select exists(select 1 from jig where id = $1 for update) as "exists!""#,
-        id.0
-    )
-    .fetch_one(&mut transaction)
-    .await?
-    .exists
-    {
-        return Err(error::UpdateWithMetadata::ResourceNotFound);
+
+    let revision = sqlx::query!(r#"select revision from jig where id = $1 for update"#, id.0)
+        .fetch_optional(&mut transaction)
+        .await?
+        .ok_or(error::UpdateWithMetadata::ResourceNotFound)?
+        .revision as u32;
+
+    if let Some(if_revision) = if_revision {
+        if !if_revision.contains(&revision) {
+            return Ok(RevisionUpdate::Conflict);
+        }
     }
 
+    sqlx::query!("update jig set revision = revision + 1 where id = $1", id.0)
+        .execute(&mut transaction)
+        .await?;
+
     if let Some(publish_at) = publish_at {
         sqlx::query!(
             r#"
@@ -400,7 +425,7 @@ where id = $1
 
     transaction.commit().await?;
 
-    Ok(())
+    Ok(RevisionUpdate::Updated(revision + 1))
 }
 
 pub async fn delete(pool: &PgPool, id: JigId) -> anyhow::Result<()> {
@@ -408,7 +433,31 @@ pub async fn delete(pool: &PgPool, id: JigId) -> anyhow::Result<()> {
         r#"
 with draft as (
     select draft_id as id from jig_draft_join where live_id = $1
-)
+),
+     player_session as (
+         delete from jig_player_session where jig_id = $1
+     ),
+     assignment as (
+         delete from assignment where jig_id = $1
+     ),
+     version as (
+         delete from jig_version where jig_id = $1
+     ),
+     collaborator as (
+         delete from jig_collaborator where jig_id = $1
+     ),
+     likes as (
+         delete from jig_like where jig_id = $1
+     ),
+     views as (
+         delete from jig_view where jig_id = $1
+     ),
+     plays as (
+         delete from jig_play where jig_id = $1
+     ),
+     metrics as (
+         delete from jig_metrics where jig_id = $1
+     )
 delete from jig where id = $1 or id = (select id from draft)
 "#,
         id.0
@@ -423,7 +472,10 @@ pub async fn list(
     pool: &sqlx::Pool<sqlx::Postgres>,
     is_published: Option<bool>,
     author_id: Option<Uuid>,
+    language: Option<&str>,
+    module_kinds: &[i16],
     page: i32,
+    sort: JigSort,
 ) -> sqlx::Result<Vec<Jig>> {
     log::info!("{:?}", author_id);
     sqlx::query!(
@@ -441,6 +493,10 @@ select
     direction as "direction: TextDirection",
     display_score,
     theme as "theme: ThemeId",
+    revision,
+    metrics.plays as "plays!",
+    metrics.likes as "likes!",
+    metrics.views as "views!",
     audio_background as "audio_background!: Option<AudioBackground>",
     array(select row(unnest(audio_feedback_positive))) as "audio_feedback_positive!: Vec<(AudioFeedbackPositive,)>", -- TODO: fix ugly!
     array(select row(unnest(audio_feedback_negative))) as "audio_feedback_negative!: Vec<(AudioFeedbackNegative,)>",
@@ -456,16 +512,32 @@ select
     array(select row(age_range_id) from jig_age_range where jig_id = jig.id) as "age_ranges!: Vec<(AgeRangeId,)>",
     array(select row(id) from jig_additional_resource where jig_id = jig.id) as "additional_resources!: Vec<(AdditionalResourceId,)>"
 from jig
+cross join lateral (
+    select coalesce((select plays from jig_metrics where jig_id = jig.id), 0) as plays,
+           (select count(*) from jig_like where jig_id = jig.id) as likes,
+           (select count(*) from jig_view where jig_id = jig.id) as views
+) metrics
 where 
     (publish_at < now() is not distinct from $1 or $1 is null)
     and (author_id is not distinct from $3 or $3 is null)
+    and (language = $5 or $5 is null)
+    and $6::smallint[] <@ array(select kind from jig_module where jig_id = jig.id)
     and jig.id not in (select draft_id as id from jig_draft_join) -- check explain. is this slow?
-order by coalesce(updated_at, created_at) desc
+order by case $4::smallint
+             when 1 then metrics.plays
+             when 2 then metrics.likes
+             when 3 then metrics.views
+             else 0
+         end desc,
+         coalesce(updated_at, created_at) desc
 limit 20 offset 20 * $2
 "#,
         is_published,
         page,
         author_id,
+        sort as i16,
+        language,
+        module_kinds,
     )
         .fetch(pool)
         .map_ok(|row| Jig {
@@ -491,6 +563,10 @@ limit 20 offset 20 * $2
             age_ranges: row.age_ranges.into_iter().map(|(it, )| it).collect(),
             affiliations: row.affiliations.into_iter().map(|(it, )| it).collect(),
             additional_resources: row.additional_resources.into_iter().map(|(it, )| it).collect(),
+            revision: row.revision as u32,
+            plays: row.plays as u64,
+            likes: row.likes as u64,
+            views: row.views as u64,
             audio_background: row.audio_background,
             audio_effects: AudioEffects {
                 feedback_positive: row.audio_feedback_positive.into_iter().map(|(it, )| it).collect(),
@@ -505,6 +581,8 @@ pub async fn filtered_count(
     db: &PgPool,
     is_published: Option<bool>,
     author_id: Option<Uuid>,
+    language: Option<&str>,
+    module_kinds: &[i16],
 ) -> sqlx::Result<u64> {
     sqlx::query!(
         r#"
@@ -513,10 +591,14 @@ from jig
 where
     (publish_at < now() is not distinct from $1 or $1 is null)
     and (author_id is not distinct from $2 or $2 is null)
+    and (language = $3 or $3 is null)
+    and $4::smallint[] <@ array(select kind from jig_module where jig_id = jig.id)
     and id not in (select draft_id as id from jig_draft_join) 
 "#,
         is_published,
         author_id,
+        language,
+        module_kinds,
     )
     .fetch_one(db)
     .await
@@ -572,8 +654,8 @@ returning id as "id: JigId"
 
     sqlx::query!(
         r#"
-insert into jig_module ("index", jig_id, kind, contents)
-select "index", $2 as "jig_id", kind, contents
+insert into jig_module ("index", jig_id, kind, contents, module_text)
+select "index", $2 as "jig_id", kind, contents, module_text
 from jig_module where jig_id = $1
 "#,
         parent.0,
@@ -637,11 +719,116 @@ select $1, url from jig_additional_resource where jig_id = $2
     .execute(&mut txn)
     .await?;
 
+    super::module_media_ref::sync_jig(&mut txn, JigId(new_id)).await?;
+    super::module_text::sync_jig(&mut txn, JigId(new_id)).await?;
+
     txn.commit().await?;
 
     Ok(JigId(new_id))
 }
 
+/// Creates a new jig owned by `creator_id` from an exported one.
+///
+/// Metadata that doesn't exist (in this environment) is dropped.
+pub async fn import(
+    db: &PgPool,
+    creator_id: Uuid,
+    jig: &Jig,
+    additional_resources: &[String],
+    modules: &[Module],
+) -> anyhow::Result<JigId> {
+    let mut txn = db.begin().await?;
+
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into jig (display_name, creator_id, author_id, language, description, direction, display_score, theme,
+                 audio_background, audio_feedback_positive, audio_feedback_negative)
+values ($1, $2, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+returning id as "id: JigId"
+"#,
+        jig.display_name,
+        creator_id,
+        jig.language,
+        jig.description,
+        jig.direction as i16,
+        jig.display_score,
+        jig.theme as i16,
+        jig.audio_background.clone().map(|it| it as i16),
+        &jig.audio_effects
+            .feedback_positive
+            .iter()
+            .map(|it| it.clone() as i16)
+            .collect::<Vec<_>>(),
+        &jig.audio_effects
+            .feedback_negative
+            .iter()
+            .map(|it| it.clone() as i16)
+            .collect::<Vec<_>>(),
+    )
+    .fetch_one(&mut txn)
+    .await?
+    .id;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with affiliation as (
+         insert into jig_affiliation (jig_id, affiliation_id)
+         select $1, id from affiliation where id = any($2)
+     ),
+     category as (
+         insert into jig_category (jig_id, category_id)
+         select $1, id from category where id = any($3)
+     ),
+     goal as (
+         insert into jig_goal (jig_id, goal_id)
+         select $1, id from goal where id = any($4)
+     ),
+     age_range as (
+         insert into jig_age_range (jig_id, age_range_id)
+         select $1, id from age_range where id = any($5)
+     )
+insert into jig_additional_resource (jig_id, url)
+select $1, unnest($6::text[])
+"#,
+        id.0,
+        &jig.affiliations.iter().map(|it| it.0).collect::<Vec<_>>(),
+        &jig.categories.iter().map(|it| it.0).collect::<Vec<_>>(),
+        &jig.goals.iter().map(|it| it.0).collect::<Vec<_>>(),
+        &jig.age_ranges.iter().map(|it| it.0).collect::<Vec<_>>(),
+        additional_resources,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    // todo: batch
+    for (idx, module) in modules.iter().enumerate() {
+        let (kind, contents) = super::module::map_module_contents(&module.body)?;
+
+        sqlx::query!(
+            r#"
+insert into jig_module (jig_id, "index", kind, contents, is_complete, module_text)
+values ($1, $2, $3, $4, $5, $6)"#,
+            id.0,
+            idx as i16,
+            kind as i16,
+            contents,
+            module.is_complete,
+            crate::module_text::module_text(&module.body),
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    super::module_media_ref::sync_jig(&mut txn, id).await?;
+    super::module_text::sync_jig(&mut txn, id).await?;
+
+    txn.commit().await?;
+
+    Ok(id)
+}
+
 pub async fn create_draft(db: &PgPool, live_id: JigId) -> Result<JigId, error::JigCloneDraft> {
     let mut txn = db.begin().await?;
 
@@ -712,8 +899,8 @@ values ($1, $2)
 
     sqlx::query!(
         r#"
-insert into jig_module ("index", jig_id, kind, contents)
-select "index", $1 as "jig_id", kind, contents
+insert into jig_module ("index", jig_id, kind, contents, module_text)
+select "index", $1 as "jig_id", kind, contents, module_text
 from jig_module where jig_id = $2
 "#,
         draft_id,
@@ -777,6 +964,9 @@ select $1, url from jig_additional_resource where jig_id = $2
     .execute(&mut txn)
     .await?;
 
+    super::module_media_ref::sync_jig(&mut txn, JigId(draft_id)).await?;
+    super::module_text::sync_jig(&mut txn, JigId(draft_id)).await?;
+
     txn.commit().await?;
 
     Ok(JigId(draft_id))
@@ -806,14 +996,277 @@ select draft_id as "id: JigId" from jig_draft_join where live_id = $1
     .ok_or(error::JigCloneDraft::ResourceNotFound)
 }
 
+/// Schedules the draft of `live_id` to be published at `publish_at`, or cancels the schedule if it's [`None`].
+pub async fn schedule_publish(
+    db: &PgPool,
+    live_id: JigId,
+    publish_at: Option<DateTime<Utc>>,
+    scheduled_by: Uuid,
+) -> sqlx::Result<()> {
+    match publish_at {
+        Some(publish_at) => {
+            sqlx::query!(
+                r#"
+insert into jig_draft_schedule (live_id, publish_at, scheduled_by)
+values ($1, $2, $3)
+on conflict (live_id) do update set publish_at = $2, scheduled_by = $3, created_at = now()
+"#,
+                live_id.0,
+                publish_at,
+                scheduled_by
+            )
+            .execute(db)
+            .await?;
+        }
+
+        None => {
+            sqlx::query!(
+                "delete from jig_draft_schedule where live_id = $1",
+                live_id.0
+            )
+            .execute(db)
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns when the draft of `live_id` is scheduled to be published, if it is.
+pub async fn get_publish_schedule(
+    db: &PgPool,
+    live_id: JigId,
+) -> sqlx::Result<Option<DateTime<Utc>>> {
+    sqlx::query!(
+        r#"select publish_at as "publish_at: DateTime<Utc>" from jig_draft_schedule where live_id = $1"#,
+        live_id.0
+    )
+    .fetch_optional(db)
+    .await
+    .map(|it| it.map(|it| it.publish_at))
+}
+
+/// Returns the id of the live jig if `id` is a draft, otherwise `id` itself.
+pub async fn get_live_id(db: &PgPool, id: JigId) -> sqlx::Result<JigId> {
+    sqlx::query!(
+        r#"select live_id as "id: JigId" from jig_draft_join where draft_id = $1"#,
+        id.0
+    )
+    .fetch_optional(db)
+    .await
+    .map(|it| it.map_or(id, |it| it.id))
+}
+
+pub async fn exists(db: &PgPool, id: JigId) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"select exists(select 1 from jig where id = $1) as "exists!""#,
+        id.0
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.exists)
+}
+
+/// Gets the ids of (at most `limit`) published jigs related to the jig, most related first.
+///
+/// Shared categories count the most, then shared goals and the same language, then shared age ranges and the same author.
+/// Jigs that have nothing in common with it are left out.
+pub async fn related(db: &PgPool, id: JigId, limit: u16) -> sqlx::Result<Vec<Uuid>> {
+    sqlx::query!(
+        //language=SQL
+        r#"
+select jig.id as "id!"
+from jig,
+     lateral (
+         select 3 * (select count(*)
+                     from jig_category
+                     where jig_id = jig.id
+                       and category_id in (select category_id from jig_category where jig_id = $1)) +
+                2 * (select count(*)
+                     from jig_goal
+                     where jig_id = jig.id
+                       and goal_id in (select goal_id from jig_goal where jig_id = $1)) +
+                (select count(*)
+                 from jig_age_range
+                 where jig_id = jig.id
+                   and age_range_id in (select age_range_id from jig_age_range where jig_id = $1)) +
+                2 * (jig.language = (select language from jig where id = $1))::int +
+                (jig.author_id = (select author_id from jig where id = $1) is true)::int as score
+     ) as related
+where jig.id <> $1
+  and jig.publish_at < now()
+  and related.score > 0
+order by related.score desc,
+         coalesce((select plays from jig_metrics where jig_id = jig.id), 0) desc,
+         jig.publish_at desc
+limit $2
+"#,
+        id.0,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.id).collect())
+}
+
+/// Likes the jig as the user, returns `false` if the user already liked it.
+///
+/// Note: `live_id` should be a live jig's id, see [`get_live_id`].
+pub async fn like(db: &PgPool, live_id: JigId, user_id: Uuid) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    let inserted = sqlx::query!(
+        "insert into jig_like (jig_id, user_id) values ($1, $2) on conflict do nothing",
+        live_id.0,
+        user_id
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if inserted {
+        touch_metrics(&mut txn, live_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(inserted)
+}
+
+/// Un-likes the jig as the user, returns `false` if the user didn't like it.
+pub async fn unlike(db: &PgPool, live_id: JigId, user_id: Uuid) -> sqlx::Result<bool> {
+    let mut txn = db.begin().await?;
+
+    let deleted = sqlx::query!(
+        "delete from jig_like where jig_id = $1 and user_id = $2",
+        live_id.0,
+        user_id
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if deleted {
+        touch_metrics(&mut txn, live_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(deleted)
+}
+
+pub async fn is_liked(db: &PgPool, live_id: JigId, user_id: Uuid) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"select exists(select 1 from jig_like where jig_id = $1 and user_id = $2) as "exists!""#,
+        live_id.0,
+        user_id
+    )
+    .fetch_one(db)
+    .await
+    .map(|it| it.exists)
+}
+
+/// Records that the player (a user or an assignment student) played the jig,
+/// plays are only counted once per player every [`JIG_PLAY_DEDUPE_SECS`](config::JIG_PLAY_DEDUPE_SECS).
+pub async fn record_play(db: &PgPool, live_id: JigId, player_id: Uuid) -> sqlx::Result<()> {
+    let cutoff = Utc::now() - chrono::Duration::seconds(config::JIG_PLAY_DEDUPE_SECS);
+
+    let mut txn = db.begin().await?;
+
+    let counted = sqlx::query!(
+        r#"
+insert into jig_play (jig_id, player_id) values ($1, $2)
+on conflict (jig_id, player_id) do update set played_at = now()
+where jig_play.played_at < $3
+"#,
+        live_id.0,
+        player_id,
+        cutoff,
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if counted {
+        sqlx::query!(
+            r#"
+insert into jig_metrics (jig_id, plays) values ($1, 1)
+on conflict (jig_id) do update set plays = jig_metrics.plays + 1, updated_at = now()
+"#,
+            live_id.0
+        )
+        .execute(&mut txn)
+        .await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Records that the user viewed the jig, views are only counted once per user.
+pub async fn record_view(db: &PgPool, live_id: JigId, user_id: Uuid) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    let inserted = sqlx::query!(
+        "insert into jig_view (jig_id, user_id) values ($1, $2) on conflict do nothing",
+        live_id.0,
+        user_id
+    )
+    .execute(&mut txn)
+    .await?
+    .rows_affected()
+        > 0;
+
+    if inserted {
+        touch_metrics(&mut txn, live_id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Marks the jig's metrics as changed, so that they get synced to algolia.
+async fn touch_metrics(txn: &mut PgConnection, live_id: JigId) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+insert into jig_metrics (jig_id) values ($1)
+on conflict (jig_id) do update set updated_at = now()
+"#,
+        live_id.0
+    )
+    .execute(txn)
+    .await
+    .map(drop)
+}
+
 pub async fn publish_draft_to_live(
     db: &PgPool,
     live_id: JigId,
+    published_by: Uuid,
 ) -> Result<(), error::JigCloneDraft> {
     let draft_id = get_draft(db, live_id).await?;
 
     let mut txn = db.begin().await?;
 
+    publish_draft_in(&mut txn, live_id, draft_id, published_by).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
+
+/// Like [`publish_draft_to_live`], but runs in the caller's transaction, once they looked up the draft with [`get_draft`].
+pub async fn publish_draft_in(
+    txn: &mut PgConnection,
+    live_id: JigId,
+    draft_id: JigId,
+    published_by: Uuid,
+) -> sqlx::Result<()> {
     // delete live from database
     let res = sqlx::query!(
         r#"
@@ -821,7 +1274,7 @@ delete from jig where id = $1 returning publish_at as "publish_at: DateTime<Utc>
         "#,
         live_id.0
     )
-    .fetch_one(&mut txn)
+    .fetch_one(&mut *txn)
     .await?;
 
     // update draft ids to previous live id
@@ -846,7 +1299,7 @@ with module as (
          update jig_additional_resource set jig_id = $1 where jig_id = $4
      )
 update jig
-set id = $1, publish_at = $2, is_public = $3
+set id = $1, publish_at = $2, is_public = $3, last_synced_at = null
 where id = $4
 "#,
         live_id.0,
@@ -854,10 +1307,10 @@ where id = $4
         res.is_public,
         draft_id.0,
     )
-    .execute(&mut txn)
+    .execute(&mut *txn)
     .await?;
 
-    txn.commit().await?;
+    super::jig_version::create(txn, live_id, published_by).await?;
 
     Ok(())
 }
@@ -898,7 +1351,35 @@ select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) a
     Ok(())
 }
 
+/// Checks whether the user can modify the jig (or create jigs, when `jig_id` is [`None`]).
+///
+/// Accepted editors of the jig are let through, as long as they are allowed to manage their own jigs.
 pub async fn authz(db: &PgPool, user_id: Uuid, jig_id: Option<JigId>) -> Result<(), error::Auth> {
+    authz_with_roles(db, user_id, jig_id, &[JigCollaboratorRole::Editor]).await
+}
+
+/// Like [`authz`], but also lets viewers through, for endpoints that don't modify the jig.
+pub async fn authz_read(db: &PgPool, user_id: Uuid, jig_id: JigId) -> Result<(), error::Auth> {
+    authz_with_roles(
+        db,
+        user_id,
+        Some(jig_id),
+        &[JigCollaboratorRole::Editor, JigCollaboratorRole::Viewer],
+    )
+    .await
+}
+
+/// Like [`authz`], but doesn't let collaborators through, for deleting the jig or managing who can access it.
+pub async fn authz_owner(db: &PgPool, user_id: Uuid, jig_id: JigId) -> Result<(), error::Auth> {
+    authz_with_roles(db, user_id, Some(jig_id), &[]).await
+}
+
+async fn authz_with_roles(
+    db: &PgPool,
+    user_id: Uuid,
+    jig_id: Option<JigId>,
+    roles: &[JigCollaboratorRole],
+) -> Result<(), error::Auth> {
     let authed = match jig_id {
         None => {
             sqlx::query!(
@@ -917,19 +1398,31 @@ select exists(select 1 from user_scope where user_id = $1 and scope = any($2)) a
             .authed
         }
         Some(id) => {
+            let roles: Vec<i16> = roles.iter().map(|it| *it as i16).collect();
+
+            // collaborators are recorded against the live jig, so they also have access to its draft
             sqlx::query!(
                 r#"
 select exists (
     select 1 from user_scope where user_id = $1 and scope = any($2)
 ) or (
-    exists (select 1 from user_scope where user_id = $1 and scope = $3) and
-    not exists (select 1 from jig where jig.id = $4 and jig.author_id <> $1)
+    exists (select 1 from user_scope where user_id = $1 and scope = $3) and (
+        not exists (select 1 from jig where jig.id = $4 and jig.author_id <> $1) or
+        exists (
+            select 1 from jig_collaborator
+            where user_id = $1
+              and role = any($5)
+              and accepted_at is not null
+              and (jig_id = $4 or jig_id = (select live_id from jig_draft_join where draft_id = $4))
+        )
+    )
 ) as "authed!"
 "#,
                 user_id,
                 &[UserScope::Admin as i16, UserScope::AdminJig as i16,][..],
                 UserScope::ManageSelfJig as i16,
-                id.0
+                id.0,
+                &roles[..],
             )
             .fetch_one(db)
             .await?
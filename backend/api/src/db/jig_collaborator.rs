@@ -0,0 +1,81 @@
+use shared::domain::jig::{
+    collaborator::{JigCollaborator, JigCollaboratorRole},
+    JigId,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Invites the user with the given email, or changes their role if they were already invited.
+///
+/// Returns [`None`] if the jig or the user doesn't exist.
+pub async fn invite(
+    pool: &PgPool,
+    jig_id: JigId,
+    email: &str,
+    role: JigCollaboratorRole,
+    invited_by: Uuid,
+) -> sqlx::Result<Option<Uuid>> {
+    sqlx::query!(
+        r#"
+insert into jig_collaborator (jig_id, user_id, role, invited_by)
+select jig.id, user_email.user_id, $3, $4
+from jig, user_email
+where jig.id = $1 and user_email.email = $2::text
+on conflict (jig_id, user_id) do update set role = excluded.role
+returning user_id
+"#,
+        jig_id.0,
+        email,
+        role as i16,
+        invited_by,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|it| it.map(|it| it.user_id))
+}
+
+/// Returns `false` if the user wasn't invited to the jig.
+pub async fn accept(pool: &PgPool, jig_id: JigId, user_id: Uuid) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"
+update jig_collaborator
+set accepted_at = coalesce(accepted_at, now())
+where jig_id = $1 and user_id = $2
+"#,
+        jig_id.0,
+        user_id,
+    )
+    .execute(pool)
+    .await
+    .map(|it| it.rows_affected() != 0)
+}
+
+pub async fn remove(pool: &PgPool, jig_id: JigId, user_id: Uuid) -> sqlx::Result<()> {
+    sqlx::query!(
+        "delete from jig_collaborator where jig_id = $1 and user_id = $2",
+        jig_id.0,
+        user_id,
+    )
+    .execute(pool)
+    .await
+    .map(drop)
+}
+
+pub async fn list(pool: &PgPool, jig_id: JigId) -> sqlx::Result<Vec<JigCollaborator>> {
+    sqlx::query_as!(
+        JigCollaborator,
+        r#"
+select user_id,
+       role as "role: JigCollaboratorRole",
+       invited_by,
+       invited_at,
+       accepted_at
+from jig_collaborator
+where jig_id = $1
+order by invited_at
+"#,
+        jig_id.0,
+    )
+    .fetch_all(pool)
+    .await
+}
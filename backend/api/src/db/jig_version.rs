@@ -0,0 +1,328 @@
+use anyhow::Context;
+use futures::TryStreamExt;
+use shared::domain::{
+    category::CategoryId,
+    jig::{
+        module::{body::ThemeId, Module, ModuleId, ModuleKind},
+        version::{JigVersion, JigVersionId, JigVersionSnapshot},
+        AudioBackground, AudioEffects, AudioFeedbackNegative, AudioFeedbackPositive, JigId,
+        TextDirection,
+    },
+    meta::{AffiliationId, AgeRangeId, GoalId},
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use super::module::transform_response_kind;
+
+/// Snapshots the current contents of the jig (including its modules) as its next version.
+pub async fn create(
+    conn: &mut PgConnection,
+    jig_id: JigId,
+    published_by: Uuid,
+) -> sqlx::Result<JigVersionId> {
+    let id = sqlx::query!(
+        //language=SQL
+        r#"
+insert into jig_version (jig_id, version, published_by, display_name, language, description, direction,
+                         display_score, theme, audio_background, audio_feedback_positive, audio_feedback_negative,
+                         goals, categories, affiliations, age_ranges, additional_resources)
+select id,
+       coalesce((select max(version) from jig_version where jig_id = $1), 0) + 1,
+       $2,
+       display_name,
+       language,
+       description,
+       direction,
+       display_score,
+       theme,
+       audio_background,
+       audio_feedback_positive,
+       audio_feedback_negative,
+       array(select goal_id from jig_goal where jig_id = $1),
+       array(select category_id from jig_category where jig_id = $1),
+       array(select affiliation_id from jig_affiliation where jig_id = $1),
+       array(select age_range_id from jig_age_range where jig_id = $1),
+       array(select url from jig_additional_resource where jig_id = $1)
+from jig
+where id = $1
+returning id as "id: JigVersionId"
+"#,
+        jig_id.0,
+        published_by,
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .id;
+
+    sqlx::query!(
+        r#"
+insert into jig_version_module (version_id, module_id, "index", kind, contents, is_complete, revision)
+select $1, id, "index", kind, contents, is_complete, revision
+from jig_module
+where jig_id = $2
+"#,
+        id.0,
+        jig_id.0,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+insert into jig_version_media_ref (version_id, kind, library, media_id)
+select distinct $1::uuid, module_media_ref.kind, library, media_id
+from module_media_ref
+         inner join jig_module on jig_module.id = module_media_ref.module_id
+where jig_id = $2
+"#,
+        id.0,
+        jig_id.0,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(id)
+}
+
+pub async fn list(pool: &PgPool, jig_id: JigId) -> sqlx::Result<Vec<JigVersion>> {
+    sqlx::query!(
+        r#"
+select id as "id: JigVersionId",
+       jig_id as "jig_id: JigId",
+       version,
+       published_at,
+       published_by
+from jig_version
+where jig_id = $1
+order by version desc
+"#,
+        jig_id.0,
+    )
+    .fetch(pool)
+    .map_ok(|row| JigVersion {
+        id: row.id,
+        jig_id: row.jig_id,
+        version: row.version as u32,
+        published_at: row.published_at,
+        published_by: row.published_by,
+    })
+    .try_collect()
+    .await
+}
+
+pub async fn exists(pool: &PgPool, jig_id: JigId, id: JigVersionId) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"select exists(select 1 from jig_version where id = $1 and jig_id = $2) as "exists!""#,
+        id.0,
+        jig_id.0,
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.exists)
+}
+
+pub async fn get(
+    pool: &PgPool,
+    jig_id: JigId,
+    id: JigVersionId,
+) -> anyhow::Result<Option<(JigVersion, JigVersionSnapshot)>> {
+    let row = sqlx::query!(
+        //language=SQL
+        r#"
+select id as "id: JigVersionId",
+       jig_id as "jig_id: JigId",
+       version,
+       published_at,
+       published_by,
+       display_name,
+       language,
+       description,
+       direction as "direction: TextDirection",
+       display_score,
+       theme as "theme: ThemeId",
+       audio_background as "audio_background: AudioBackground",
+       array(select row(unnest(audio_feedback_positive))) as "audio_feedback_positive!: Vec<(AudioFeedbackPositive,)>",
+       array(select row(unnest(audio_feedback_negative))) as "audio_feedback_negative!: Vec<(AudioFeedbackNegative,)>",
+       goals,
+       categories,
+       affiliations,
+       age_ranges,
+       additional_resources
+from jig_version
+where id = $1 and jig_id = $2
+"#,
+        id.0,
+        jig_id.0,
+    )
+    .fetch_optional(pool)
+    .await?;
+
+    let row = match row {
+        Some(row) => row,
+        None => return Ok(None),
+    };
+
+    let modules = sqlx::query!(
+        r#"
+select module_id as "id: ModuleId",
+       kind as "kind: ModuleKind",
+       contents,
+       is_complete,
+       revision
+from jig_version_module
+where version_id = $1
+order by "index"
+"#,
+        id.0,
+    )
+    .fetch(pool)
+    .err_into::<anyhow::Error>()
+    .and_then(|it| async move {
+        Ok(Module {
+            id: it.id,
+            body: transform_response_kind(it.contents, it.kind).context(anyhow::anyhow!(
+                "failed to transform module of kind {:?}",
+                it.kind
+            ))?,
+            is_complete: it.is_complete,
+            revision: it.revision as u32,
+        })
+    })
+    .try_collect()
+    .await?;
+
+    let version = JigVersion {
+        id: row.id,
+        jig_id: row.jig_id,
+        version: row.version as u32,
+        published_at: row.published_at,
+        published_by: row.published_by,
+    };
+
+    let snapshot = JigVersionSnapshot {
+        display_name: row.display_name,
+        language: row.language,
+        description: row.description,
+        direction: row.direction,
+        display_score: row.display_score,
+        theme: row.theme,
+        audio_background: row.audio_background,
+        audio_effects: AudioEffects {
+            feedback_positive: row
+                .audio_feedback_positive
+                .into_iter()
+                .map(|(it,)| it)
+                .collect(),
+            feedback_negative: row
+                .audio_feedback_negative
+                .into_iter()
+                .map(|(it,)| it)
+                .collect(),
+        },
+        goals: row.goals.into_iter().map(GoalId).collect(),
+        categories: row.categories.into_iter().map(CategoryId).collect(),
+        affiliations: row.affiliations.into_iter().map(AffiliationId).collect(),
+        age_ranges: row.age_ranges.into_iter().map(AgeRangeId).collect(),
+        additional_resources: row.additional_resources,
+        modules,
+    };
+
+    Ok(Some((version, snapshot)))
+}
+
+/// Overwrites the contents of the (draft) jig with those of the version.
+///
+/// Metadata that has since been deleted is skipped.
+pub async fn restore(pool: &PgPool, id: JigVersionId, draft_id: JigId) -> sqlx::Result<()> {
+    let mut txn = pool.begin().await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+update jig
+set display_name            = jig_version.display_name,
+    language                = jig_version.language,
+    description             = jig_version.description,
+    direction               = jig_version.direction,
+    display_score           = jig_version.display_score,
+    theme                   = jig_version.theme,
+    audio_background        = jig_version.audio_background,
+    audio_feedback_positive = jig_version.audio_feedback_positive,
+    audio_feedback_negative = jig_version.audio_feedback_negative,
+    revision                = jig.revision + 1
+from jig_version
+where jig.id = $1 and jig_version.id = $2
+"#,
+        draft_id.0,
+        id.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+with module as (
+         delete from jig_module where jig_id = $1
+     ),
+     affiliation as (
+         delete from jig_affiliation where jig_id = $1
+     ),
+     category as (
+         delete from jig_category where jig_id = $1
+     ),
+     goal as (
+         delete from jig_goal where jig_id = $1
+     ),
+     age_range as (
+         delete from jig_age_range where jig_id = $1
+     )
+delete from jig_additional_resource where jig_id = $1
+"#,
+        draft_id.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        //language=SQL
+        r#"
+with version as (
+         select * from jig_version where id = $2
+     ),
+     module as (
+         insert into jig_module ("index", jig_id, kind, contents, is_complete)
+         select "index", $1, kind, contents, is_complete from jig_version_module where version_id = $2
+     ),
+     affiliation as (
+         insert into jig_affiliation (jig_id, affiliation_id)
+         select $1, affiliation.id from affiliation, version where affiliation.id = any(version.affiliations)
+     ),
+     category as (
+         insert into jig_category (jig_id, category_id)
+         select $1, category.id from category, version where category.id = any(version.categories)
+     ),
+     goal as (
+         insert into jig_goal (jig_id, goal_id)
+         select $1, goal.id from goal, version where goal.id = any(version.goals)
+     ),
+     age_range as (
+         insert into jig_age_range (jig_id, age_range_id)
+         select $1, age_range.id from age_range, version where age_range.id = any(version.age_ranges)
+     )
+insert into jig_additional_resource (jig_id, url)
+select $1, unnest(additional_resources) from version
+"#,
+        draft_id.0,
+        id.0,
+    )
+    .execute(&mut txn)
+    .await?;
+
+    super::module_media_ref::sync_jig(&mut txn, draft_id).await?;
+    super::module_text::sync_jig(&mut txn, draft_id).await?;
+
+    txn.commit().await?;
+
+    Ok(())
+}
@@ -6,30 +6,42 @@ use shared::domain::jig::{
 use sqlx::PgPool;
 use std::cmp;
 
+use super::RevisionUpdate;
+
 pub async fn create(
     pool: &PgPool,
     parent: JigId,
     body: ModuleBody,
 ) -> anyhow::Result<(ModuleId, u16)> {
     let kind = body.kind();
-    let body = serde_json::to_value(body)?;
+    let contents = serde_json::to_value(&body)?;
 
-    sqlx::query!(
+    let mut txn = pool.begin().await?;
+
+    let module = sqlx::query!(
         r#"
 insert into jig_module (jig_id, kind, contents, index)
 values ($1, $2, $3, (select count(*) from jig_module where jig_id = $1))
-returning id, "index"
+returning id as "id: ModuleId", "index"
 "#,
         parent.0,
         kind as i16,
-        body,
+        contents,
     )
-    .fetch_one(pool)
-    .await
-    .map(|it| (ModuleId(it.id), it.index as u16))
-    .map_err(Into::into)
+    .fetch_one(&mut txn)
+    .await?;
+
+    super::module_media_ref::sync_module(&mut txn, module.id, &body).await?;
+    super::module_text::sync_module(&mut txn, module.id, &body).await?;
+
+    txn.commit().await?;
+
+    Ok((module.id, module.index as u16))
 }
 
+/// Returns [`None`] if the module doesn't exist.
+///
+/// If `if_revision` is given, the update is only applied if the module's current revision is one of them.
 pub async fn update(
     pool: &PgPool,
     parent_id: JigId,
@@ -37,18 +49,19 @@ pub async fn update(
     body: Option<&ModuleBody>,
     new_index: Option<u16>,
     is_complete: Option<bool>,
-) -> anyhow::Result<bool> {
+    if_revision: Option<&[u32]>,
+) -> anyhow::Result<Option<RevisionUpdate>> {
     let (id, index) = (lookup.id(), lookup.index());
 
-    let (kind, body) = match body.map(map_module_contents).transpose()? {
-        Some((kind, body)) => (Some(kind), Some(body)),
+    let (kind, contents) = match body.map(map_module_contents).transpose()? {
+        Some((kind, contents)) => (Some(kind), Some(contents)),
         None => (None, None),
     };
 
     let mut txn = pool.begin().await?;
 
-    let index = sqlx::query!(
-        r#"select index from jig_module where jig_id = $1 and (id is not distinct from $2 or index is not distinct from $3)"#,
+    let current = sqlx::query!(
+        r#"select id as "id: ModuleId", index, revision from jig_module where jig_id = $1 and (id is not distinct from $2 or index is not distinct from $3) for update"#,
         parent_id.0,
         id.map(|it| it.0),
         index.map(|it| it as i16)
@@ -56,28 +69,40 @@ pub async fn update(
     .fetch_optional(&mut txn)
     .await?;
 
-    let index = match index {
-        Some(it) => it.index,
-        None => return Ok(false),
+    let (module_id, index, revision) = match current {
+        Some(it) => (it.id, it.index, it.revision as u32),
+        None => return Ok(None),
     };
 
+    if let Some(if_revision) = if_revision {
+        if !if_revision.contains(&revision) {
+            return Ok(Some(RevisionUpdate::Conflict));
+        }
+    }
+
     sqlx::query!(
         r#"
 update jig_module
 set contents = coalesce($3, contents),
     kind = coalesce($4, kind),
-    is_complete = coalesce($5, is_complete)
+    is_complete = coalesce($5, is_complete),
+    revision = revision + 1
 where jig_id = $1 and index = $2
 "#,
         parent_id.0,
         index,
-        body.as_ref(),
+        contents,
         kind.map(|it| it as i16),
         is_complete,
     )
     .execute(&mut txn)
     .await?;
 
+    if let Some(body) = body {
+        super::module_media_ref::sync_module(&mut txn, module_id, body).await?;
+        super::module_text::sync_module(&mut txn, module_id, body).await?;
+    }
+
     if let Some(new_index) = new_index {
         let new_index = new_index as i16;
 
@@ -127,10 +152,12 @@ where jig_id = $1 and index between $2 and $3
 
     txn.commit().await?;
 
-    Ok(true)
+    Ok(Some(RevisionUpdate::Updated(revision + 1)))
 }
 
-fn map_module_contents(body: &ModuleBody) -> anyhow::Result<(ModuleKind, serde_json::Value)> {
+pub(crate) fn map_module_contents(
+    body: &ModuleBody,
+) -> anyhow::Result<(ModuleKind, serde_json::Value)> {
     let kind = body.kind();
 
     let body = match body {
@@ -149,7 +176,7 @@ fn map_module_contents(body: &ModuleBody) -> anyhow::Result<(ModuleKind, serde_j
     Ok((kind, body))
 }
 
-fn transform_response_kind(
+pub(crate) fn transform_response_kind(
     contents: serde_json::Value,
     kind: ModuleKind,
 ) -> anyhow::Result<ModuleBody> {
@@ -180,7 +207,8 @@ select
     id as "id: ModuleId",
     contents as "body",
     kind as "kind: ModuleKind",
-    is_complete as "is_complete"
+    is_complete as "is_complete",
+    revision
 from jig_module
 where jig_id = $1 and (id is not distinct from $2 or index is not distinct from $3)
 "#,
@@ -201,6 +229,7 @@ where jig_id = $1 and (id is not distinct from $2 or index is not distinct from
                 it.kind
             ))?,
             is_complete: it.is_complete,
+            revision: it.revision as u32,
         })),
         None => Ok(None),
     }
@@ -228,6 +257,8 @@ pub async fn delete(pool: &PgPool, parent: JigId, lookup: ModuleIdOrIndex) -> an
         )
         .execute(&mut txn)
         .await?;
+
+        super::module_text::sync_jig(&mut txn, parent).await?;
     }
 
     txn.commit().await?;
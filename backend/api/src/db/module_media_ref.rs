@@ -0,0 +1,129 @@
+use shared::{
+    domain::{
+        jig::{
+            module::{ModuleBody, ModuleId, ModuleKind},
+            JigId,
+        },
+        media::MediaUsage,
+    },
+    media::MediaLibrary,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+use crate::module_media::{self, MediaRefKind};
+
+/// Replaces the media references of a module with the ones found in its body.
+pub async fn sync_module(
+    conn: &mut PgConnection,
+    module_id: ModuleId,
+    body: &ModuleBody,
+) -> sqlx::Result<()> {
+    let refs = module_media::media_refs(body);
+
+    sqlx::query!(
+        "delete from module_media_ref where module_id = $1",
+        module_id.0
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    if refs.is_empty() {
+        return Ok(());
+    }
+
+    sqlx::query!(
+        r#"
+insert into module_media_ref (module_id, kind, library, media_id)
+select $1::uuid, * from unnest($2::smallint[], $3::smallint[], $4::uuid[])
+on conflict do nothing
+"#,
+        module_id.0,
+        &refs.iter().map(|it| it.kind as i16).collect::<Vec<_>>(),
+        &refs.iter().map(|it| it.library as i16).collect::<Vec<_>>(),
+        &refs.iter().map(|it| it.id).collect::<Vec<_>>(),
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Re-syncs the media references of every module of a jig, for when modules are copied in bulk.
+pub async fn sync_jig(conn: &mut PgConnection, jig_id: JigId) -> sqlx::Result<()> {
+    let modules = sqlx::query!(
+        r#"select id as "id: ModuleId", kind as "kind: ModuleKind", contents from jig_module where jig_id = $1"#,
+        jig_id.0
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for module in modules {
+        let contents = match module.contents {
+            Some(contents) => contents,
+            None => continue,
+        };
+
+        // a body that doesn't parse can't be played either, so it doesn't use any media.
+        let body = match super::module::transform_response_kind(contents, module.kind) {
+            Ok(body) => body,
+            Err(e) => {
+                log::warn!("skipping the media of module {}: {:?}", module.id.0, e);
+                continue;
+            }
+        };
+
+        sync_module(&mut *conn, module.id, &body).await?;
+    }
+
+    Ok(())
+}
+
+/// Lists the modules that use the given media.
+pub async fn list_usages(
+    db: &PgPool,
+    kind: MediaRefKind,
+    library: MediaLibrary,
+    id: Uuid,
+) -> sqlx::Result<Vec<MediaUsage>> {
+    sqlx::query_as!(
+        MediaUsage,
+        r#"
+select jig_module.jig_id as "jig_id: JigId",
+       module_id         as "module_id: ModuleId"
+from module_media_ref
+inner join jig_module on jig_module.id = module_media_ref.module_id
+where media_id = $1 and module_media_ref.kind = $2 and library = $3
+order by jig_module.jig_id, jig_module."index"
+"#,
+        id,
+        kind as i16,
+        library as i16,
+    )
+    .fetch_all(db)
+    .await
+}
+
+/// Lists the jigs that have modules that use the given media.
+pub async fn list_jigs(
+    db: &PgPool,
+    kind: MediaRefKind,
+    library: MediaLibrary,
+    id: Uuid,
+) -> sqlx::Result<Vec<JigId>> {
+    sqlx::query!(
+        r#"
+select distinct jig_module.jig_id as "id: JigId"
+from module_media_ref
+inner join jig_module on jig_module.id = module_media_ref.module_id
+where media_id = $1 and module_media_ref.kind = $2 and library = $3
+order by jig_module.jig_id
+"#,
+        id,
+        kind as i16,
+        library as i16,
+    )
+    .fetch_all(db)
+    .await
+    .map(|it| it.into_iter().map(|it| it.id).collect())
+}
@@ -0,0 +1,110 @@
+use shared::domain::jig::{
+    module::{ModuleBody, ModuleId, ModuleKind},
+    JigId,
+};
+use sqlx::{PgConnection, PgPool};
+
+use crate::module_text;
+
+/// Re-extracts the text of a module whose body changed, and updates its jig's module text.
+pub async fn sync_module(
+    conn: &mut PgConnection,
+    module_id: ModuleId,
+    body: &ModuleBody,
+) -> sqlx::Result<()> {
+    let jig_id = sqlx::query!(
+        r#"update jig_module set module_text = $2 where id = $1 returning jig_id as "jig_id: JigId""#,
+        module_id.0,
+        module_text::module_text(body),
+    )
+    .fetch_one(&mut *conn)
+    .await?
+    .jig_id;
+
+    sync_jig(conn, jig_id).await
+}
+
+/// Puts the module text of a jig back together, for when its modules are added or removed.
+///
+/// Only modules whose text hasn't been extracted yet (like the ones restored from a version) are parsed,
+/// the jig is only re-indexed if its text actually changed.
+pub async fn sync_jig(conn: &mut PgConnection, jig_id: JigId) -> sqlx::Result<()> {
+    let modules = sqlx::query!(
+        r#"
+select id as "id: ModuleId", kind as "kind: ModuleKind", contents
+from jig_module
+where jig_id = $1 and module_text is null
+"#,
+        jig_id.0
+    )
+    .fetch_all(&mut *conn)
+    .await?;
+
+    for module in modules {
+        // a body that doesn't parse can't be played either, so it doesn't show any text.
+        let text = match module
+            .contents
+            .map(|contents| super::module::transform_response_kind(contents, module.kind))
+        {
+            Some(Ok(body)) => module_text::module_text(&body),
+            Some(Err(e)) => {
+                log::warn!("skipping the text of module {}: {:?}", module.id.0, e);
+                String::new()
+            }
+            None => String::new(),
+        };
+
+        sqlx::query!(
+            "update jig_module set module_text = $2 where id = $1",
+            module.id.0,
+            text
+        )
+        .execute(&mut *conn)
+        .await?;
+    }
+
+    sqlx::query!(
+        r#"
+with text as (
+    select coalesce(string_agg(module_text, E'\n' order by "index"), '') as module_text
+    from jig_module
+    where jig_id = $1 and module_text <> ''
+)
+update jig
+set module_text = text.module_text, last_synced_at = null
+from text
+where id = $1 and jig.module_text is distinct from text.module_text
+"#,
+        jig_id.0,
+    )
+    .execute(&mut *conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Extracts the module text of a batch of jigs that don't have it yet, returns how many jigs were updated.
+pub async fn backfill(db: &PgPool) -> sqlx::Result<usize> {
+    let mut txn = db.begin().await?;
+
+    let ids = sqlx::query!(
+        r#"
+select id as "id: JigId"
+from jig
+where module_text is null
+   or exists(select 1 from jig_module where jig_id = jig.id and module_text is null)
+limit 100
+for no key update skip locked
+"#
+    )
+    .fetch_all(&mut txn)
+    .await?;
+
+    for it in &ids {
+        sync_jig(&mut txn, it.id).await?;
+    }
+
+    txn.commit().await?;
+
+    Ok(ids.len())
+}
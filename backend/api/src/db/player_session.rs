@@ -0,0 +1,234 @@
+use std::{collections::HashMap, convert::TryFrom};
+
+use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
+use shared::domain::{
+    class::assignment::{AssignmentId, AssignmentStudentId},
+    jig::{
+        module::ModuleId,
+        player::{JigPlayerModuleResult, JigPlayerSession, JigPlayerSessionId},
+        JigId,
+    },
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::error;
+
+pub async fn create(
+    pool: &PgPool,
+    jig_id: JigId,
+    user_id: Option<Uuid>,
+) -> sqlx::Result<Option<JigPlayerSessionId>> {
+    sqlx::query!(
+        r#"
+insert into jig_player_session (jig_id, user_id)
+select id, $2 from jig where id = $1
+returning id as "id: JigPlayerSessionId"
+"#,
+        jig_id.0,
+        user_id,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|it| it.map(|it| it.id))
+}
+
+/// Starts a session for a student playing the jig of the assignment they joined.
+pub async fn create_for_assignment_student(
+    pool: &PgPool,
+    student_id: AssignmentStudentId,
+) -> sqlx::Result<JigPlayerSessionId> {
+    sqlx::query!(
+        r#"
+insert into jig_player_session (jig_id, assignment_student_id)
+select assignment.jig_id, assignment_student.id
+from assignment_student
+inner join assignment on assignment.id = assignment_student.assignment_id
+where assignment_student.id = $1
+returning id as "id: JigPlayerSessionId"
+"#,
+        student_id.0,
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.id)
+}
+
+/// Returns whether the session was started by the given student.
+pub async fn is_assignment_student_session(
+    pool: &PgPool,
+    student_id: AssignmentStudentId,
+    session_id: JigPlayerSessionId,
+) -> sqlx::Result<bool> {
+    sqlx::query!(
+        r#"select exists(select 1 from jig_player_session where id = $1 and assignment_student_id = $2) as "exists!""#,
+        session_id.0,
+        student_id.0,
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.exists)
+}
+
+/// Returns the user (if any) the session belongs to, [`None`] if the session doesn't exist for the jig.
+pub async fn get_owner(
+    pool: &PgPool,
+    jig_id: JigId,
+    session_id: JigPlayerSessionId,
+) -> sqlx::Result<Option<Option<Uuid>>> {
+    sqlx::query!(
+        r#"select user_id from jig_player_session where id = $1 and jig_id = $2"#,
+        session_id.0,
+        jig_id.0,
+    )
+    .fetch_optional(pool)
+    .await
+    .map(|it| it.map(|it| it.user_id))
+}
+
+/// Fails with [`ResourceNotFound`](error::JigPlayerResult::ResourceNotFound) if the module isn't part of the session's jig.
+pub async fn create_module_result(
+    pool: &PgPool,
+    session_id: JigPlayerSessionId,
+    module_id: ModuleId,
+    score: Option<u32>,
+    attempts: u32,
+    time_spent: u32,
+) -> Result<(), error::JigPlayerResult> {
+    let score = score
+        .map(i32::try_from)
+        .transpose()
+        .map_err(|_| error::JigPlayerResult::OutOfRange)?;
+    let attempts = i32::try_from(attempts).map_err(|_| error::JigPlayerResult::OutOfRange)?;
+    let time_spent = i32::try_from(time_spent).map_err(|_| error::JigPlayerResult::OutOfRange)?;
+
+    let res = sqlx::query!(
+        r#"
+insert into jig_player_session_module (session_id, module_id, score, attempts, time_spent)
+select jig_player_session.id, $2, $3, $4, $5
+from jig_player_session
+inner join jig_module on jig_module.jig_id = jig_player_session.jig_id
+where jig_player_session.id = $1 and jig_module.id = $2
+"#,
+        session_id.0,
+        module_id.0,
+        score,
+        attempts,
+        time_spent,
+    )
+    .execute(pool)
+    .await?;
+
+    if res.rows_affected() == 0 {
+        return Err(error::JigPlayerResult::ResourceNotFound);
+    }
+
+    Ok(())
+}
+
+pub async fn list(pool: &PgPool, jig_id: JigId, page: i32) -> sqlx::Result<Vec<JigPlayerSession>> {
+    sqlx::query!(
+        r#"
+select id as "id: JigPlayerSessionId",
+       jig_id as "jig_id: JigId",
+       user_id,
+       started_at,
+       array(
+           select row (module_id, score, attempts, time_spent, created_at)
+           from jig_player_session_module
+           where session_id = jig_player_session.id
+           order by created_at
+       ) as "module_results!: Vec<(ModuleId, Option<i32>, i32, i32, DateTime<Utc>)>"
+from jig_player_session
+where jig_id = $1
+order by started_at desc
+limit 20 offset 20 * $2
+"#,
+        jig_id.0,
+        page,
+    )
+    .fetch(pool)
+    .map_ok(|row| JigPlayerSession {
+        id: row.id,
+        jig_id: row.jig_id,
+        user_id: row.user_id,
+        started_at: row.started_at,
+        module_results: module_results(row.module_results),
+    })
+    .try_collect()
+    .await
+}
+
+/// Lists the sessions of every student in an assignment, most recent first, keyed by student.
+pub async fn list_for_assignment(
+    pool: &PgPool,
+    assignment_id: AssignmentId,
+) -> sqlx::Result<HashMap<Uuid, Vec<JigPlayerSession>>> {
+    let rows = sqlx::query!(
+        r#"
+select jig_player_session.id as "id: JigPlayerSessionId",
+       jig_player_session.jig_id as "jig_id: JigId",
+       assignment_student_id as "assignment_student_id!",
+       user_id,
+       started_at,
+       array(
+           select row (module_id, score, attempts, time_spent, created_at)
+           from jig_player_session_module
+           where session_id = jig_player_session.id
+           order by created_at
+       ) as "module_results!: Vec<(ModuleId, Option<i32>, i32, i32, DateTime<Utc>)>"
+from jig_player_session
+inner join assignment_student on assignment_student.id = jig_player_session.assignment_student_id
+where assignment_student.assignment_id = $1
+order by started_at desc
+"#,
+        assignment_id.0,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut sessions: HashMap<Uuid, Vec<JigPlayerSession>> = HashMap::new();
+
+    for row in rows {
+        sessions
+            .entry(row.assignment_student_id)
+            .or_default()
+            .push(JigPlayerSession {
+                id: row.id,
+                jig_id: row.jig_id,
+                user_id: row.user_id,
+                started_at: row.started_at,
+                module_results: module_results(row.module_results),
+            });
+    }
+
+    Ok(sessions)
+}
+
+fn module_results(
+    results: Vec<(ModuleId, Option<i32>, i32, i32, DateTime<Utc>)>,
+) -> Vec<JigPlayerModuleResult> {
+    results
+        .into_iter()
+        .map(
+            |(module_id, score, attempts, time_spent, created_at)| JigPlayerModuleResult {
+                module_id,
+                score: score.map(|score| score as u32),
+                attempts: attempts as u32,
+                time_spent: time_spent as u32,
+                created_at,
+            },
+        )
+        .collect()
+}
+
+pub async fn count(pool: &PgPool, jig_id: JigId) -> sqlx::Result<u64> {
+    sqlx::query!(
+        r#"select count(*) as "count!" from jig_player_session where jig_id = $1"#,
+        jig_id.0
+    )
+    .fetch_one(pool)
+    .await
+    .map(|it| it.count as u64)
+}
@@ -0,0 +1,227 @@
+use chrono::{DateTime, Utc};
+use shared::domain::{
+    jig::{JigId, JigSearchQuery},
+    user::saved_search::{SavedSearch, SavedSearchId},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+pub async fn create(
+    db: &PgPool,
+    user_id: Uuid,
+    name: &str,
+    query: &JigSearchQuery,
+    email_alerts: bool,
+) -> anyhow::Result<SavedSearchId> {
+    let id = sqlx::query!(
+        r#"
+insert into user_saved_search (user_id, name, query, email_alerts)
+values ($1, $2, $3, $4)
+returning id as "id: SavedSearchId"
+"#,
+        user_id,
+        name,
+        serde_json::to_value(query)?,
+        email_alerts
+    )
+    .fetch_one(db)
+    .await?
+    .id;
+
+    Ok(id)
+}
+
+pub async fn list(db: &PgPool, user_id: Uuid) -> anyhow::Result<Vec<SavedSearch>> {
+    let rows = sqlx::query!(
+        r#"
+select id as "id: SavedSearchId", name, query, email_alerts, created_at, last_checked_at
+from user_saved_search
+where user_id = $1
+order by created_at desc
+"#,
+        user_id
+    )
+    .fetch_all(db)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(SavedSearch {
+                id: row.id,
+                name: row.name,
+                query: serde_json::from_value(row.query)?,
+                email_alerts: row.email_alerts,
+                created_at: row.created_at,
+                last_checked_at: row.last_checked_at,
+            })
+        })
+        .collect()
+}
+
+/// Returns `false` if the user has no such saved search.
+pub async fn update(
+    db: &PgPool,
+    user_id: Uuid,
+    id: SavedSearchId,
+    name: Option<&str>,
+    query: Option<&JigSearchQuery>,
+    email_alerts: Option<bool>,
+) -> anyhow::Result<bool> {
+    let query = query.map(serde_json::to_value).transpose()?;
+
+    let exists = sqlx::query!(
+        r#"
+update user_saved_search
+set name         = coalesce($3, name),
+    query        = coalesce($4, query),
+    email_alerts = coalesce($5, email_alerts)
+where id = $1 and user_id = $2
+returning true as "exists!"
+"#,
+        id.0,
+        user_id,
+        name,
+        query,
+        email_alerts
+    )
+    .fetch_optional(db)
+    .await?
+    .is_some();
+
+    Ok(exists)
+}
+
+pub async fn delete(db: &PgPool, user_id: Uuid, id: SavedSearchId) -> sqlx::Result<()> {
+    sqlx::query!(
+        "delete from user_saved_search where id = $1 and user_id = $2",
+        id.0,
+        user_id
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// A saved search that's due a digest.
+pub struct DueSavedSearch {
+    pub id: SavedSearchId,
+    pub name: String,
+    pub query: JigSearchQuery,
+    pub email: String,
+
+    /// When the search was last checked, jigs published since are new.
+    pub since: DateTime<Utc>,
+}
+
+/// Claims (at most `limit`) saved searches with email alerts that haven't been checked since `cutoff`,
+/// marking them as checked now so that other instances of the api don't claim them too.
+///
+/// Saved searches of users without an email are skipped, there's nowhere to send their digests.
+pub async fn claim_due(
+    db: &PgPool,
+    cutoff: DateTime<Utc>,
+    limit: i64,
+) -> anyhow::Result<Vec<DueSavedSearch>> {
+    let rows = sqlx::query!(
+        r#"
+with due as (
+    select id, last_checked_at, email::text as email
+    from user_saved_search
+    inner join user_email using (user_id)
+    where email_alerts
+      and last_checked_at <= $1
+    order by last_checked_at
+    limit $2
+    for update of user_saved_search skip locked
+)
+update user_saved_search
+set last_checked_at = now()
+from due
+where user_saved_search.id = due.id
+returning user_saved_search.id as "id: SavedSearchId",
+          name,
+          query,
+          due.email as "email!",
+          due.last_checked_at as "since"
+"#,
+        cutoff,
+        limit
+    )
+    .fetch_all(db)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok(DueSavedSearch {
+                id: row.id,
+                name: row.name,
+                query: serde_json::from_value(row.query)?,
+                email: row.email,
+                since: row.since,
+            })
+        })
+        .collect()
+}
+
+/// Puts a claimed saved search back, for when its digest couldn't be sent.
+pub async fn unclaim(db: &PgPool, id: SavedSearchId, since: DateTime<Utc>) -> sqlx::Result<()> {
+    sqlx::query!(
+        "update user_saved_search set last_checked_at = $2 where id = $1",
+        id.0,
+        since
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+/// Filters the search hits down to jigs published since `since` that haven't been sent for the saved search yet,
+/// returning their ids and names in the same order.
+pub async fn new_jigs(
+    db: &PgPool,
+    id: SavedSearchId,
+    hits: &[Uuid],
+    since: DateTime<Utc>,
+) -> sqlx::Result<Vec<(JigId, String)>> {
+    sqlx::query!(
+        r#"
+select jig.id as "id: JigId", display_name
+from jig
+inner join unnest($2::uuid[]) with ordinality t(id, ord) using (id)
+where publish_at > $3
+  and publish_at <= now()
+  and not exists(select 1 from user_saved_search_sent where saved_search_id = $1 and jig_id = jig.id)
+order by t.ord
+"#,
+        id.0,
+        hits,
+        since
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| (row.id, row.display_name))
+            .collect()
+    })
+}
+
+/// Records that the jigs were sent for the saved search, so that they're never sent for it again.
+pub async fn mark_sent(db: &PgPool, id: SavedSearchId, jigs: &[Uuid]) -> sqlx::Result<()> {
+    sqlx::query!(
+        r#"
+insert into user_saved_search_sent (saved_search_id, jig_id)
+select $1, jig_id
+from unnest($2::uuid[]) as t(jig_id)
+on conflict do nothing
+"#,
+        id.0,
+        jigs
+    )
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,244 @@
+use shared::domain::admin::SearchIndexEntity;
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+
+/// Records that the user searched for `query`, and forgets their oldest searches past [`config::RECENT_SEARCH_LIMIT`].
+pub async fn record_recent(db: &PgPool, user_id: Uuid, query: &str) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    sqlx::query!(
+        r#"
+insert into user_recent_search (user_id, query)
+values ($1, $2)
+on conflict (user_id, query) do update set searched_at = now()
+"#,
+        user_id,
+        query
+    )
+    .execute(&mut txn)
+    .await?;
+
+    sqlx::query!(
+        r#"
+delete from user_recent_search
+where user_id = $1
+  and query not in (select query
+                    from user_recent_search
+                    where user_id = $1
+                    order by searched_at desc
+                    limit $2)
+"#,
+        user_id,
+        config::RECENT_SEARCH_LIMIT
+    )
+    .execute(&mut txn)
+    .await?;
+
+    txn.commit().await
+}
+
+/// Lists the user's recent searches that start with the (normalized) `prefix`, most recent first.
+pub async fn list_recent(
+    db: &PgPool,
+    user_id: Uuid,
+    prefix: &str,
+    limit: u16,
+) -> sqlx::Result<Vec<String>> {
+    sqlx::query!(
+        r#"
+select query
+from user_recent_search
+where user_id = $1
+  and starts_with(lower(search_normalize(query)), $2)
+order by searched_at desc
+limit $3
+"#,
+        user_id,
+        prefix,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.query).collect())
+}
+
+pub async fn clear_recent(db: &PgPool, user_id: Uuid) -> sqlx::Result<()> {
+    sqlx::query!("delete from user_recent_search where user_id = $1", user_id)
+        .execute(db)
+        .await?;
+
+    Ok(())
+}
+
+/// Gets the names of the jigs with the given ids, in the same order.
+pub async fn jig_names(db: &PgPool, ids: &[Uuid]) -> sqlx::Result<Vec<String>> {
+    sqlx::query!(
+        r#"
+select display_name
+from jig
+inner join unnest($1::uuid[]) with ordinality t(id, ord) using (id)
+order by t.ord
+"#,
+        ids
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.display_name).collect())
+}
+
+/// Gets the names of the images with the given ids, in the same order.
+pub async fn image_names(db: &PgPool, ids: &[Uuid]) -> sqlx::Result<Vec<String>> {
+    sqlx::query!(
+        r#"
+select name
+from image_metadata
+inner join unnest($1::uuid[]) with ordinality t(id, ord) using (id)
+order by t.ord
+"#,
+        ids
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.name).collect())
+}
+
+/// Gets the names of image tags matching the `tsquery`, in tag order.
+pub async fn tag_names(db: &PgPool, tsquery: &str, limit: u16) -> sqlx::Result<Vec<String>> {
+    sqlx::query!(
+        r#"
+select display_name
+from image_tag
+where to_tsvector('simple', search_normalize(display_name)) @@ to_tsquery('simple', $1)
+order by index
+limit $2
+"#,
+        tsquery,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.display_name).collect())
+}
+
+/// Gets the names of categories matching the `tsquery`, the ones with the most content first.
+pub async fn category_names(db: &PgPool, tsquery: &str, limit: u16) -> sqlx::Result<Vec<String>> {
+    sqlx::query!(
+        r#"
+select name
+from category
+where to_tsvector('simple', search_normalize(name)) @@ to_tsquery('simple', $1)
+order by (select count(*) from jig_category where category_id = category.id) +
+         (select count(*) from image_category where category_id = category.id) desc,
+         name
+limit $2
+"#,
+        tsquery,
+        i64::from(limit)
+    )
+    .fetch_all(db)
+    .await
+    .map(|rows| rows.into_iter().map(|it| it.name).collect())
+}
+
+/// Marks everything of the given kinds as needing to be synced to the search index again.
+pub async fn reindex(db: &PgPool, entities: &[SearchIndexEntity]) -> sqlx::Result<()> {
+    let mut txn = db.begin().await?;
+
+    for entity in entities {
+        match entity {
+            SearchIndexEntity::Image => {
+                sqlx::query!("update image_metadata set last_synced_at = null")
+                    .execute(&mut txn)
+                    .await?;
+            }
+
+            SearchIndexEntity::Animation => {
+                sqlx::query!("update animation_metadata set last_synced_at = null")
+                    .execute(&mut txn)
+                    .await?;
+            }
+
+            SearchIndexEntity::Jig => {
+                sqlx::query!("update jig set last_synced_at = null")
+                    .execute(&mut txn)
+                    .await?;
+            }
+        }
+    }
+
+    request_orphan_check(&mut txn, entities).await?;
+
+    txn.commit().await
+}
+
+/// How a [`SearchIndexEntity`] is stored in `search_orphan_check`.
+pub(crate) const fn entity_kind(entity: SearchIndexEntity) -> i16 {
+    match entity {
+        SearchIndexEntity::Image => 0,
+        SearchIndexEntity::Animation => 1,
+        SearchIndexEntity::Jig => 2,
+    }
+}
+
+/// Asks for the search index records of the given kinds to be checked for content that doesn't exist anymore,
+/// since a full reindex only updates the records of content that does.
+pub(crate) async fn request_orphan_check(
+    conn: &mut PgConnection,
+    entities: &[SearchIndexEntity],
+) -> sqlx::Result<()> {
+    let kinds: Vec<i16> = entities.iter().map(|&it| entity_kind(it)).collect();
+
+    sqlx::query!(
+        r#"
+insert into search_orphan_check (entity)
+select * from unnest($1::smallint[])
+on conflict (entity) do update set requested_at = now()
+"#,
+        &kinds
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// How many of a kind of content there are, and how many of them are waiting to be synced to the search index.
+pub struct SyncCounts {
+    pub total: u64,
+    pub pending: u64,
+}
+
+/// Gets the [`SyncCounts`] for images, animations and jigs, in that order.
+///
+/// Pending means the same thing here as it does to the algolia sync task, they share the `search_sync_pending` SQL functions.
+pub async fn sync_counts(db: &PgPool) -> sqlx::Result<(SyncCounts, SyncCounts, SyncCounts)> {
+    let row = sqlx::query!(
+        r#"
+select (select count(*) from image_metadata) as "images!",
+       (select count(*)
+        from image_metadata
+        where search_sync_pending(last_synced_at, updated_at, publish_at)) as "pending_images!",
+       (select count(*) from animation_metadata) as "animations!",
+       (select count(*)
+        from animation_metadata
+        where search_sync_pending(last_synced_at, updated_at, publish_at)) as "pending_animations!",
+       (select count(*) from jig) as "jigs!",
+       (select count(*)
+        from jig
+        where jig_search_sync_pending(id, last_synced_at, updated_at, publish_at)) as "pending_jigs!"
+"#
+    )
+    .fetch_one(db)
+    .await?;
+
+    let counts = |total: i64, pending: i64| SyncCounts {
+        total: total as u64,
+        pending: pending as u64,
+    };
+
+    Ok((
+        counts(row.images, row.pending_images),
+        counts(row.animations, row.pending_animations),
+        counts(row.jigs, row.pending_jigs),
+    ))
+}
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use shared::domain::class::assignment::AssignmentStudentId;
 use sqlx::PgConnection;
 use uuid::Uuid;
 
@@ -34,6 +35,26 @@ pub async fn create(
     Ok(session)
 }
 
+/// Creates a session for a student who joined an assignment, they don't have a user to attach it to.
+pub async fn create_for_assignment_student(
+    conn: &mut PgConnection,
+    student_id: AssignmentStudentId,
+    valid_until: &DateTime<Utc>,
+) -> sqlx::Result<String> {
+    let session = generate_session_token();
+    sqlx::query!(
+        "insert into session (token, assignment_student_id, expires_at, scope_mask) values ($1, $2, $3, $4)",
+        &session,
+        student_id.0,
+        valid_until,
+        SessionMask::PLAY_ASSIGNMENT.bits()
+    )
+    .execute(conn)
+    .await?;
+
+    Ok(session)
+}
+
 pub async fn clear_any(
     conn: &mut PgConnection,
     user_id: Uuid,
@@ -57,7 +78,7 @@ pub async fn get_onetime(
     token: &str,
 ) -> sqlx::Result<Option<Uuid>> {
     let res = sqlx::query!(
-        r#"delete from session where token = $1 and (scope_mask & $2) = $2 returning user_id"#,
+        r#"delete from session where token = $1 and (scope_mask & $2) = $2 and user_id is not null returning user_id as "user_id!""#,
         token,
         min_mask.bits()
     )
@@ -145,9 +145,12 @@ set
     })?;
 
     sqlx::query!(
-        "insert into user_scope (user_id, scope) values ($1, $2)",
+        "insert into user_scope (user_id, scope) select $1, unnest($2::int2[])",
         user_id,
-        UserScope::ManageSelfJig as i16
+        &[
+            UserScope::ManageSelfJig as i16,
+            UserScope::ManageClass as i16
+        ][..]
     )
     .execute(&mut *txn)
     .await?;
@@ -1,5 +1,8 @@
-use actix_http::cookie::{Cookie, SameSite};
-use actix_web::Responder;
+use actix_http::{
+    cookie::{Cookie, SameSite},
+    http::header::{self, ETag, EntityTag, Header, IfMatch},
+};
+use actix_web::{HttpRequest, HttpResponse, Responder};
 use chrono::{DateTime, Utc};
 use http::StatusCode;
 use paperclip::{
@@ -9,6 +12,7 @@ use paperclip::{
         schema::Apiv2Schema,
     },
 };
+use serde::Serialize;
 use shared::domain::{
     category::{Category, CategoryId},
     session::AUTH_COOKIE_NAME,
@@ -156,3 +160,73 @@ impl OperationModifier for NoContentClearAuth {
         );
     }
 }
+
+/// Reads the revisions a conditional update may be applied to from `If-Match`.
+///
+/// Returns [`None`] if the update is unconditional (no `If-Match`, or `If-Match: *`).
+/// Entity tags that aren't revisions (or a malformed header) never match.
+pub fn if_match_revisions(req: &HttpRequest) -> Option<Vec<u32>> {
+    if !req.headers().contains_key(header::IF_MATCH) {
+        return None;
+    }
+
+    match IfMatch::parse(req) {
+        Ok(IfMatch::Any) => None,
+        Ok(IfMatch::Items(items)) => Some(
+            items
+                .iter()
+                .filter(|it| !it.weak)
+                .filter_map(|it| it.tag().parse().ok())
+                .collect(),
+        ),
+        Err(_) => Some(Vec::new()),
+    }
+}
+
+/// Response for an update that can be made conditional with [`if_match_revisions`].
+#[derive(Debug)]
+pub enum Conditional<T> {
+    /// The update was applied, responds with `204 No Content` and the new revision as the `ETag`.
+    Updated(u32),
+
+    /// The revision didn't match, responds with `412 Precondition Failed` and the resource as it's currently stored.
+    PreconditionFailed { revision: u32, current: T },
+}
+
+impl<T: Serialize> Responder for Conditional<T> {
+    type Error = actix_web::Error;
+    type Future = Ready<Result<HttpResponse, Self::Error>>;
+
+    fn respond_to(self, _: &HttpRequest) -> Self::Future {
+        let resp = match self {
+            Self::Updated(revision) => HttpResponse::build(StatusCode::NO_CONTENT)
+                .set(ETag(EntityTag::strong(revision.to_string())))
+                .finish(),
+
+            Self::PreconditionFailed { revision, current } => {
+                HttpResponse::build(StatusCode::PRECONDITION_FAILED)
+                    .set(ETag(EntityTag::strong(revision.to_string())))
+                    .json(current)
+            }
+        };
+
+        ready(Ok(resp))
+    }
+}
+
+impl<T> Apiv2Schema for Conditional<T> {}
+
+impl<T> OperationModifier for Conditional<T> {
+    fn update_response(op: &mut DefaultOperationRaw) {
+        for status in &[StatusCode::NO_CONTENT, StatusCode::PRECONDITION_FAILED] {
+            op.responses.insert(
+                status.as_str().into(),
+                Either::Right(Response {
+                    description: status.canonical_reason().map(ToString::to_string),
+                    schema: None,
+                    ..Default::default()
+                }),
+            );
+        }
+    }
+}
@@ -9,7 +9,7 @@
 use actix_web::error::BlockingError;
 use actix_web::HttpResponse;
 use paperclip::actix::api_v2_errors;
-use shared::error::{ApiError, EmptyError, MetadataNotFound};
+use shared::error::{ApiError, EmptyError, MediaInUse, MetadataNotFound};
 
 use crate::db::meta::MetaWrapperError;
 
@@ -58,9 +58,17 @@ pub fn ise(e: anyhow::Error) -> actix_web::Error {
 }
 
 #[non_exhaustive]
-#[api_v2_errors(code = 401, code = 403, code = 404, code = 500)]
+#[api_v2_errors(
+    code = 401,
+    code = 403,
+    code = 404,
+    code = 409,
+    description = "Conflict: Media is still in use",
+    code = 500
+)]
 pub enum Delete {
     Conflict,
+    InUse(MediaInUse),
     Forbidden,
     InternalServerError(anyhow::Error),
 }
@@ -84,6 +92,12 @@ impl Into<actix_web::Error> for Delete {
     fn into(self) -> actix_web::Error {
         match self {
             Self::Conflict => BasicError::new(http::StatusCode::CONFLICT).into(),
+            Self::InUse(data) => ApiError {
+                code: http::StatusCode::CONFLICT,
+                message: "Media is still in use".to_owned(),
+                extra: data,
+            }
+            .into(),
             Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
             Self::InternalServerError(e) => ise(e),
         }
@@ -782,6 +796,93 @@ impl Into<actix_web::Error> for JigCloneDraft {
     }
 }
 
+#[api_v2_errors(
+    code = 400,
+    description = "Bad Request: Value Out Of Range",
+    code = 401,
+    code = 403,
+    code = 404,
+    code = 500
+)]
+pub enum JigPlayerResult {
+    ResourceNotFound,
+    OutOfRange,
+    Forbidden,
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for JigPlayerResult {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl Into<actix_web::Error> for JigPlayerResult {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::ResourceNotFound => BasicError::with_message(
+                http::StatusCode::NOT_FOUND,
+                "Resource Not Found".to_owned(),
+            )
+            .into(),
+
+            Self::OutOfRange => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Value Out Of Range".to_owned(),
+            )
+            .into(),
+
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
+#[api_v2_errors(
+    code = 400,
+    description = "Bad Request: Invalid Archive",
+    code = 401,
+    code = 403,
+    code = 500
+)]
+pub enum JigImport {
+    InvalidArchive,
+    Forbidden,
+    InternalServerError(anyhow::Error),
+}
+
+impl<T: Into<anyhow::Error>> From<T> for JigImport {
+    fn from(e: T) -> Self {
+        Self::InternalServerError(e.into())
+    }
+}
+
+impl From<Auth> for JigImport {
+    fn from(e: Auth) -> Self {
+        match e {
+            Auth::InternalServerError(e) => Self::InternalServerError(e),
+            Auth::Forbidden => Self::Forbidden,
+        }
+    }
+}
+
+impl Into<actix_web::Error> for JigImport {
+    fn into(self) -> actix_web::Error {
+        match self {
+            Self::InvalidArchive => BasicError::with_message(
+                http::StatusCode::BAD_REQUEST,
+                "Invalid Archive".to_owned(),
+            )
+            .into(),
+
+            Self::Forbidden => BasicError::new(http::StatusCode::FORBIDDEN).into(),
+
+            Self::InternalServerError(e) => ise(e),
+        }
+    }
+}
+
 #[api_v2_errors(
     code = 400,
     code = 409,
@@ -2,7 +2,10 @@ use crate::{
     domain::RegistrationStatus,
     error::BasicError,
     more_futures::ReadyOrNot,
-    token::{check_login_token, SessionClaims, SessionMask},
+    token::{
+        check_assignment_token, check_login_token, AssignmentSessionClaims, SessionClaims,
+        SessionMask,
+    },
 };
 use actix_http::error::BlockingError;
 use actix_web::{cookie::Cookie, http::HeaderMap, web::Data, Either, FromRequest, HttpMessage};
@@ -169,6 +172,15 @@ impl Scope for ScopeManageAudio {
     }
 }
 
+#[derive(Apiv2Schema)]
+pub struct ScopeManageClass;
+
+impl Scope for ScopeManageClass {
+    fn scope() -> UserScope {
+        UserScope::ManageClass
+    }
+}
+
 #[derive(Apiv2Security)]
 #[openapi(
     apiKey,
@@ -332,6 +344,119 @@ impl<S: SessionMaskRequirement> FromRequest for TokenSessionOf<S> {
     }
 }
 
+/// A student who joined an assignment by code.
+#[derive(Apiv2Security)]
+#[openapi(
+    apiKey,
+    alias = "assignmentApiKey",
+    in = "header",
+    name = "Authorization",
+    description = "Use format 'Bearer TOKEN'"
+)]
+#[repr(transparent)]
+pub struct TokenAssignmentStudent(pub AssignmentSessionClaims);
+
+impl FromRequest for TokenAssignmentStudent {
+    type Error = actix_web::Error;
+    type Future = ReadyOrNot<'static, Result<Self, Self::Error>>;
+    type Config = ();
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let cookie = req.cookie(AUTH_COOKIE_NAME);
+        let csrf = csrf_header(req.headers()).map(ToOwned::to_owned);
+
+        let settings: &Data<RuntimeSettings> = req.app_data().expect("Settings??");
+        let settings = Data::clone(settings);
+
+        let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
+        let db = db.as_ref().clone();
+
+        let (cookie, csrf) = match check_cookie_csrf(cookie, csrf.map(Cow::Owned)) {
+            Ok((cookie, csrf)) => (cookie, csrf.into_owned()),
+            Err(e) => return futures::future::err(e.into()).into(),
+        };
+
+        async move {
+            let csrf = csrf;
+            let claims =
+                check_assignment_token(&db, cookie.value(), &csrf, &settings.token_secret).await?;
+
+            Ok(Self(claims))
+        }
+        .boxed()
+        .into()
+    }
+}
+
+/// Someone playing a jig, either a logged in user or a student who joined an assignment.
+pub enum PlayerClaims {
+    User(SessionClaims),
+    AssignmentStudent(AssignmentSessionClaims),
+}
+
+#[derive(Apiv2Security)]
+#[openapi(
+    apiKey,
+    alias = "playerApiKey",
+    in = "header",
+    name = "Authorization",
+    description = "Use format 'Bearer TOKEN'"
+)]
+#[repr(transparent)]
+pub struct TokenPlayer(pub PlayerClaims);
+
+impl FromRequest for TokenPlayer {
+    type Error = actix_web::Error;
+    type Future = ReadyOrNot<'static, Result<Self, Self::Error>>;
+    type Config = ();
+    fn from_request(
+        req: &actix_web::HttpRequest,
+        _payload: &mut actix_web::dev::Payload,
+    ) -> Self::Future {
+        let cookie = req.cookie(AUTH_COOKIE_NAME);
+        let csrf = csrf_header(req.headers()).map(ToOwned::to_owned);
+
+        let settings: &Data<RuntimeSettings> = req.app_data().expect("Settings??");
+        let settings = Data::clone(settings);
+
+        let db: &Data<PgPool> = req.app_data().expect("Missing `Data` for db?");
+        let db = db.as_ref().clone();
+
+        let (cookie, csrf) = match check_cookie_csrf(cookie, csrf.map(Cow::Owned)) {
+            Ok((cookie, csrf)) => (cookie, csrf.into_owned()),
+            Err(e) => return futures::future::err(e.into()).into(),
+        };
+
+        async move {
+            let csrf = csrf;
+
+            // most requests come from users, so try that first.
+            match check_login_token(
+                &db,
+                cookie.value(),
+                &csrf,
+                &settings.token_secret,
+                SessionMask::GENERAL_API,
+            )
+            .await
+            {
+                Ok(claims) => Ok(Self(PlayerClaims::User(claims))),
+                Err(_) => {
+                    let claims =
+                        check_assignment_token(&db, cookie.value(), &csrf, &settings.token_secret)
+                            .await?;
+
+                    Ok(Self(PlayerClaims::AssignmentStudent(claims)))
+                }
+            }
+        }
+        .boxed()
+        .into()
+    }
+}
+
 #[derive(Apiv2Security)]
 #[openapi(
     apiKey,
@@ -17,8 +17,8 @@ use sqlx::postgres::PgPool;
 
 use crate::{
     error::BasicError,
-    s3, service,
     service::{mail, ServiceData},
+    storage::MediaStorage,
 };
 
 mod cors;
@@ -108,8 +108,7 @@ impl Drop for Application {
 pub async fn build_and_run(
     pool: PgPool,
     settings: RuntimeSettings,
-    s3: Option<s3::Client>,
-    gcs: Option<service::storage::Client>,
+    storage: Option<Arc<dyn MediaStorage>>,
     algolia: Option<crate::algolia::Client>,
     algolia_key_store: Option<crate::algolia::SearchKeyStore>,
     jwk_verifier: Arc<crate::jwk::JwkVerifier>,
@@ -118,8 +117,7 @@ pub async fn build_and_run(
     let app = build(
         pool,
         settings,
-        s3,
-        gcs,
+        storage,
         algolia,
         algolia_key_store,
         jwk_verifier,
@@ -133,19 +131,53 @@ pub async fn build_and_run(
 pub fn build(
     pool: PgPool,
     settings: RuntimeSettings,
-    s3: Option<s3::Client>,
-    gcs: Option<service::storage::Client>,
+    storage: Option<Arc<dyn MediaStorage>>,
+    algolia: Option<crate::algolia::Client>,
+    algolia_key_store: Option<crate::algolia::SearchKeyStore>,
+    jwk_verifier: Arc<crate::jwk::JwkVerifier>,
+    mail_client: Option<mail::Client>,
+) -> anyhow::Result<Application> {
+    let listener = listen(settings.api_port)?;
+
+    build_with_listener(
+        listener,
+        pool,
+        settings,
+        storage,
+        algolia,
+        algolia_key_store,
+        jwk_verifier,
+        mail_client,
+    )
+}
+
+/// Binds the listener the api is served on.
+pub fn listen(api_port: u16) -> std::io::Result<TcpListener> {
+    // if listenfd doesn't take a TcpListener (i.e. we're not running via
+    // the command above), we fall back to explicitly binding to a given
+    // host:port.
+    match get_tcp_fd() {
+        Some(listener) => Ok(listener),
+        None => TcpListener::bind(get_addr(Some(api_port))),
+    }
+}
+
+/// Like [`build`], but serves on an already bound `listener`,
+/// so anything that needs the api's url (like a [`LocalStorage`](crate::storage::LocalStorage)) can be created with its port.
+pub fn build_with_listener(
+    listener: TcpListener,
+    pool: PgPool,
+    settings: RuntimeSettings,
+    storage: Option<Arc<dyn MediaStorage>>,
     algolia: Option<crate::algolia::Client>,
     algolia_key_store: Option<crate::algolia::SearchKeyStore>,
     jwk_verifier: Arc<crate::jwk::JwkVerifier>,
     mail_client: Option<mail::Client>,
 ) -> anyhow::Result<Application> {
     let local_insecure = settings.is_local();
-    let api_port = settings.api_port;
 
-    let s3 = s3.map(ServiceData::new);
-    let gcs = gcs.map(ServiceData::new);
-    let algolia = algolia.map(ServiceData::new);
+    let storage = storage.map(ServiceData::from);
+    let search = ServiceData::new(crate::search::Client::new(algolia, pool.clone()));
     let algolia_key_store = algolia_key_store.map(ServiceData::new);
     let mail_client = mail_client.map(ServiceData::new);
 
@@ -154,18 +186,10 @@ pub fn build(
             .data(pool.clone())
             .data(settings.clone());
 
-        let server = match s3.clone() {
-            Some(s3) => server.app_data(s3),
-            None => server,
-        };
-
-        let server = match gcs.clone() {
-            Some(gcs) => server.app_data(gcs),
-            None => server,
-        };
-
-        let server = match algolia.clone() {
-            Some(algolia) => server.app_data(algolia),
+        let server = match storage.clone() {
+            Some(storage) => server
+                .app_data(storage.clone())
+                .configure(|cfg| storage.configure(cfg)),
             None => server,
         };
 
@@ -180,6 +204,7 @@ pub fn build(
         };
 
         server
+            .app_data(search.clone())
             .app_data(Data::from(jwk_verifier.clone()))
             .wrap(actix_web::middleware::Logger::default())
             .wrap_fn(log_ise)
@@ -204,6 +229,7 @@ pub fn build(
             .wrap_api()
             .configure(endpoints::user::configure)
             .configure(endpoints::category::configure)
+            .configure(endpoints::class::configure)
             .configure(endpoints::image::configure)
             .configure(endpoints::audio::configure)
             .configure(endpoints::meta::configure)
@@ -221,15 +247,6 @@ pub fn build(
             .build()
     });
 
-    // if listenfd doesn't take a TcpListener (i.e. we're not running via
-    // the command above), we fall back to explicitly binding to a given
-    // host:port.
-    let listener = if let Some(l) = get_tcp_fd() {
-        l
-    } else {
-        TcpListener::bind(get_addr(Some(api_port)))?
-    };
-
     let port = listener.local_addr().unwrap().port();
 
     let server = server.listen(listener)?;
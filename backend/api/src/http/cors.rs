@@ -15,8 +15,10 @@ pub fn get(local_insecure: bool) -> actix_cors::Cors {
         .allowed_headers(&[
             header::AUTHORIZATION,
             header::CONTENT_TYPE,
+            header::IF_MATCH,
             header::HeaderName::from_static("x-csrf"),
-        ]);
+        ])
+        .expose_headers(&[header::ETAG]);
 
     if local_insecure {
         cors = cors.allow_any_origin();
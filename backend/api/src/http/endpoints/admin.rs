@@ -9,13 +9,17 @@ use chrono::{DateTime, Duration, Utc};
 use futures::TryStreamExt;
 use paperclip::actix::{
     api_v2_operation,
-    web::{Data, HttpRequest, Path, ServiceConfig},
+    web::{self, Data, HttpRequest, Path, Query, ServiceConfig},
     NoContent,
 };
 use shared::{
     api::{endpoints::admin, ApiEndpoint},
     domain::{
-        admin::{AdminListMediaResponse, AdminMediaItem},
+        admin::{
+            AdminImageDuplicate, AdminImageDuplicatesResponse, AdminIndexHealth,
+            AdminListMediaResponse, AdminMediaItem, AdminSearchHealthResponse, SearchBackend,
+            SearchIndexEntity,
+        },
         image::ImageKind,
         session::NewSessionResponse,
     },
@@ -27,9 +31,10 @@ use uuid::Uuid;
 use crate::{
     db, error,
     extractor::{ScopeAdmin, TokenUserWithScope},
-    image_ops::{regenerate_images, MediaKind},
-    s3,
+    image_ops::{load_image, regenerate_images, MediaKind},
+    search,
     service::ServiceData,
+    storage::MediaStorage,
     token::{create_auth_token, SessionMask},
 };
 
@@ -79,7 +84,7 @@ async fn impersonate(
 #[api_v2_operation]
 async fn refresh_image_files(
     _auth: TokenUserWithScope<ScopeAdmin>,
-    s3: ServiceData<s3::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     db: Data<PgPool>,
     Path((library, id)): Path<(MediaLibrary, Uuid)>,
     req: HttpRequest,
@@ -169,14 +174,23 @@ async fn refresh_image_files(
     }
 
     // handle the 404 here (image isn't uploaded)
-    let original = s3
-        .download_media_file(library, id, FileKind::ImagePng(PngImageFile::Original))
+    let original = storage
+        .download_image_original(library, id)
         .await?
         .ok_or(error::Refresh::ResourceNotFound)?;
 
+    // global and user library images are processed again, which also (re)generates their perceptual hash,
+    // and any formats they're missing.
+    // the original is uploaded like it was the first time (as a PNG, whatever it is, processing sniffs its format).
     match library {
         MediaLibrary::Global => {
-            s3.back_copy_unprocessed_file(library, id, FileKind::ImagePng(PngImageFile::Original))
+            storage
+                .upload_media_for_processing(
+                    original,
+                    library,
+                    id,
+                    FileKind::ImagePng(PngImageFile::Original),
+                )
                 .await?;
 
             sqlx::query!("update image_upload set uploaded_at = now(), processing_result = null where image_id = $1", id)
@@ -187,7 +201,13 @@ async fn refresh_image_files(
         }
 
         MediaLibrary::User => {
-            s3.back_copy_unprocessed_file(library, id, FileKind::ImagePng(PngImageFile::Original))
+            storage
+                .upload_media_for_processing(
+                    original,
+                    library,
+                    id,
+                    FileKind::ImagePng(PngImageFile::Original),
+                )
                 .await?;
 
             sqlx::query!("update user_image_upload set uploaded_at = now(), processing_result = null where image_id = $1", id)
@@ -201,9 +221,11 @@ async fn refresh_image_files(
     }
 
     let (resized, thumbnail) = actix_web::web::block(move || -> Result<_, error::Refresh> {
-        let original = image::load_from_memory(&original)?;
+        let original = load_image(&original, kind)
+            .map_err(|_| anyhow::anyhow!("failed to load the original"))?;
 
-        Ok(regenerate_images(&original, kind)?)
+        regenerate_images(&original, kind)
+            .map_err(|_| anyhow::anyhow!("failed to regenerate images").into())
     })
     .await
     .map_err(|err| match err {
@@ -211,7 +233,8 @@ async fn refresh_image_files(
         BlockingError::Error(e) => e,
     })?;
 
-    s3.upload_png_images_resized_thumb(library, id, resized, thumbnail)
+    storage
+        .upload_resized_images(library, id, resized, thumbnail)
         .await?;
 
     match library {
@@ -262,6 +285,95 @@ async fn list_media(
     Ok(Json(AdminListMediaResponse { media: items }))
 }
 
+/// Reindex content for search
+#[api_v2_operation]
+async fn reindex(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    search: ServiceData<search::Client>,
+    req: web::Json<<admin::Reindex as ApiEndpoint>::Req>,
+) -> actix_web::Result<NoContent, error::Service> {
+    if let search::Client::Postgres(_) = &*search {
+        return Err(error::Service::DisabledService(error::ServiceKind::Algolia));
+    }
+
+    let mut entities = req.into_inner().entities;
+
+    if entities.is_empty() {
+        entities = vec![
+            SearchIndexEntity::Image,
+            SearchIndexEntity::Animation,
+            SearchIndexEntity::Jig,
+        ];
+    }
+
+    db::search::reindex(&db, &entities).await?;
+
+    Ok(NoContent)
+}
+
+/// Compare the database with the search indexes
+#[api_v2_operation]
+async fn search_health(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    search: ServiceData<search::Client>,
+) -> actix_web::Result<Json<<admin::SearchHealth as ApiEndpoint>::Res>, error::Server> {
+    let (images, animations, jigs) = db::search::sync_counts(&db).await?;
+
+    let index_counts = search.index_counts().await?;
+
+    let backend = match &*search {
+        search::Client::Algolia(_) => SearchBackend::Algolia,
+        search::Client::Postgres(_) => SearchBackend::Postgres,
+    };
+
+    let health = |counts: db::search::SyncCounts, index_count: Option<u64>| AdminIndexHealth {
+        db_count: counts.total,
+        // nothing gets synced when searching postgres.
+        pending_sync: index_count.map(|_| counts.pending),
+        index_count,
+    };
+
+    Ok(Json(AdminSearchHealthResponse {
+        backend,
+        images: health(images, index_counts.as_ref().map(|it| it.images)),
+        animations: health(animations, index_counts.as_ref().map(|it| it.animations)),
+        jigs: health(jigs, index_counts.as_ref().map(|it| it.jigs)),
+    }))
+}
+
+/// Report near-duplicate images in the global library
+#[api_v2_operation]
+async fn image_duplicates(
+    _auth: TokenUserWithScope<ScopeAdmin>,
+    db: Data<PgPool>,
+    query: Option<Query<<admin::ImageDuplicates as ApiEndpoint>::Req>>,
+) -> Result<Json<<admin::ImageDuplicates as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let max_distance = query
+        .max_distance
+        .unwrap_or(config::IMAGE_DUPLICATE_MAX_DISTANCE);
+
+    let limit = query
+        .limit
+        .unwrap_or(config::IMAGE_DUPLICATES_DEFAULT_LIMIT)
+        .min(config::IMAGE_DUPLICATES_MAX_LIMIT);
+
+    let duplicates = db::image::duplicates(&db, max_distance, limit)
+        .await?
+        .into_iter()
+        .map(|(image_id, duplicate_id, distance)| AdminImageDuplicate {
+            image_id,
+            duplicate_id,
+            distance,
+        })
+        .collect();
+
+    Ok(Json(AdminImageDuplicatesResponse { duplicates }))
+}
+
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
     cfg.route(
         admin::Impersonate::PATH,
@@ -274,5 +386,17 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
     .route(
         admin::ListMedia::PATH,
         admin::ListMedia::METHOD.route().to(list_media),
+    )
+    .route(
+        admin::Reindex::PATH,
+        admin::Reindex::METHOD.route().to(reindex),
+    )
+    .route(
+        admin::SearchHealth::PATH,
+        admin::SearchHealth::METHOD.route().to(search_health),
+    )
+    .route(
+        admin::ImageDuplicates::PATH,
+        admin::ImageDuplicates::METHOD.route().to(image_duplicates),
     );
 }
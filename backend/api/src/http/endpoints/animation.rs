@@ -1,25 +1,29 @@
 use chrono::{DateTime, Utc};
+use futures::TryStreamExt;
 use paperclip::actix::{
     api_v2_operation,
-    web::{self, Data, Json, Path, PayloadConfig, ServiceConfig},
+    web::{self, Data, Json, Path, PayloadConfig, Query, ServiceConfig},
     CreatedJson, NoContent,
 };
 use shared::{
     api::{endpoints::animation, ApiEndpoint},
     domain::{
-        animation::{AnimationId, AnimationKind, AnimationResponse},
+        animation::{
+            AnimationId, AnimationKind, AnimationMetadata, AnimationResponse,
+            AnimationSearchResponse,
+        },
         CreateResponse,
     },
-    media::{FileKind, MediaLibrary},
+    media::{FileKind, MediaLibrary, SpritesheetFile},
 };
 use sqlx::{postgres::PgDatabaseError, PgPool};
 
-use crate::service::storage;
 use crate::{
     db, error,
     extractor::{ScopeManageAnimation, TokenUser, TokenUserWithScope},
-    s3,
+    search::SearchResults,
     service::ServiceData,
+    storage::MediaStorage,
 };
 use shared::domain::animation::AnimationUploadResponse;
 
@@ -38,7 +42,8 @@ async fn delete(
     db: Data<PgPool>,
     _claims: TokenUserWithScope<ScopeManageAnimation>,
     req: Path<AnimationId>,
-    s3: ServiceData<s3::Client>,
+    storage: ServiceData<dyn MediaStorage>,
+    search: ServiceData<crate::search::Client>,
 ) -> Result<NoContent, error::Delete> {
     let animation = req.into_inner();
     let kind = db::animation::delete(&db, animation)
@@ -46,14 +51,20 @@ async fn delete(
         .map_err(check_conflict_delete)?;
 
     if let Some(kind) = kind {
-        let file = match kind {
-            AnimationKind::Gif => FileKind::AnimationGif,
-            // todo:
-            _ => return Err(anyhow::anyhow!("Unsupported animation kind").into()),
-        };
-
-        s3.delete_media(MediaLibrary::Global, file, animation.0)
-            .await;
+        let delete = |file| storage.delete_media(MediaLibrary::Global, file, animation.0);
+
+        match kind {
+            AnimationKind::Gif => delete(FileKind::AnimationGif).await,
+            AnimationKind::Spritesheet => {
+                futures::future::join(
+                    delete(FileKind::Spritesheet(SpritesheetFile::Sheet)),
+                    delete(FileKind::Spritesheet(SpritesheetFile::Frames)),
+                )
+                .await;
+            }
+        }
+
+        search.delete_animation(animation).await;
     }
 
     Ok(NoContent)
@@ -101,7 +112,7 @@ async fn create(
 #[api_v2_operation]
 async fn upload(
     db: Data<PgPool>,
-    gcs: ServiceData<storage::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     _claims: TokenUserWithScope<ScopeManageAnimation>,
     Path(id): Path<AnimationId>,
     req: Json<<animation::Upload as ApiEndpoint>::Req>,
@@ -119,20 +130,27 @@ async fn upload(
         return Err(error::Upload::ResourceNotFound);
     }
 
-    let upload_content_length = req.into_inner().file_size;
+    let req = req.into_inner();
+    let upload_content_length = req.file_size;
+
+    // spritesheet animations can also be uploaded as a GIF, which gets converted.
+    let file_kind = match req.spritesheet_file {
+        Some(file) => FileKind::Spritesheet(file),
+        None => FileKind::AnimationGif,
+    };
 
-    if let Some(file_limit) = gcs.file_size_limit(&FileKind::AnimationGif) {
+    if let Some(file_limit) = storage.file_size_limit(&file_kind) {
         if file_limit < upload_content_length {
             return Err(error::Upload::FileTooLarge);
         }
     }
 
-    let resp = gcs
+    let resp = storage
         .get_url_for_resumable_upload_for_processing(
             upload_content_length,
             MediaLibrary::Global,
             id.0,
-            FileKind::AnimationGif,
+            file_kind,
         )
         .await?;
 
@@ -162,8 +180,51 @@ async fn get_one(
     Ok(Json(AnimationResponse { metadata }))
 }
 
+/// Search for animations in the global animation library.
+#[api_v2_operation]
+async fn search(
+    db: Data<PgPool>,
+    search: ServiceData<crate::search::Client>,
+    _claims: TokenUser,
+    query: Option<Query<<animation::Search as ApiEndpoint>::Req>>,
+) -> Result<Json<<animation::Search as ApiEndpoint>::Res>, error::Service> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let SearchResults {
+        ids,
+        pages,
+        total_hits,
+        facets: (),
+    } = search
+        .search_animation(
+            &query.q,
+            query.kind,
+            query.page,
+            query.is_premium,
+            query.is_published,
+            &query.styles,
+        )
+        .await?;
+
+    let animations: Vec<_> = db::animation::get(db.as_ref(), &ids)
+        .err_into::<error::Service>()
+        .and_then(|metadata: AnimationMetadata| async { Ok(AnimationResponse { metadata }) })
+        .try_collect()
+        .await?;
+
+    Ok(Json(AnimationSearchResponse {
+        animations,
+        pages,
+        total_animation_count: total_hits,
+    }))
+}
+
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
     cfg.route(
+        animation::Search::PATH,
+        animation::Search::METHOD.route().to(search),
+    )
+    .route(
         animation::Create::PATH,
         animation::Create::METHOD.route().to(create),
     )
@@ -1,4 +1,4 @@
-use paperclip::actix::web::ServiceConfig;
+use paperclip::actix::web::{self, PayloadConfig, ServiceConfig};
 use shared::api::{endpoints::audio, ApiEndpoint};
 use sqlx::postgres::PgDatabaseError;
 
@@ -14,46 +14,69 @@ fn check_conflict_delete(err: sqlx::Error) -> error::Delete {
 }
 
 pub mod user {
-    use crate::{db, error, extractor::TokenUser, s3, service::ServiceData};
+    use crate::{
+        audio_ops, db, error, extractor::TokenUser, module_media::MediaRefKind,
+        service::ServiceData, storage::MediaStorage,
+    };
     use futures::TryStreamExt;
     use paperclip::actix::{
         api_v2_operation,
-        web::{Bytes, Data, Json, Path},
+        web::{Bytes, Data, Json, Path, Query},
         CreatedJson, NoContent,
     };
     use shared::{
         api::{endpoints, ApiEndpoint},
         domain::{
             audio::{
-                user::{UserAudio, UserAudioListResponse, UserAudioResponse},
+                user::{
+                    UserAudio, UserAudioListResponse, UserAudioResponse, UserAudioSearchResponse,
+                },
                 AudioId,
             },
+            media::MediaUsageResponse,
             CreateResponse,
         },
+        error::MediaInUse,
         media::{FileKind, MediaLibrary},
+        search::normalize,
     };
     use sqlx::PgPool;
 
+    const SEARCH_PAGE_SIZE: u32 = 20;
+
     /// Create a audio file in the user's audio library.
     #[api_v2_operation]
     pub(super) async fn create(
         db: Data<PgPool>,
-        _claims: TokenUser,
+        claims: TokenUser,
     ) -> Result<CreatedJson<<endpoints::audio::user::Create as ApiEndpoint>::Res>, error::NotFound>
     {
-        let id = db::audio::user::create(db.as_ref()).await?;
+        let id = db::audio::user::create(db.as_ref(), claims.0.user_id).await?;
         Ok(CreatedJson(CreateResponse { id }))
     }
 
-    /// upload a audio file to the user's audio library.
+    /// Upload an audio file to the user's audio library.
+    ///
+    /// The file is transcoded to MP3, trimmed and normalized.
     #[api_v2_operation]
     pub(super) async fn upload(
         db: Data<PgPool>,
-        s3: ServiceData<s3::Client>,
+        storage: ServiceData<dyn MediaStorage>,
         _claims: TokenUser,
         Path(id): Path<AudioId>,
         bytes: Bytes,
     ) -> Result<NoContent, error::Upload> {
+        sqlx::query!(
+            r#"select 1 as discard from user_audio_library where id = $1"#,
+            id.0
+        )
+        .fetch_optional(db.as_ref())
+        .await?
+        .ok_or(error::Upload::ResourceNotFound)?;
+
+        // transcoding takes a while, so it's done before the row is locked.
+        let audio = audio_ops::process(&bytes).await?;
+
         let mut txn = db.begin().await?;
 
         sqlx::query!(
@@ -64,25 +87,11 @@ pub mod user {
         .await?
         .ok_or(error::Upload::ResourceNotFound)?;
 
-        // todo: use the duration
-        let _duration = {
-            let bytes = bytes.clone();
-            tokio::task::spawn_blocking(move || {
-                mp3_metadata::read_from_slice(&bytes).map_err(|_it| error::Upload::InvalidMedia)
-            })
-            .await
-            .unwrap()?
-        };
-
-        s3.upload_media(bytes.to_vec(), MediaLibrary::User, id.0, FileKind::AudioMp3)
+        storage
+            .upload_media(audio.mp3, MediaLibrary::User, id.0, FileKind::AudioMp3)
             .await?;
 
-        sqlx::query!(
-            "update user_audio_library set uploaded_at = now() where id = $1",
-            id.0
-        )
-        .execute(&mut txn)
-        .await?;
+        db::audio::user::set_uploaded(&mut txn, id, audio.duration_ms, &audio.waveform).await?;
 
         txn.commit().await?;
 
@@ -95,14 +104,31 @@ pub mod user {
         db: Data<PgPool>,
         _claims: TokenUser,
         req: Path<AudioId>,
-        s3: ServiceData<s3::Client>,
+        query: Query<<endpoints::audio::user::Delete as ApiEndpoint>::Req>,
+        storage: ServiceData<dyn MediaStorage>,
     ) -> Result<NoContent, error::Delete> {
         let audio = req.into_inner();
+
+        if !query.force {
+            let jigs = db::module_media_ref::list_jigs(
+                &db,
+                MediaRefKind::Audio,
+                MediaLibrary::User,
+                audio.0,
+            )
+            .await?;
+
+            if !jigs.is_empty() {
+                return Err(error::Delete::InUse(MediaInUse { jigs }));
+            }
+        }
+
         db::audio::user::delete(&db, audio)
             .await
             .map_err(super::check_conflict_delete)?;
 
-        s3.delete_media(MediaLibrary::User, FileKind::AudioMp3, audio.0)
+        storage
+            .delete_media(MediaLibrary::User, FileKind::AudioMp3, audio.0)
             .await;
 
         Ok(NoContent)
@@ -136,6 +162,83 @@ pub mod user {
 
         Ok(Json(UserAudioListResponse { audio_files }))
     }
+
+    /// Update an audio file's name and tags in the user's audio library.
+    #[api_v2_operation]
+    pub(super) async fn update(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        req: Path<AudioId>,
+        body: Json<<endpoints::audio::user::Update as ApiEndpoint>::Req>,
+    ) -> Result<NoContent, error::NotFound> {
+        let body = body.into_inner();
+
+        let exists = db::audio::user::update(
+            &db,
+            claims.0.user_id,
+            req.into_inner(),
+            body.name.as_deref(),
+            body.tags.as_deref(),
+        )
+        .await?;
+
+        if !exists {
+            return Err(error::NotFound::ResourceNotFound);
+        }
+
+        Ok(NoContent)
+    }
+
+    /// Search the audio files the user uploaded to their audio library.
+    #[api_v2_operation]
+    pub(super) async fn search(
+        db: Data<PgPool>,
+        claims: TokenUser,
+        query: Option<Query<<endpoints::audio::user::Search as ApiEndpoint>::Req>>,
+    ) -> Result<Json<<endpoints::audio::user::Search as ApiEndpoint>::Res>, error::Server> {
+        let query = query.map_or_else(Default::default, Query::into_inner);
+
+        let (audio_files, total_audio_count) = db::audio::user::search(
+            &db,
+            claims.0.user_id,
+            &normalize(&query.q),
+            query.page.unwrap_or(0),
+            SEARCH_PAGE_SIZE,
+        )
+        .await?;
+
+        let audio_files = audio_files
+            .into_iter()
+            .map(|metadata| UserAudioResponse { metadata })
+            .collect();
+
+        let page_size = u64::from(SEARCH_PAGE_SIZE);
+        let pages = ((total_audio_count + page_size - 1) / page_size) as u32;
+
+        Ok(Json(UserAudioSearchResponse {
+            audio_files,
+            pages,
+            total_audio_count,
+        }))
+    }
+
+    /// List the modules that use an audio file from the user's audio library.
+    #[api_v2_operation]
+    pub(super) async fn usage(
+        db: Data<PgPool>,
+        _claims: TokenUser,
+        req: Path<AudioId>,
+    ) -> Result<Json<<endpoints::audio::user::Usage as ApiEndpoint>::Res>, error::Server> {
+        let usages = db::module_media_ref::list_usages(
+            &db,
+            MediaRefKind::Audio,
+            MediaLibrary::User,
+            req.into_inner().0,
+        )
+        .await?;
+
+        Ok(Json(MediaUsageResponse { usages }))
+    }
 }
 
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
@@ -143,20 +246,33 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         audio::user::Create::PATH,
         audio::user::Create::METHOD.route().to(self::user::create),
     )
-    .route(
-        audio::user::Upload::PATH,
-        audio::user::Upload::METHOD.route().to(self::user::upload),
+    .service(
+        web::resource(audio::user::Upload::PATH)
+            .app_data(PayloadConfig::default().limit(config::AUDIO_BODY_SIZE_LIMIT))
+            .route(audio::user::Upload::METHOD.route().to(self::user::upload)),
     )
     .route(
         audio::user::Delete::PATH,
         audio::user::Delete::METHOD.route().to(self::user::delete),
     )
+    .route(
+        audio::user::Search::PATH,
+        audio::user::Search::METHOD.route().to(self::user::search),
+    )
     .route(
         audio::user::Get::PATH,
         audio::user::Get::METHOD.route().to(self::user::get),
     )
+    .route(
+        audio::user::Update::PATH,
+        audio::user::Update::METHOD.route().to(self::user::update),
+    )
     .route(
         audio::user::List::PATH,
         audio::user::List::METHOD.route().to(self::user::list),
+    )
+    .route(
+        audio::user::Usage::PATH,
+        audio::user::Usage::METHOD.route().to(self::user::usage),
     );
 }
@@ -0,0 +1,192 @@
+use paperclip::actix::{
+    api_v2_operation,
+    web::{self, Data, Json, Query, ServiceConfig},
+    CreatedJson, NoContent,
+};
+use shared::{
+    api::{endpoints::class, ApiEndpoint},
+    domain::{
+        class::{ClassBrowseResponse, ClassId, ClassResponse},
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{ScopeManageClass, TokenUser, TokenUserWithScope},
+};
+
+mod assignment;
+
+/// Create a class.
+#[api_v2_operation]
+async fn create(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeManageClass>,
+    req: Json<<class::Create as ApiEndpoint>::Req>,
+) -> Result<CreatedJson<<class::Create as ApiEndpoint>::Res>, error::Server> {
+    let req = req.into_inner();
+
+    let id = db::class::create(&*db, &req.name, auth.claims.user_id).await?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
+
+/// Get a class.
+#[api_v2_operation]
+async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<ClassId>,
+) -> Result<Json<<class::Get as ApiEndpoint>::Res>, error::NotFound> {
+    let id = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, id).await?;
+
+    let class = db::class::get(&db, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(ClassResponse { class }))
+}
+
+/// Update a class.
+#[api_v2_operation]
+async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Option<Json<<class::Update as ApiEndpoint>::Req>>,
+    path: web::Path<ClassId>,
+) -> Result<NoContent, error::NotFound> {
+    let id = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, id).await?;
+
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    let exists = db::class::update(&*db, id, req.name.as_deref()).await?;
+
+    if !exists {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(NoContent)
+}
+
+/// Delete a class.
+#[api_v2_operation]
+async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<ClassId>,
+) -> Result<NoContent, error::Delete> {
+    let id = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, id).await?;
+
+    db::class::delete(&*db, id).await?;
+
+    Ok(NoContent)
+}
+
+/// Browse the current user's classes.
+#[api_v2_operation]
+async fn browse(
+    db: Data<PgPool>,
+    auth: TokenUserWithScope<ScopeManageClass>,
+    query: Option<Query<<class::Browse as ApiEndpoint>::Req>>,
+) -> Result<Json<<class::Browse as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let classes = db::class::list(
+        db.as_ref(),
+        auth.claims.user_id,
+        query.page.unwrap_or(0) as i32,
+    )
+    .await?;
+
+    let total_count = db::class::count(db.as_ref(), auth.claims.user_id).await?;
+
+    let pages = (total_count / 20 + (total_count % 20 != 0) as u64) as u32;
+
+    Ok(Json(ClassBrowseResponse {
+        classes,
+        pages,
+        total_class_count: total_count,
+    }))
+}
+
+pub fn configure(cfg: &mut ServiceConfig<'_>) {
+    cfg.route(
+        class::Browse::PATH,
+        class::Browse::METHOD.route().to(browse),
+    )
+    .route(
+        class::Create::PATH,
+        class::Create::METHOD.route().to(create),
+    )
+    .route(class::Get::PATH, class::Get::METHOD.route().to(get))
+    .route(
+        class::Update::PATH,
+        class::Update::METHOD.route().to(update),
+    )
+    .route(
+        class::Delete::PATH,
+        class::Delete::METHOD.route().to(delete),
+    )
+    .route(
+        class::assignment::Join::PATH,
+        class::assignment::Join::METHOD
+            .route()
+            .to(self::assignment::join),
+    )
+    .route(
+        class::assignment::CreateSession::PATH,
+        class::assignment::CreateSession::METHOD
+            .route()
+            .to(self::assignment::create_session),
+    )
+    .route(
+        class::assignment::CreateModuleResult::PATH,
+        class::assignment::CreateModuleResult::METHOD
+            .route()
+            .to(self::assignment::create_module_result),
+    )
+    .route(
+        class::assignment::List::PATH,
+        class::assignment::List::METHOD
+            .route()
+            .to(self::assignment::list),
+    )
+    .route(
+        class::assignment::Create::PATH,
+        class::assignment::Create::METHOD
+            .route()
+            .to(self::assignment::create),
+    )
+    .route(
+        class::assignment::Get::PATH,
+        class::assignment::Get::METHOD
+            .route()
+            .to(self::assignment::get),
+    )
+    .route(
+        class::assignment::Update::PATH,
+        class::assignment::Update::METHOD
+            .route()
+            .to(self::assignment::update),
+    )
+    .route(
+        class::assignment::Delete::PATH,
+        class::assignment::Delete::METHOD
+            .route()
+            .to(self::assignment::delete),
+    )
+    .route(
+        class::assignment::Results::PATH,
+        class::assignment::Results::METHOD
+            .route()
+            .to(self::assignment::results),
+    );
+}
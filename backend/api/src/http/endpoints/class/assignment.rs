@@ -0,0 +1,223 @@
+use actix_web::HttpResponse;
+use chrono::{Duration, Utc};
+use core::settings::RuntimeSettings;
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Data, Json, Path},
+    CreatedJson, NoContent,
+};
+use shared::{
+    api::{endpoints::class::assignment, ApiEndpoint},
+    domain::{
+        class::{
+            assignment::{
+                AssignmentId, AssignmentJoinResponse, AssignmentListResponse, AssignmentResponse,
+                AssignmentResultsResponse,
+            },
+            ClassId,
+        },
+        jig::player::JigPlayerSessionId,
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{
+    db, error,
+    extractor::{TokenAssignmentStudent, TokenUser},
+    token::create_auth_token,
+};
+
+/// How long a student stays joined to an assignment before they need to enter the code again.
+const STUDENT_SESSION_TTL_HOURS: i64 = 24;
+
+/// Assign a jig to a class.
+#[api_v2_operation]
+pub(super) async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ClassId>,
+    req: Json<<assignment::Create as ApiEndpoint>::Req>,
+) -> Result<CreatedJson<<assignment::Create as ApiEndpoint>::Res>, error::NotFound> {
+    let class_id = path.into_inner();
+    let req = req.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    let id = db::class::assignment::create(&*db, class_id, req.jig_id, req.due_at).await?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
+
+/// Get an assignment.
+#[api_v2_operation]
+pub(super) async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(ClassId, AssignmentId)>,
+) -> Result<Json<<assignment::Get as ApiEndpoint>::Res>, error::NotFound> {
+    let (class_id, id) = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    let assignment = db::class::assignment::get(&*db, class_id, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(AssignmentResponse { assignment }))
+}
+
+/// List a class's assignments.
+#[api_v2_operation]
+pub(super) async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<ClassId>,
+) -> Result<Json<<assignment::List as ApiEndpoint>::Res>, error::NotFound> {
+    let class_id = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    let assignments = db::class::assignment::list(&*db, class_id).await?;
+
+    Ok(Json(AssignmentListResponse { assignments }))
+}
+
+/// Update an assignment.
+#[api_v2_operation]
+pub(super) async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(ClassId, AssignmentId)>,
+    req: Option<Json<<assignment::Update as ApiEndpoint>::Req>>,
+) -> Result<NoContent, error::NotFound> {
+    let (class_id, id) = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    let req = req.map_or_else(Default::default, Json::into_inner);
+
+    let exists = db::class::assignment::update(&*db, class_id, id, req.due_at).await?;
+
+    if !exists {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(NoContent)
+}
+
+/// Delete an assignment.
+#[api_v2_operation]
+pub(super) async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(ClassId, AssignmentId)>,
+) -> Result<NoContent, error::Delete> {
+    let (class_id, id) = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    db::class::assignment::delete(&*db, class_id, id).await?;
+
+    Ok(NoContent)
+}
+
+/// Get the students who joined an assignment, along with their results.
+#[api_v2_operation]
+pub(super) async fn results(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(ClassId, AssignmentId)>,
+) -> Result<Json<<assignment::Results as ApiEndpoint>::Res>, error::NotFound> {
+    let (class_id, id) = path.into_inner();
+
+    db::class::authz(&*db, claims.0.user_id, class_id).await?;
+
+    // make sure the assignment belongs to the class.
+    db::class::assignment::get(&*db, class_id, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let students = db::class::assignment::results(&*db, id).await?;
+
+    Ok(Json(AssignmentResultsResponse { students }))
+}
+
+/// Join an assignment with its code.
+#[api_v2_operation]
+pub(super) async fn join(
+    db: Data<PgPool>,
+    settings: Data<RuntimeSettings>,
+    req: Json<<assignment::Join as ApiEndpoint>::Req>,
+) -> Result<HttpResponse, error::NotFound> {
+    let req = req.into_inner();
+
+    let ttl = Duration::hours(STUDENT_SESSION_TTL_HOURS);
+
+    let mut txn = db.begin().await?;
+
+    let (student_id, assignment_id, jig_id) =
+        db::class::assignment::join(&mut txn, &req.code, &req.name)
+            .await?
+            .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let session =
+        db::session::create_for_assignment_student(&mut txn, student_id, &(Utc::now() + ttl))
+            .await?;
+
+    let (csrf, cookie) =
+        create_auth_token(&settings.token_secret, settings.is_local(), ttl, &session)?;
+
+    txn.commit().await?;
+
+    Ok(HttpResponse::Created()
+        .cookie(cookie)
+        .json(AssignmentJoinResponse {
+            csrf,
+            assignment_id,
+            jig_id,
+        }))
+}
+
+/// Start a play session for the joined assignment.
+#[api_v2_operation]
+pub(super) async fn create_session(
+    db: Data<PgPool>,
+    student: TokenAssignmentStudent,
+) -> Result<CreatedJson<<assignment::CreateSession as ApiEndpoint>::Res>, error::Server> {
+    let id = db::player_session::create_for_assignment_student(&*db, student.0.student_id).await?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
+
+/// Record the result of a module in an assignment play session.
+#[api_v2_operation]
+pub(super) async fn create_module_result(
+    db: Data<PgPool>,
+    student: TokenAssignmentStudent,
+    path: Path<JigPlayerSessionId>,
+    req: Json<<assignment::CreateModuleResult as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::JigPlayerResult> {
+    let session_id = path.into_inner();
+    let req = req.into_inner();
+
+    let is_own_session =
+        db::player_session::is_assignment_student_session(&*db, student.0.student_id, session_id)
+            .await?;
+
+    if !is_own_session {
+        return Err(error::JigPlayerResult::ResourceNotFound);
+    }
+
+    db::player_session::create_module_result(
+        &*db,
+        session_id,
+        req.module_id,
+        req.score,
+        req.attempts,
+        req.time_spent,
+    )
+    .await?;
+
+    Ok(NoContent)
+}
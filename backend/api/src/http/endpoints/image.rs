@@ -7,20 +7,27 @@ use paperclip::actix::{
 };
 use shared::{
     api::{endpoints, ApiEndpoint},
-    domain::image::{
-        CreateResponse, ImageBrowseResponse, ImageId, ImageMetadata, ImageResponse,
-        ImageSearchResponse, ImageUpdateRequest, ImageUploadResponse,
+    domain::{
+        image::{
+            CreateResponse, ImageBrowseResponse, ImageId, ImageMetadata, ImageRelatedResponse,
+            ImageResponse, ImageSearchResponse, ImageSimilarResponse, ImageUpdateRequest,
+            ImageUploadResponse,
+        },
+        media::MediaUsageResponse,
     },
+    error::MediaInUse,
     media::{FileKind, MediaLibrary, PngImageFile},
 };
 use sqlx::{postgres::PgDatabaseError, PgPool};
 
 use crate::{
     db::{self, meta::handle_metadata_err, nul_if_empty},
-    error::{self, ServiceKind},
+    error,
     extractor::{ScopeManageImage, TokenUser, TokenUserWithScope},
-    s3, service,
+    module_media::MediaRefKind,
+    search::SearchResults,
     service::ServiceData,
+    storage::MediaStorage,
 };
 
 pub mod recent;
@@ -69,7 +76,7 @@ async fn create(
 #[api_v2_operation]
 async fn upload(
     db: Data<PgPool>,
-    gcs: ServiceData<service::storage::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     _claims: TokenUserWithScope<ScopeManageImage>,
     Path(id): Path<ImageId>,
     req: Json<<endpoints::image::Upload as ApiEndpoint>::Req>,
@@ -89,13 +96,13 @@ async fn upload(
 
     let upload_content_length = req.into_inner().file_size;
 
-    if let Some(file_limit) = gcs.file_size_limit(&FileKind::ImagePng(PngImageFile::Original)) {
+    if let Some(file_limit) = storage.file_size_limit(&FileKind::ImagePng(PngImageFile::Original)) {
         if file_limit < upload_content_length {
             return Err(error::Upload::FileTooLarge);
         }
     }
 
-    let resp = gcs
+    let resp = storage
         .get_url_for_resumable_upload_for_processing(
             upload_content_length,
             MediaLibrary::Global,
@@ -130,19 +137,95 @@ async fn get_one(
     Ok(Json(ImageResponse { metadata }))
 }
 
+/// Get published images related to an image from the global image library.
+#[api_v2_operation]
+async fn related(
+    db: Data<PgPool>,
+    _claims: TokenUser,
+    req: Path<ImageId>,
+    query: Option<Query<<endpoints::image::Related as ApiEndpoint>::Req>>,
+) -> Result<Json<<endpoints::image::Related as ApiEndpoint>::Res>, error::NotFound> {
+    let id = req.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    db::image::get_one(&db, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(config::RELATED_DEFAULT_LIMIT)
+        .min(config::RELATED_MAX_LIMIT);
+
+    let ids = db::image::related(&db, id, limit).await?;
+
+    let images: Vec<_> = db::image::get(db.as_ref(), &ids)
+        .err_into::<error::NotFound>()
+        .and_then(|metadata: ImageMetadata| async { Ok(ImageResponse { metadata }) })
+        .try_collect()
+        .await?;
+
+    Ok(Json(ImageRelatedResponse { images }))
+}
+
+/// Get images that look like an image from the global image library.
+#[api_v2_operation]
+async fn similar(
+    db: Data<PgPool>,
+    _claims: TokenUserWithScope<ScopeManageImage>,
+    req: Path<ImageId>,
+    query: Option<Query<<endpoints::image::Similar as ApiEndpoint>::Req>>,
+) -> Result<Json<<endpoints::image::Similar as ApiEndpoint>::Res>, error::NotFound> {
+    let id = req.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    db::image::get_one(&db, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(config::RELATED_DEFAULT_LIMIT)
+        .min(config::RELATED_MAX_LIMIT);
+
+    let ids = db::image::similar(&db, id, config::IMAGE_SIMILAR_MAX_DISTANCE, limit).await?;
+
+    let images: Vec<_> = db::image::get(db.as_ref(), &ids)
+        .err_into::<error::NotFound>()
+        .and_then(|metadata: ImageMetadata| async { Ok(ImageResponse { metadata }) })
+        .try_collect()
+        .await?;
+
+    Ok(Json(ImageSimilarResponse { images }))
+}
+
 /// Search for images in the global image library.
 #[api_v2_operation]
 async fn search(
     db: Data<PgPool>,
-    algolia: ServiceData<crate::algolia::Client>,
-    _claims: TokenUser,
+    search: ServiceData<crate::search::Client>,
+    claims: TokenUser,
     query: Option<Query<<endpoints::image::Search as ApiEndpoint>::Req>>,
 ) -> Result<Json<<endpoints::image::Search as ApiEndpoint>::Res>, error::Service> {
     let query = query.map_or_else(Default::default, Query::into_inner);
 
-    let (ids, pages, total_hits) = algolia
+    // only record the search once, rather than for every page of it.
+    // failing to record it shouldn't fail the search.
+    if !query.q.trim().is_empty() && query.page.unwrap_or(0) == 0 {
+        if let Err(e) = db::search::record_recent(&db, claims.0.user_id, query.q.trim()).await {
+            log::warn!("failed to record recent search: {:?}", e);
+        }
+    }
+
+    let SearchResults {
+        ids,
+        pages,
+        total_hits,
+        facets,
+    } = search
         .search_image(
             &query.q,
+            query.transliterate,
             query.page,
             query.is_premium,
             query.is_published,
@@ -152,8 +235,7 @@ async fn search(
             &query.categories,
             &query.tags,
         )
-        .await?
-        .ok_or_else(|| error::Service::DisabledService(ServiceKind::Algolia))?;
+        .await?;
 
     let images: Vec<_> = db::image::get(db.as_ref(), &ids)
         .err_into::<error::Service>()
@@ -165,6 +247,7 @@ async fn search(
         images,
         pages,
         total_image_count: total_hits,
+        facets,
     }))
 }
 
@@ -254,30 +337,59 @@ fn check_conflict_delete(err: sqlx::Error) -> error::Delete {
 #[api_v2_operation]
 async fn delete(
     db: Data<PgPool>,
-    algolia: ServiceData<crate::algolia::Client>,
+    search: ServiceData<crate::search::Client>,
     _claims: TokenUserWithScope<ScopeManageImage>,
     req: Path<ImageId>,
-    s3: ServiceData<s3::Client>,
+    query: Query<<endpoints::image::Delete as ApiEndpoint>::Req>,
+    storage: ServiceData<dyn MediaStorage>,
 ) -> Result<NoContent, error::Delete> {
     let image = req.into_inner();
+
+    if !query.force {
+        let jigs = db::module_media_ref::list_jigs(
+            &db,
+            MediaRefKind::Image,
+            MediaLibrary::Global,
+            image.0,
+        )
+        .await?;
+
+        if !jigs.is_empty() {
+            return Err(error::Delete::InUse(MediaInUse { jigs }));
+        }
+    }
+
     db::image::delete(&db, image)
         .await
         .map_err(check_conflict_delete)?;
 
-    // todo: 501 when algolia is disabled.
-
-    let delete = |kind| s3.delete_media(MediaLibrary::Global, FileKind::ImagePng(kind), image.0);
-    let ((), (), (), ()) = futures::future::join4(
-        delete(PngImageFile::Original),
-        delete(PngImageFile::Resized),
-        delete(PngImageFile::Thumbnail),
-        algolia.delete_image(image),
+    let ((), ()) = futures::future::join(
+        storage.delete_image(MediaLibrary::Global, image.0),
+        search.delete_image(image),
     )
     .await;
 
     Ok(NoContent)
 }
 
+/// List the modules that use an image from the global image library.
+#[api_v2_operation]
+async fn usage(
+    db: Data<PgPool>,
+    _claims: TokenUserWithScope<ScopeManageImage>,
+    req: Path<ImageId>,
+) -> Result<Json<<endpoints::image::Usage as ApiEndpoint>::Res>, error::Server> {
+    let usages = db::module_media_ref::list_usages(
+        &db,
+        MediaRefKind::Image,
+        MediaLibrary::Global,
+        req.into_inner().0,
+    )
+    .await?;
+
+    Ok(Json(MediaUsageResponse { usages }))
+}
+
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
     use endpoints::image;
     cfg.route(
@@ -294,6 +406,14 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         image::Upload::METHOD.route().to(upload),
     )
     .route(image::Get::PATH, image::Get::METHOD.route().to(get_one))
+    .route(
+        image::Related::PATH,
+        image::Related::METHOD.route().to(related),
+    )
+    .route(
+        image::Similar::PATH,
+        image::Similar::METHOD.route().to(similar),
+    )
     .route(
         image::Search::PATH,
         image::Search::METHOD.route().to(search),
@@ -310,6 +430,7 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         image::Delete::PATH,
         image::Delete::METHOD.route().to(delete),
     )
+    .route(image::Usage::PATH, image::Usage::METHOD.route().to(usage))
     .route(
         image::user::Create::PATH,
         image::user::Create::METHOD.route().to(self::user::create),
@@ -322,14 +443,30 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         image::user::Delete::PATH,
         image::user::Delete::METHOD.route().to(self::user::delete),
     )
+    .route(
+        image::user::Search::PATH,
+        image::user::Search::METHOD.route().to(self::user::search),
+    )
     .route(
         image::user::Get::PATH,
         image::user::Get::METHOD.route().to(self::user::get),
     )
+    .route(
+        image::user::Similar::PATH,
+        image::user::Similar::METHOD.route().to(self::user::similar),
+    )
+    .route(
+        image::user::Update::PATH,
+        image::user::Update::METHOD.route().to(self::user::update),
+    )
     .route(
         image::user::List::PATH,
         image::user::List::METHOD.route().to(self::user::list),
     )
+    .route(
+        image::user::Usage::PATH,
+        image::user::Usage::METHOD.route().to(self::user::usage),
+    )
     .route(
         image::tag::Create::PATH,
         image::tag::Create::METHOD.route().to(self::tag::create),
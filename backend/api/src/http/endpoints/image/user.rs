@@ -1,32 +1,42 @@
-use crate::service::storage;
-use crate::{db, error, extractor::TokenUser, s3, service::ServiceData};
+use crate::{
+    db, error, extractor::TokenUser, module_media::MediaRefKind, service::ServiceData,
+    storage::MediaStorage,
+};
 use futures::TryStreamExt;
 use paperclip::actix::{
     api_v2_operation,
-    web::{Data, Json, Path},
+    web::{Data, Json, Path, Query},
     CreatedJson, NoContent,
 };
 use shared::{
     api::{endpoints, ApiEndpoint},
     domain::{
         image::{
-            user::{UserImage, UserImageListResponse, UserImageResponse, UserImageUploadResponse},
+            user::{
+                UserImage, UserImageListResponse, UserImageResponse, UserImageSearchResponse,
+                UserImageSimilarResponse, UserImageUploadResponse,
+            },
             ImageId,
         },
+        media::MediaUsageResponse,
         CreateResponse,
     },
+    error::MediaInUse,
     media::MediaLibrary,
     media::{FileKind, PngImageFile},
+    search::normalize,
 };
 use sqlx::PgPool;
 
+const SEARCH_PAGE_SIZE: u32 = 20;
+
 /// Create a image in the user's image library.
 #[api_v2_operation]
 pub(super) async fn create(
     db: Data<PgPool>,
-    _claims: TokenUser,
+    claims: TokenUser,
 ) -> Result<CreatedJson<<endpoints::image::user::Create as ApiEndpoint>::Res>, error::Server> {
-    let id = db::image::user::create(db.as_ref()).await?;
+    let id = db::image::user::create(db.as_ref(), claims.0.user_id).await?;
     Ok(CreatedJson(CreateResponse { id }))
 }
 
@@ -34,7 +44,7 @@ pub(super) async fn create(
 #[api_v2_operation]
 pub(super) async fn upload(
     db: Data<PgPool>,
-    gcs: ServiceData<storage::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     _claims: TokenUser,
     Path(id): Path<ImageId>,
     req: Json<<endpoints::image::user::Upload as ApiEndpoint>::Req>,
@@ -51,13 +61,13 @@ pub(super) async fn upload(
 
     let upload_content_length = req.into_inner().file_size;
 
-    if let Some(file_limit) = gcs.file_size_limit(&FileKind::ImagePng(PngImageFile::Original)) {
+    if let Some(file_limit) = storage.file_size_limit(&FileKind::ImagePng(PngImageFile::Original)) {
         if file_limit < upload_content_length {
             return Err(error::Upload::FileTooLarge);
         }
     }
 
-    let resp = gcs
+    let resp = storage
         .get_url_for_resumable_upload_for_processing(
             upload_content_length,
             MediaLibrary::User,
@@ -84,20 +94,26 @@ pub(super) async fn delete(
     db: Data<PgPool>,
     _claims: TokenUser,
     req: Path<ImageId>,
-    s3: ServiceData<s3::Client>,
+    query: Query<<endpoints::image::user::Delete as ApiEndpoint>::Req>,
+    storage: ServiceData<dyn MediaStorage>,
 ) -> Result<NoContent, error::Delete> {
     let image = req.into_inner();
+
+    if !query.force {
+        let jigs =
+            db::module_media_ref::list_jigs(&db, MediaRefKind::Image, MediaLibrary::User, image.0)
+                .await?;
+
+        if !jigs.is_empty() {
+            return Err(error::Delete::InUse(MediaInUse { jigs }));
+        }
+    }
+
     db::image::user::delete(&db, image)
         .await
         .map_err(super::check_conflict_delete)?;
 
-    let delete = |kind| s3.delete_media(MediaLibrary::User, FileKind::ImagePng(kind), image.0);
-    let ((), (), ()) = futures::future::join3(
-        delete(PngImageFile::Original),
-        delete(PngImageFile::Resized),
-        delete(PngImageFile::Thumbnail),
-    )
-    .await;
+    storage.delete_image(MediaLibrary::User, image.0).await;
 
     Ok(NoContent)
 }
@@ -116,6 +132,41 @@ pub(super) async fn get(
     Ok(Json(UserImageResponse { metadata }))
 }
 
+/// Get images from the user's image library that look like an image.
+#[api_v2_operation]
+pub(super) async fn similar(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Path<ImageId>,
+    query: Option<Query<<endpoints::image::user::Similar as ApiEndpoint>::Req>>,
+) -> Result<Json<<endpoints::image::user::Similar as ApiEndpoint>::Res>, error::NotFound> {
+    let id = req.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    db::image::user::get(&db, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let limit = query
+        .limit
+        .unwrap_or(config::RELATED_DEFAULT_LIMIT)
+        .min(config::RELATED_MAX_LIMIT);
+
+    let images = db::image::user::similar(
+        &db,
+        claims.0.user_id,
+        id,
+        config::IMAGE_SIMILAR_MAX_DISTANCE,
+        limit,
+    )
+    .await?
+    .into_iter()
+    .map(|metadata| UserImageResponse { metadata })
+    .collect();
+
+    Ok(Json(UserImageSimilarResponse { images }))
+}
+
 /// List images from the user's image library.
 #[api_v2_operation]
 pub(super) async fn list(
@@ -130,3 +181,80 @@ pub(super) async fn list(
 
     Ok(Json(UserImageListResponse { images }))
 }
+
+/// Update an image's name and tags in the user's image library.
+#[api_v2_operation]
+pub(super) async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Path<ImageId>,
+    body: Json<<endpoints::image::user::Update as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::NotFound> {
+    let body = body.into_inner();
+
+    let exists = db::image::user::update(
+        &db,
+        claims.0.user_id,
+        req.into_inner(),
+        body.name.as_deref(),
+        body.tags.as_deref(),
+    )
+    .await?;
+
+    if !exists {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(NoContent)
+}
+
+/// Search the images the user uploaded to their image library.
+#[api_v2_operation]
+pub(super) async fn search(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    query: Option<Query<<endpoints::image::user::Search as ApiEndpoint>::Req>>,
+) -> Result<Json<<endpoints::image::user::Search as ApiEndpoint>::Res>, error::Server> {
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    let (images, total_image_count) = db::image::user::search(
+        &db,
+        claims.0.user_id,
+        &normalize(&query.q),
+        query.page.unwrap_or(0),
+        SEARCH_PAGE_SIZE,
+    )
+    .await?;
+
+    let images = images
+        .into_iter()
+        .map(|metadata| UserImageResponse { metadata })
+        .collect();
+
+    let page_size = u64::from(SEARCH_PAGE_SIZE);
+    let pages = ((total_image_count + page_size - 1) / page_size) as u32;
+
+    Ok(Json(UserImageSearchResponse {
+        images,
+        pages,
+        total_image_count,
+    }))
+}
+
+/// List the modules that use an image from the user's image library.
+#[api_v2_operation]
+pub(super) async fn usage(
+    db: Data<PgPool>,
+    _claims: TokenUser,
+    req: Path<ImageId>,
+) -> Result<Json<<endpoints::image::user::Usage as ApiEndpoint>::Res>, error::Server> {
+    let usages = db::module_media_ref::list_usages(
+        &db,
+        MediaRefKind::Image,
+        MediaLibrary::User,
+        req.into_inner().0,
+    )
+    .await?;
+
+    Ok(Json(MediaUsageResponse { usages }))
+}
@@ -2,15 +2,15 @@ use actix_web::web::Query;
 use chrono::{DateTime, Utc};
 use paperclip::actix::{
     api_v2_operation,
-    web::{self, Data, Json, ServiceConfig},
+    web::{self, Data, HttpRequest, Json, PayloadConfig, ServiceConfig},
     CreatedJson, NoContent,
 };
 use shared::{
     api::{endpoints::jig, ApiEndpoint},
     domain::{
         jig::{
-            Jig, JigBrowseResponse, JigCountResponse, JigCreateRequest, JigId, JigResponse,
-            JigSearchResponse, UserOrMe,
+            Jig, JigBrowseResponse, JigCountResponse, JigCreateRequest, JigId, JigLikedResponse,
+            JigRelatedResponse, JigResponse, JigSearchResponse, UserOrMe,
         },
         CreateResponse,
     },
@@ -19,13 +19,20 @@ use sqlx::PgPool;
 
 use crate::db::jig::CreateJigError;
 use crate::{
-    db,
-    error::{self, ServiceKind},
-    extractor::TokenUser,
+    db::{self, RevisionUpdate},
+    domain::{if_match_revisions, Conditional},
+    error,
+    extractor::{PlayerClaims, TokenPlayer, TokenUser},
+    search::SearchResults,
     service::ServiceData,
 };
 use shared::domain::jig::JigDraftResponse;
 
+mod archive;
+mod collaborator;
+mod player;
+mod version;
+
 /// Create a jig.
 #[api_v2_operation]
 async fn create(
@@ -96,34 +103,41 @@ async fn delete(
     db: Data<PgPool>,
     claims: TokenUser,
     path: web::Path<JigId>,
-    algolia: ServiceData<crate::algolia::Client>,
+    search: ServiceData<crate::search::Client>,
 ) -> Result<NoContent, error::Delete> {
     let id = path.into_inner();
 
-    db::jig::authz(&*db, claims.0.user_id, Some(id)).await?;
+    db::jig::authz_owner(&*db, claims.0.user_id, id).await?;
 
     db::jig::delete(&*db, id).await?;
 
-    algolia.delete_jig(id).await;
+    search.delete_jig(id).await;
 
     Ok(NoContent)
 }
 
 /// Update a jig.
+/// Note: this request can be conditional on `If-Match`
 #[api_v2_operation]
 async fn update(
     db: Data<PgPool>,
     claims: TokenUser,
     req: Option<Json<<jig::Update as ApiEndpoint>::Req>>,
     path: web::Path<JigId>,
-) -> Result<NoContent, error::UpdateWithMetadata> {
+    http_req: HttpRequest,
+) -> Result<Conditional<JigResponse>, error::UpdateWithMetadata> {
     let id = path.into_inner();
 
     db::jig::authz(&*db, claims.0.user_id, Some(id)).await?;
 
     let req = req.map_or_else(Default::default, Json::into_inner);
 
-    db::jig::update(
+    // only the author can hand the jig over to someone else
+    if req.author_id.is_some() {
+        db::jig::authz_owner(&*db, claims.0.user_id, id).await?;
+    }
+
+    let res = db::jig::update(
         &*db,
         id,
         req.display_name.as_deref(),
@@ -141,26 +155,80 @@ async fn update(
         req.theme,
         req.audio_background,
         req.audio_effects,
+        if_match_revisions(&http_req).as_deref(),
     )
     .await?;
 
-    Ok(NoContent)
+    match res {
+        RevisionUpdate::Updated(revision) => Ok(Conditional::Updated(revision)),
+        RevisionUpdate::Conflict => {
+            let jig = db::jig::get(&db, id)
+                .await?
+                .ok_or(error::UpdateWithMetadata::ResourceNotFound)?;
+
+            Ok(Conditional::PreconditionFailed {
+                revision: jig.revision,
+                current: JigResponse { jig },
+            })
+        }
+    }
 }
 
 /// Get a jig.
 #[api_v2_operation]
 async fn get(
     db: Data<PgPool>,
-    _claims: TokenUser,
+    claims: TokenPlayer,
     path: web::Path<JigId>,
 ) -> Result<Json<<jig::Get as ApiEndpoint>::Res>, error::NotFound> {
-    let jig = db::jig::get(&db, path.into_inner())
+    let id = path.into_inner();
+
+    if let PlayerClaims::AssignmentStudent(claims) = &claims.0 {
+        db::class::assignment::authz_student(&db, claims.assignment_id, id).await?;
+    }
+
+    let jig = db::jig::get(&db, id)
         .await?
         .ok_or(error::NotFound::ResourceNotFound)?;
 
     Ok(Json(JigResponse { jig }))
 }
 
+/// Get published jigs related to a jig.
+#[api_v2_operation]
+async fn related(
+    db: Data<PgPool>,
+    claims: TokenPlayer,
+    path: web::Path<JigId>,
+    query: Option<Query<<jig::Related as ApiEndpoint>::Req>>,
+) -> Result<Json<<jig::Related as ApiEndpoint>::Res>, error::NotFound> {
+    let id = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    if let PlayerClaims::AssignmentStudent(claims) = &claims.0 {
+        db::class::assignment::authz_student(&db, claims.assignment_id, id).await?;
+    }
+
+    if !db::jig::exists(&db, id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(config::RELATED_DEFAULT_LIMIT)
+        .min(config::RELATED_MAX_LIMIT);
+
+    let ids = db::jig::related(&db, id, limit).await?;
+
+    let jigs = db::jig::get_by_ids(&db, &ids)
+        .await?
+        .into_iter()
+        .map(|jig| JigResponse { jig })
+        .collect();
+
+    Ok(Json(JigRelatedResponse { jigs }))
+}
+
 #[api_v2_operation]
 async fn browse(
     db: Data<PgPool>,
@@ -176,15 +244,27 @@ async fn browse(
 
     db::jig::authz_list(&*db, claims.0.user_id, author_id).await?;
 
+    let module_kinds: Vec<_> = query.module_kinds.iter().map(|&kind| kind as i16).collect();
+
     let jigs = db::jig::list(
         db.as_ref(),
         query.is_published,
         author_id,
+        query.language.as_deref(),
+        &module_kinds,
         query.page.unwrap_or(0) as i32,
+        query.sort.unwrap_or_default(),
     )
     .await?;
 
-    let total_count = db::jig::filtered_count(db.as_ref(), query.is_published, author_id).await?;
+    let total_count = db::jig::filtered_count(
+        db.as_ref(),
+        query.is_published,
+        author_id,
+        query.language.as_deref(),
+        &module_kinds,
+    )
+    .await?;
 
     let pages = (total_count / 20 + (total_count % 20 != 0) as u64) as u32;
 
@@ -199,24 +279,44 @@ async fn browse(
 #[api_v2_operation]
 async fn search(
     db: Data<PgPool>,
-    algolia: ServiceData<crate::algolia::Client>,
+    search: ServiceData<crate::search::Client>,
+    claims: Option<TokenUser>,
     query: Option<Query<<jig::Search as ApiEndpoint>::Req>>,
 ) -> Result<Json<<jig::Search as ApiEndpoint>::Res>, error::Service> {
     let query = query.map_or_else(Default::default, Query::into_inner);
 
-    let (ids, pages, total_hits) = algolia
+    // only record the search once, rather than for every page of it.
+    // failing to record it shouldn't fail the search.
+    if let Some(claims) = claims {
+        if !query.q.trim().is_empty() && query.page.unwrap_or(0) == 0 {
+            if let Err(e) = db::search::record_recent(&db, claims.0.user_id, query.q.trim()).await {
+                log::warn!("failed to record recent search: {:?}", e);
+            }
+        }
+    }
+
+    let SearchResults {
+        ids,
+        pages,
+        total_hits,
+        facets,
+    } = search
         .search_jig(
             &query.q,
+            query.transliterate,
             query.page,
             query.is_published,
             &query.age_ranges,
             &query.affiliations,
             &query.categories,
             &query.goals,
+            query.language.as_deref(),
+            &query.module_kinds,
             query.author,
+            query.sort,
+            None,
         )
-        .await?
-        .ok_or_else(|| error::Service::DisabledService(ServiceKind::Algolia))?;
+        .await?;
 
     let jigs: Vec<_> = db::jig::get_by_ids(db.as_ref(), &ids)
         .await?
@@ -228,6 +328,7 @@ async fn search(
         jigs,
         pages,
         total_image_count: total_hits,
+        facets,
     }))
 }
 
@@ -256,11 +357,13 @@ async fn get_draft(
 ) -> Result<Json<<jig::draft::Get as ApiEndpoint>::Res>, error::JigCloneDraft> {
     let live_id = live_id.into_inner();
 
-    db::jig::authz(&*db, claims.0.user_id, Some(live_id)).await?;
+    db::jig::authz_read(&*db, claims.0.user_id, live_id).await?;
 
     let id = db::jig::get_draft(db.as_ref(), live_id).await?;
 
-    Ok(Json(JigDraftResponse { id }))
+    let publish_at = db::jig::get_publish_schedule(db.as_ref(), live_id).await?;
+
+    Ok(Json(JigDraftResponse { id, publish_at }))
 }
 
 /// Publish the draft version of a jig.
@@ -274,27 +377,160 @@ async fn publish_draft(
 
     db::jig::authz(&*db, claims.0.user_id, Some(live_id)).await?;
 
-    db::jig::publish_draft_to_live(db.as_ref(), live_id).await?;
+    db::jig::publish_draft_to_live(db.as_ref(), live_id, claims.0.user_id).await?;
 
     Ok(Json(()))
 }
 
+/// Schedule the draft version of a jig to be published.
+#[api_v2_operation]
+async fn schedule_draft(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    live_id: web::Path<JigId>,
+    req: Json<<jig::draft::Schedule as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::JigCloneDraft> {
+    let live_id = live_id.into_inner();
+
+    db::jig::authz(&*db, claims.0.user_id, Some(live_id)).await?;
+
+    // make sure there's a draft to publish.
+    db::jig::get_draft(db.as_ref(), live_id).await?;
+
+    db::jig::schedule_publish(
+        db.as_ref(),
+        live_id,
+        req.into_inner().publish_at,
+        claims.0.user_id,
+    )
+    .await?;
+
+    Ok(NoContent)
+}
+
 #[api_v2_operation]
 async fn count(db: Data<PgPool>) -> Result<Json<<jig::Count as ApiEndpoint>::Res>, error::Server> {
-    let total_count: u64 = db::jig::filtered_count(&*db, Some(true), None).await?;
+    let total_count: u64 = db::jig::filtered_count(&*db, Some(true), None, None, &[]).await?;
 
     Ok(Json(JigCountResponse { total_count }))
 }
 
+/// Like a jig.
+#[api_v2_operation]
+async fn like(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigId>,
+) -> Result<NoContent, error::NotFound> {
+    let id = db::jig::get_live_id(&db, path.into_inner()).await?;
+
+    if !db::jig::exists(&db, id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    db::jig::like(&db, id, claims.0.user_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Un-like a jig.
+#[api_v2_operation]
+async fn unlike(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigId>,
+) -> Result<NoContent, error::NotFound> {
+    let id = db::jig::get_live_id(&db, path.into_inner()).await?;
+
+    db::jig::unlike(&db, id, claims.0.user_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Whether the current user likes a jig.
+#[api_v2_operation]
+async fn liked(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigId>,
+) -> Result<Json<<jig::Liked as ApiEndpoint>::Res>, error::Server> {
+    let id = db::jig::get_live_id(&db, path.into_inner()).await?;
+
+    let is_liked = db::jig::is_liked(&db, id, claims.0.user_id).await?;
+
+    Ok(Json(JigLikedResponse { is_liked }))
+}
+
+/// Count a play of a jig.
+#[api_v2_operation]
+async fn play(
+    db: Data<PgPool>,
+    claims: TokenPlayer,
+    path: web::Path<JigId>,
+) -> Result<NoContent, error::NotFound> {
+    let id = path.into_inner();
+
+    let player_id = match &claims.0 {
+        PlayerClaims::User(claims) => claims.user_id,
+        PlayerClaims::AssignmentStudent(claims) => {
+            db::class::assignment::authz_student(&db, claims.assignment_id, id).await?;
+            claims.student_id.0
+        }
+    };
+
+    let id = db::jig::get_live_id(&db, id).await?;
+
+    if !db::jig::exists(&db, id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    db::jig::record_play(&db, id, player_id).await?;
+
+    Ok(NoContent)
+}
+
+/// Count a view of a jig.
+#[api_v2_operation]
+async fn view(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: web::Path<JigId>,
+) -> Result<NoContent, error::NotFound> {
+    let id = db::jig::get_live_id(&db, path.into_inner()).await?;
+
+    if !db::jig::exists(&db, id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    db::jig::record_view(&db, id, claims.0.user_id).await?;
+
+    Ok(NoContent)
+}
+
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
     cfg.route(jig::Browse::PATH, jig::Browse::METHOD.route().to(browse))
         .route(jig::Count::PATH, jig::Count::METHOD.route().to(count))
         .route(jig::Get::PATH, jig::Get::METHOD.route().to(get))
+        .route(jig::Related::PATH, jig::Related::METHOD.route().to(related))
         .route(jig::Clone::PATH, jig::Clone::METHOD.route().to(clone))
         .route(jig::Create::PATH, jig::Create::METHOD.route().to(create))
         .route(jig::Search::PATH, jig::Search::METHOD.route().to(search))
         .route(jig::Update::PATH, jig::Update::METHOD.route().to(update))
         .route(jig::Delete::PATH, jig::Delete::METHOD.route().to(delete))
+        .route(jig::Like::PATH, jig::Like::METHOD.route().to(like))
+        .route(jig::Unlike::PATH, jig::Unlike::METHOD.route().to(unlike))
+        .route(jig::Liked::PATH, jig::Liked::METHOD.route().to(liked))
+        .route(jig::Play::PATH, jig::Play::METHOD.route().to(play))
+        .route(jig::View::PATH, jig::View::METHOD.route().to(view))
+        .route(
+            jig::Export::PATH,
+            jig::Export::METHOD.route().to(self::archive::export),
+        )
+        .service(
+            web::resource(jig::Import::PATH)
+                .app_data(PayloadConfig::default().limit(config::JIG_ARCHIVE_BODY_SIZE_LIMIT))
+                .route(jig::Import::METHOD.route().to(self::archive::import)),
+        )
         .route(
             jig::draft::Create::PATH,
             jig::draft::Create::METHOD.route().to(create_draft),
@@ -306,5 +542,69 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         .route(
             jig::draft::Publish::PATH,
             jig::draft::Publish::METHOD.route().to(publish_draft),
+        )
+        .route(
+            jig::draft::Schedule::PATH,
+            jig::draft::Schedule::METHOD.route().to(schedule_draft),
+        )
+        .route(
+            jig::player::CreateSession::PATH,
+            jig::player::CreateSession::METHOD
+                .route()
+                .to(self::player::create_session),
+        )
+        .route(
+            jig::player::ListSessions::PATH,
+            jig::player::ListSessions::METHOD
+                .route()
+                .to(self::player::list_sessions),
+        )
+        .route(
+            jig::player::CreateModuleResult::PATH,
+            jig::player::CreateModuleResult::METHOD
+                .route()
+                .to(self::player::create_module_result),
+        )
+        .route(
+            jig::collaborator::List::PATH,
+            jig::collaborator::List::METHOD
+                .route()
+                .to(self::collaborator::list),
+        )
+        .route(
+            jig::collaborator::Invite::PATH,
+            jig::collaborator::Invite::METHOD
+                .route()
+                .to(self::collaborator::invite),
+        )
+        .route(
+            jig::collaborator::Accept::PATH,
+            jig::collaborator::Accept::METHOD
+                .route()
+                .to(self::collaborator::accept),
+        )
+        .route(
+            jig::collaborator::Remove::PATH,
+            jig::collaborator::Remove::METHOD
+                .route()
+                .to(self::collaborator::remove),
+        )
+        .route(
+            jig::version::List::PATH,
+            jig::version::List::METHOD.route().to(self::version::list),
+        )
+        .route(
+            jig::version::Get::PATH,
+            jig::version::Get::METHOD.route().to(self::version::get),
+        )
+        .route(
+            jig::version::Diff::PATH,
+            jig::version::Diff::METHOD.route().to(self::version::diff),
+        )
+        .route(
+            jig::version::Restore::PATH,
+            jig::version::Restore::METHOD
+                .route()
+                .to(self::version::restore),
         );
 }
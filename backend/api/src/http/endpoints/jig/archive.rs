@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use actix_web::{error::BlockingError, http::header, HttpResponse};
+use chrono::Utc;
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Bytes, Data, Path},
+    CreatedJson,
+};
+use shared::{
+    api::{endpoints::jig, ApiEndpoint},
+    domain::{
+        image::ImageKind,
+        jig::{
+            additional_resource::AdditionalResource,
+            archive::{JigArchiveManifest, JIG_ARCHIVE_VERSION},
+            module::ModuleIdOrIndex,
+            JigId,
+        },
+        CreateResponse,
+    },
+    media::{media_key, FileKind, MediaLibrary},
+};
+use sqlx::PgPool;
+
+use crate::{
+    audio_ops, db, error,
+    extractor::TokenUser,
+    image_ops,
+    jig_archive::JigArchive,
+    module_media::{self, MediaRef, MediaRefKind},
+    service::ServiceData,
+    storage::MediaStorage,
+};
+
+/// Export a jig as a zip archive.
+#[api_v2_operation]
+pub(super) async fn export(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    storage: ServiceData<dyn MediaStorage>,
+    path: Path<JigId>,
+) -> Result<HttpResponse, error::NotFound> {
+    let id = path.into_inner();
+
+    db::jig::authz_read(&*db, claims.0.user_id, id).await?;
+
+    let jig = db::jig::get(&*db, id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let mut modules = Vec::with_capacity(jig.modules.len());
+    for module in &jig.modules {
+        // skip modules that were deleted since the jig was fetched.
+        if let Some(module) = db::module::get(&*db, id, ModuleIdOrIndex::Id(module.id)).await? {
+            modules.push(module);
+        }
+    }
+
+    let mut additional_resources = Vec::with_capacity(jig.additional_resources.len());
+    for &resource_id in &jig.additional_resources {
+        if let Some(url) = db::additional_resource::get(&*db, id, resource_id).await? {
+            additional_resources.push(AdditionalResource {
+                id: resource_id,
+                url,
+            });
+        }
+    }
+
+    let mut media_refs = Vec::new();
+    for module in &modules {
+        for media in module_media::media_refs(&module.body) {
+            if !media_refs.contains(&media) {
+                media_refs.push(media);
+            }
+        }
+    }
+
+    let mut media = HashMap::new();
+    for media_ref in media_refs {
+        for &file in media_ref.kind.files() {
+            let data = storage
+                .download_media_file(media_ref.library, media_ref.id, file)
+                .await?;
+
+            // missing media is left out, and the reference kept as is on import.
+            if let Some(data) = data {
+                media.insert(media_key(media_ref.library, media_ref.id, file), data);
+            }
+        }
+    }
+
+    let archive = JigArchive {
+        manifest: JigArchiveManifest {
+            version: JIG_ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            jig,
+            additional_resources,
+        },
+        modules,
+        media,
+    };
+
+    let data = actix_web::web::block(move || archive.write()).await?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/zip")
+        .header(
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"jig-{}.zip\"", id.0),
+        )
+        .body(data))
+}
+
+/// Import a jig from a zip archive.
+#[api_v2_operation]
+pub(super) async fn import(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    storage: ServiceData<dyn MediaStorage>,
+    bytes: Bytes,
+) -> Result<CreatedJson<<jig::Import as ApiEndpoint>::Res>, error::JigImport> {
+    db::jig::authz(&*db, claims.0.user_id, None).await?;
+
+    let size_limit = config::JIG_ARCHIVE_BODY_SIZE_LIMIT as u64 * 4;
+
+    let archive = actix_web::web::block(move || JigArchive::read(&bytes, size_limit))
+        .await
+        .map_err(|e| match e {
+            BlockingError::Error(_) => error::JigImport::InvalidArchive,
+            BlockingError::Canceled => anyhow::anyhow!("Thread pool is gone").into(),
+        })?;
+
+    let mut remapped = HashMap::new();
+
+    for module in &archive.modules {
+        for media in module_media::media_refs(&module.body) {
+            if remapped.contains_key(&media) {
+                continue;
+            }
+
+            // an image's original is exported as whichever file it's stored as, and audio as its MP3.
+            let file = media.kind.files().iter().find_map(|&file| {
+                archive
+                    .media
+                    .get(&media_key(media.library, media.id, file))
+                    .cloned()
+            });
+
+            // media that wasn't exported keeps its original reference.
+            let file = match file {
+                Some(file) => file,
+                None => continue,
+            };
+
+            let id = match media.kind {
+                MediaRefKind::Image => {
+                    let original = file;
+
+                    let id = db::image::user::create(&*db, claims.0.user_id).await?;
+
+                    // archives only have the original, so every other file is regenerated from it.
+                    let ((original, resized, thumbnail), hash) =
+                        actix_web::web::block(move || -> Result<_, error::Upload> {
+                            let image = image_ops::load_image(&original, ImageKind::Sticker)?;
+                            let hash = image_ops::perceptual_hash(&image);
+                            Ok((
+                                image_ops::generate_images(&original, &image, ImageKind::Sticker)?,
+                                hash,
+                            ))
+                        })
+                        .await
+                        .map_err(|e| match e {
+                            BlockingError::Error(error::Upload::InternalServerError(e)) => e.into(),
+                            BlockingError::Error(_) => error::JigImport::InvalidArchive,
+                            BlockingError::Canceled => {
+                                anyhow::anyhow!("Thread pool is gone").into()
+                            }
+                        })?;
+
+                    storage
+                        .upload_images(MediaLibrary::User, id.0, original, resized, thumbnail)
+                        .await?;
+
+                    db::image::user::set_processed(&*db, id, hash).await?;
+
+                    id.0
+                }
+
+                MediaRefKind::Audio => {
+                    // processed like any other upload, so the audio is measured (and can't be anything but an MP3).
+                    let audio = audio_ops::process(&file).await.map_err(|e| match e {
+                        error::Upload::InternalServerError(e) => e.into(),
+                        _ => error::JigImport::InvalidArchive,
+                    })?;
+
+                    let id = db::audio::user::create(&*db, claims.0.user_id).await?;
+
+                    storage
+                        .upload_media(audio.mp3, MediaLibrary::User, id.0, FileKind::AudioMp3)
+                        .await?;
+
+                    db::audio::user::set_uploaded(
+                        &mut *db.acquire().await?,
+                        id,
+                        audio.duration_ms,
+                        &audio.waveform,
+                    )
+                    .await?;
+
+                    id.0
+                }
+            };
+
+            remapped.insert(
+                media,
+                MediaRef {
+                    kind: media.kind,
+                    library: MediaLibrary::User,
+                    id,
+                },
+            );
+        }
+    }
+
+    let modules: Vec<_> = archive
+        .modules
+        .into_iter()
+        .map(|mut module| {
+            module_media::remap_media_refs(&mut module.body, &remapped);
+            module
+        })
+        .collect();
+
+    let additional_resources: Vec<_> = archive
+        .manifest
+        .additional_resources
+        .into_iter()
+        .map(|it| it.url)
+        .collect();
+
+    let id = db::jig::import(
+        &*db,
+        claims.0.user_id,
+        &archive.manifest.jig,
+        &additional_resources,
+        &modules,
+    )
+    .await?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
@@ -0,0 +1,85 @@
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Data, Json, Path},
+    NoContent,
+};
+use shared::{
+    api::{endpoints::jig::collaborator, ApiEndpoint},
+    domain::jig::{collaborator::JigCollaboratorListResponse, JigId},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// List the collaborators of a jig.
+#[api_v2_operation]
+pub(super) async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+) -> Result<Json<<collaborator::List as ApiEndpoint>::Res>, error::NotFound> {
+    let jig_id = db::jig::get_live_id(db.as_ref(), path.into_inner()).await?;
+
+    db::jig::authz_read(db.as_ref(), claims.0.user_id, jig_id).await?;
+
+    let collaborators = db::jig_collaborator::list(db.as_ref(), jig_id).await?;
+
+    Ok(Json(JigCollaboratorListResponse { collaborators }))
+}
+
+/// Invite a user to collaborate on a jig.
+#[api_v2_operation]
+pub(super) async fn invite(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+    req: Json<<collaborator::Invite as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::NotFound> {
+    let jig_id = db::jig::get_live_id(db.as_ref(), path.into_inner()).await?;
+    let req = req.into_inner();
+
+    db::jig::authz_owner(db.as_ref(), claims.0.user_id, jig_id).await?;
+
+    db::jig_collaborator::invite(db.as_ref(), jig_id, &req.email, req.role, claims.0.user_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(NoContent)
+}
+
+/// Accept an invite to collaborate on a jig.
+#[api_v2_operation]
+pub(super) async fn accept(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+) -> Result<NoContent, error::NotFound> {
+    let jig_id = db::jig::get_live_id(db.as_ref(), path.into_inner()).await?;
+
+    if !db::jig_collaborator::accept(db.as_ref(), jig_id, claims.0.user_id).await? {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(NoContent)
+}
+
+/// Remove a collaborator from a jig.
+#[api_v2_operation]
+pub(super) async fn remove(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(JigId, Uuid)>,
+) -> Result<NoContent, error::NotFound> {
+    let (jig_id, user_id) = path.into_inner();
+    let jig_id = db::jig::get_live_id(db.as_ref(), jig_id).await?;
+
+    // collaborators can always leave (or decline an invite)
+    if user_id != claims.0.user_id {
+        db::jig::authz_owner(db.as_ref(), claims.0.user_id, jig_id).await?;
+    }
+
+    db::jig_collaborator::remove(db.as_ref(), jig_id, user_id).await?;
+
+    Ok(NoContent)
+}
@@ -0,0 +1,91 @@
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Data, Json, Path, Query},
+    CreatedJson, NoContent,
+};
+use shared::{
+    api::{endpoints::jig::player, ApiEndpoint},
+    domain::{
+        jig::{
+            player::{JigPlayerSessionId, JigPlayerSessionListResponse},
+            JigId,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// Start a play session for a jig.
+#[api_v2_operation]
+pub(super) async fn create_session(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+) -> Result<CreatedJson<<player::CreateSession as ApiEndpoint>::Res>, error::NotFound> {
+    let id = db::player_session::create(db.as_ref(), path.into_inner(), Some(claims.0.user_id))
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
+
+/// Record the result of a module in a play session.
+#[api_v2_operation]
+pub(super) async fn create_module_result(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(JigId, JigPlayerSessionId)>,
+    req: Json<<player::CreateModuleResult as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::JigPlayerResult> {
+    let (jig_id, session_id) = path.into_inner();
+    let req = req.into_inner();
+
+    let owner = db::player_session::get_owner(db.as_ref(), jig_id, session_id)
+        .await?
+        .ok_or(error::JigPlayerResult::ResourceNotFound)?;
+
+    if owner.map_or(false, |owner| owner != claims.0.user_id) {
+        return Err(error::JigPlayerResult::Forbidden);
+    }
+
+    db::player_session::create_module_result(
+        db.as_ref(),
+        session_id,
+        req.module_id,
+        req.score,
+        req.attempts,
+        req.time_spent,
+    )
+    .await?;
+
+    Ok(NoContent)
+}
+
+/// List the play sessions of a jig.
+#[api_v2_operation]
+pub(super) async fn list_sessions(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+    query: Option<Query<<player::ListSessions as ApiEndpoint>::Req>>,
+) -> Result<Json<<player::ListSessions as ApiEndpoint>::Res>, error::Auth> {
+    let jig_id = path.into_inner();
+    let query = query.map_or_else(Default::default, Query::into_inner);
+
+    db::jig::authz_read(&*db, claims.0.user_id, jig_id).await?;
+
+    let sessions =
+        db::player_session::list(db.as_ref(), jig_id, query.page.unwrap_or(0) as i32).await?;
+
+    let total_count = db::player_session::count(db.as_ref(), jig_id).await?;
+
+    let pages = (total_count / 20 + (total_count % 20 != 0) as u64) as u32;
+
+    Ok(Json(JigPlayerSessionListResponse {
+        sessions,
+        pages,
+        total_session_count: total_count,
+    }))
+}
@@ -0,0 +1,169 @@
+use actix_web::HttpResponse;
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Data, Json, Path},
+};
+use shared::{
+    api::{endpoints::jig::version, ApiEndpoint},
+    domain::{
+        jig::{
+            version::{
+                JigVersionDiffResponse, JigVersionId, JigVersionListResponse,
+                JigVersionModuleChange, JigVersionModuleDiff, JigVersionResponse,
+                JigVersionSnapshot,
+            },
+            JigId,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+use crate::{db, error, extractor::TokenUser};
+
+/// List the published versions of a jig.
+#[api_v2_operation]
+pub(super) async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<JigId>,
+) -> Result<Json<<version::List as ApiEndpoint>::Res>, error::NotFound> {
+    let jig_id = path.into_inner();
+
+    db::jig::authz_read(db.as_ref(), claims.0.user_id, jig_id).await?;
+
+    let versions = db::jig_version::list(db.as_ref(), jig_id).await?;
+
+    Ok(Json(JigVersionListResponse { versions }))
+}
+
+/// Get a published version of a jig.
+#[api_v2_operation]
+pub(super) async fn get(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(JigId, JigVersionId)>,
+) -> Result<Json<<version::Get as ApiEndpoint>::Res>, error::NotFound> {
+    let (jig_id, version_id) = path.into_inner();
+
+    db::jig::authz_read(db.as_ref(), claims.0.user_id, jig_id).await?;
+
+    let (version, snapshot) = db::jig_version::get(db.as_ref(), jig_id, version_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    Ok(Json(JigVersionResponse { version, snapshot }))
+}
+
+/// Compare two published versions of a jig.
+#[api_v2_operation]
+pub(super) async fn diff(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(JigId, JigVersionId, JigVersionId)>,
+) -> Result<Json<<version::Diff as ApiEndpoint>::Res>, error::NotFound> {
+    let (jig_id, from_id, to_id) = path.into_inner();
+
+    db::jig::authz_read(db.as_ref(), claims.0.user_id, jig_id).await?;
+
+    let (from, from_snapshot) = db::jig_version::get(db.as_ref(), jig_id, from_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let (to, to_snapshot) = db::jig_version::get(db.as_ref(), jig_id, to_id)
+        .await?
+        .ok_or(error::NotFound::ResourceNotFound)?;
+
+    let (changed_fields, modules) = diff_snapshots(&from_snapshot, &to_snapshot)?;
+
+    Ok(Json(JigVersionDiffResponse {
+        from,
+        to,
+        changed_fields,
+        modules,
+    }))
+}
+
+/// Restore a published version of a jig into its draft.
+#[api_v2_operation]
+pub(super) async fn restore(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    path: Path<(JigId, JigVersionId)>,
+) -> Result<HttpResponse, error::JigCloneDraft> {
+    let (jig_id, version_id) = path.into_inner();
+
+    db::jig::authz(db.as_ref(), claims.0.user_id, Some(jig_id)).await?;
+
+    if !db::jig_version::exists(db.as_ref(), jig_id, version_id).await? {
+        return Err(error::JigCloneDraft::ResourceNotFound);
+    }
+
+    let (draft_id, created) = match db::jig::get_draft(db.as_ref(), jig_id).await {
+        Err(error::JigCloneDraft::ResourceNotFound) => {
+            (db::jig::create_draft(db.as_ref(), jig_id).await?, true)
+        }
+        res => (res?, false),
+    };
+
+    db::jig_version::restore(db.as_ref(), version_id, draft_id).await?;
+
+    let res: <version::Restore as ApiEndpoint>::Res = CreateResponse { id: draft_id };
+
+    if created {
+        Ok(HttpResponse::Created().json(res))
+    } else {
+        Ok(HttpResponse::Ok().json(res))
+    }
+}
+
+/// Compares snapshots by their serialized form, since module bodies don't implement `PartialEq`.
+fn diff_snapshots(
+    from: &JigVersionSnapshot,
+    to: &JigVersionSnapshot,
+) -> anyhow::Result<(Vec<String>, Vec<JigVersionModuleDiff>)> {
+    let mut from_fields = match serde_json::to_value(from)? {
+        serde_json::Value::Object(fields) => fields,
+        _ => anyhow::bail!("snapshot didn't serialize to an object"),
+    };
+
+    let mut to_fields = match serde_json::to_value(to)? {
+        serde_json::Value::Object(fields) => fields,
+        _ => anyhow::bail!("snapshot didn't serialize to an object"),
+    };
+
+    from_fields.remove("modules");
+    to_fields.remove("modules");
+
+    let changed_fields = from_fields
+        .into_iter()
+        .filter(|(field, value)| to_fields.get(field) != Some(value))
+        .map(|(field, _)| field)
+        .collect();
+
+    let mut modules = Vec::new();
+
+    for index in 0..from.modules.len().max(to.modules.len()) {
+        let change = match (from.modules.get(index), to.modules.get(index)) {
+            (Some(_), None) => JigVersionModuleChange::Removed,
+            (None, Some(_)) => JigVersionModuleChange::Added,
+            (Some(from), Some(to)) => {
+                if from.is_complete == to.is_complete
+                    && serde_json::to_value(&from.body)? == serde_json::to_value(&to.body)?
+                {
+                    continue;
+                }
+
+                JigVersionModuleChange::Modified
+            }
+            (None, None) => unreachable!(),
+        };
+
+        modules.push(JigVersionModuleDiff {
+            index: index as u16,
+            change,
+        });
+    }
+
+    Ok((changed_fields, modules))
+}
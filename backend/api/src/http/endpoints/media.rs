@@ -1,26 +1,28 @@
 use std::sync::Arc;
 
 use crate::{
-    error,
+    db, error,
     extractor::{ScopeAdmin, TokenUser, TokenUserWithScope},
     image_ops::MediaKind,
-    s3,
+    module_media::MediaRefKind,
     service::ServiceData,
+    storage::MediaStorage,
 };
 use actix_web::web::Path;
 use paperclip::actix::{
     api_v2_operation,
-    web::{Bytes, Data, Json, ServiceConfig},
+    web::{Bytes, Data, Json, Query, ServiceConfig},
     CreatedJson, NoContent,
 };
 use sha2::Digest as _;
 use shared::{
     api::{endpoints, ApiEndpoint},
     domain::media::{UrlCreatedResponse, WebMediaMetadataResponse, WebMediaUrlCreateRequest},
-    media::{FileKind, PngImageFile},
+    media::FileKind,
 };
 use shared::{
     domain::{image::ImageKind, Base64},
+    error::MediaInUse,
     media::MediaLibrary,
 };
 use sqlx::PgPool;
@@ -39,7 +41,7 @@ const fn max(a: usize, b: usize) -> usize {
 pub async fn create(
     pool: Data<PgPool>,
     _claims: TokenUser,
-    s3: ServiceData<s3::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     request: Json<WebMediaUrlCreateRequest>,
 ) -> Result<CreatedJson<UrlCreatedResponse>, error::Server> {
     let url = request.into_inner().url;
@@ -165,23 +167,27 @@ for update
 
     match kind {
         MediaKind::GifAnimation => {
-            s3.upload_media(
-                Arc::try_unwrap(data).expect("This should be unique by now"),
-                MediaLibrary::Web,
-                id,
-                FileKind::AnimationGif,
-            )
-            .await?;
+            storage
+                .upload_media(
+                    Arc::try_unwrap(data).expect("This should be unique by now"),
+                    MediaLibrary::Web,
+                    id,
+                    FileKind::AnimationGif,
+                )
+                .await?;
         }
 
         MediaKind::PngStickerImage => {
             let (original, resized, thumbnail) = actix_web::web::block(move || {
-                let original = image::load_from_memory(&data)?;
-                crate::image_ops::generate_images(&original, ImageKind::Sticker)
+                let original = crate::image_ops::load_image(&data, ImageKind::Sticker)
+                    .map_err(|_| anyhow::anyhow!("failed to load image"))?;
+                crate::image_ops::generate_images(&data, &original, ImageKind::Sticker)
+                    .map_err(|_| anyhow::anyhow!("failed to process image"))
             })
             .await?;
 
-            s3.upload_png_images(MediaLibrary::Web, id, original, resized, thumbnail)
+            storage
+                .upload_images(MediaLibrary::Web, id, original, resized, thumbnail)
                 .await?;
         }
 
@@ -211,9 +217,21 @@ for update
 async fn delete_media(
     pool: Data<PgPool>,
     _auth: TokenUserWithScope<ScopeAdmin>,
-    s3: ServiceData<s3::Client>,
+    storage: ServiceData<dyn MediaStorage>,
     Path(id): Path<Uuid>,
-) -> Result<NoContent, error::Server> {
+    query: Query<<endpoints::media::DeleteId as ApiEndpoint>::Req>,
+) -> Result<NoContent, error::Delete> {
+    // modules only reference web media as images.
+    if !query.force {
+        let jigs =
+            db::module_media_ref::list_jigs(&pool, MediaRefKind::Image, MediaLibrary::Web, id)
+                .await?;
+
+        if !jigs.is_empty() {
+            return Err(error::Delete::InUse(MediaInUse { jigs }));
+        }
+    }
+
     let record = sqlx::query!(
         r#"delete from web_media_library where id = $1 returning kind as "kind: MediaKind""#,
         id
@@ -226,15 +244,10 @@ async fn delete_media(
         None => return Ok(NoContent),
     };
 
-    let delete = |file_kind| s3.delete_media(MediaLibrary::Web, file_kind, id);
+    let delete = |file_kind| storage.delete_media(MediaLibrary::Web, file_kind, id);
     match kind {
         MediaKind::PngStickerImage => {
-            futures::future::join3(
-                delete(FileKind::ImagePng(PngImageFile::Original)),
-                delete(FileKind::ImagePng(PngImageFile::Resized)),
-                delete(FileKind::ImagePng(PngImageFile::Thumbnail)),
-            )
-            .await;
+            storage.delete_image(MediaLibrary::Web, id).await;
         }
 
         MediaKind::GifAnimation => {
@@ -348,10 +361,10 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
     )
     .route(
         endpoints::media::DeleteId::PATH,
-        endpoints::media::DeleteId::METHOD.route().to(delete_url),
+        endpoints::media::DeleteId::METHOD.route().to(delete_media),
     )
     .route(
         endpoints::media::DeleteUrl::PATH,
-        endpoints::media::DeleteUrl::METHOD.route().to(delete_media),
+        endpoints::media::DeleteUrl::METHOD.route().to(delete_url),
     );
 }
@@ -3,6 +3,7 @@ pub mod admin;
 pub mod animation;
 pub mod audio;
 pub mod category;
+pub mod class;
 pub mod image;
 pub mod jig;
 pub mod locale;
@@ -1,6 +1,6 @@
 use paperclip::actix::{
     api_v2_operation,
-    web::{self, Data, Json, Path, ServiceConfig},
+    web::{self, Data, HttpRequest, Json, Path, ServiceConfig},
     NoContent,
 };
 use shared::{
@@ -15,7 +15,12 @@ use shared::{
 };
 use sqlx::PgPool;
 
-use crate::{db, error, extractor::TokenUser};
+use crate::{
+    db::{self, RevisionUpdate},
+    domain::{if_match_revisions, Conditional},
+    error,
+    extractor::{PlayerClaims, TokenPlayer, TokenUser},
+};
 
 /// Create a new module.
 #[api_v2_operation]
@@ -52,44 +57,62 @@ async fn delete(
 }
 
 /// Update a module.
+/// Note: this request can be conditional on `If-Match`
 #[api_v2_operation]
 async fn update(
     db: Data<PgPool>,
     auth: TokenUser,
     req: Option<Json<<module::Update as ApiEndpoint>::Req>>,
     path: web::Path<(JigId, ModuleId)>,
-) -> Result<NoContent, error::NotFound> {
+    http_req: HttpRequest,
+) -> Result<Conditional<ModuleResponse>, error::NotFound> {
     let (parent_id, module) = path.into_inner();
 
     db::jig::authz(&*db, auth.0.user_id, Some(parent_id)).await?;
 
+    let if_revision = if_match_revisions(&http_req);
+
     let req = req.map_or_else(Default::default, Json::into_inner);
-    let exists = db::module::update(
+    let res = db::module::update(
         &*db,
         parent_id,
         ModuleIdOrIndex::Id(module),
         req.body.as_ref(),
         req.index,
         req.is_complete,
+        if_revision.as_deref(),
     )
-    .await?;
-
-    if !exists {
-        return Err(error::NotFound::ResourceNotFound);
+    .await?
+    .ok_or(error::NotFound::ResourceNotFound)?;
+
+    match res {
+        RevisionUpdate::Updated(revision) => Ok(Conditional::Updated(revision)),
+        RevisionUpdate::Conflict => {
+            let module = db::module::get(&db, parent_id, ModuleIdOrIndex::Id(module))
+                .await?
+                .ok_or(error::NotFound::ResourceNotFound)?;
+
+            Ok(Conditional::PreconditionFailed {
+                revision: module.revision,
+                current: ModuleResponse { module },
+            })
+        }
     }
-
-    Ok(NoContent)
 }
 
 /// Get a module.
 #[api_v2_operation]
 async fn get(
     db: Data<PgPool>,
-    _claims: TokenUser,
+    claims: TokenPlayer,
     path: web::Path<(JigId, ModuleId)>,
 ) -> Result<Json<<module::Get as ApiEndpoint>::Res>, error::NotFound> {
     let (parent_id, module) = path.into_inner();
 
+    if let PlayerClaims::AssignmentStudent(claims) = &claims.0 {
+        db::class::assignment::authz_student(&db, claims.assignment_id, parent_id).await?;
+    }
+
     let module = db::module::get(&db, parent_id, ModuleIdOrIndex::Id(module))
         .await?
         .ok_or(error::NotFound::ResourceNotFound)?;
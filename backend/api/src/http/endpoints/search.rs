@@ -1,14 +1,20 @@
-use crate::{error, extractor::TokenUser, service::ServiceData};
+use crate::{db, error, extractor::TokenUser, service::ServiceData};
 use core::settings::RuntimeSettings;
 use paperclip::actix::{
     api_v2_operation,
     web::{Data, Json, Query, ServiceConfig},
-    CreatedJson,
+    CreatedJson, NoContent,
 };
 use shared::{
     api::{endpoints::search, ApiEndpoint},
-    domain::search::{CreateSearchKeyResponse, WebImageSearchResponse},
+    domain::search::{
+        CreateSearchKeyResponse, RecentSearchResponse, SearchSuggestResponse, SearchSuggestion,
+        SearchSuggestionKind, WebImageSearchResponse,
+    },
+    search::normalize,
 };
+use sqlx::PgPool;
+use std::collections::HashSet;
 
 /// Create an Algolia search key based on the user's auth. Currently expires after 15 minutes, but that number is subject to change.
 /// # Errors
@@ -43,6 +49,126 @@ pub async fn search_web_images(
     Ok(Json(res))
 }
 
+/// Collects suggestions until there's `limit` of them, skipping any that only differ in how they're written.
+struct Suggestions {
+    seen: HashSet<String>,
+    suggestions: Vec<SearchSuggestion>,
+    limit: u16,
+}
+
+impl Suggestions {
+    fn new(limit: u16) -> Self {
+        Self {
+            seen: HashSet::new(),
+            suggestions: Vec::new(),
+            limit,
+        }
+    }
+
+    /// How many more suggestions are wanted.
+    fn remaining(&self) -> u16 {
+        self.limit - self.suggestions.len() as u16
+    }
+
+    fn extend(&mut self, texts: Vec<String>, kind: SearchSuggestionKind) {
+        for text in texts {
+            if self.remaining() == 0 {
+                return;
+            }
+
+            if self.seen.insert(normalize(text.trim())) {
+                self.suggestions.push(SearchSuggestion { text, kind });
+            }
+        }
+    }
+}
+
+/// Get completions for a partially typed search.
+#[api_v2_operation]
+async fn suggest(
+    db: Data<PgPool>,
+    search: ServiceData<crate::search::Client>,
+    claims: TokenUser,
+    query: Query<<search::Suggest as ApiEndpoint>::Req>,
+) -> Result<Json<<search::Suggest as ApiEndpoint>::Res>, error::Service> {
+    let query = query.into_inner();
+
+    let limit = query
+        .limit
+        .unwrap_or(config::SEARCH_SUGGEST_DEFAULT_LIMIT)
+        .min(config::SEARCH_SUGGEST_MAX_LIMIT);
+
+    let q = normalize(query.q.trim_start());
+
+    let mut suggestions = Suggestions::new(limit);
+
+    let recent =
+        db::search::list_recent(&db, claims.0.user_id, &q, suggestions.remaining()).await?;
+
+    suggestions.extend(recent, SearchSuggestionKind::Recent);
+
+    // there's nothing to complete, so only suggest recent searches.
+    let tsquery = match crate::search::prefix_tsquery(&q) {
+        Some(tsquery) => tsquery,
+        None => {
+            return Ok(Json(SearchSuggestResponse {
+                suggestions: suggestions.suggestions,
+            }))
+        }
+    };
+
+    if suggestions.remaining() > 0 {
+        let ids = search.suggest_jigs(&q, suggestions.remaining()).await?;
+        let names = db::search::jig_names(&db, &ids).await?;
+        suggestions.extend(names, SearchSuggestionKind::Jig);
+    }
+
+    if suggestions.remaining() > 0 {
+        let ids = search.suggest_images(&q, suggestions.remaining()).await?;
+        let names = db::search::image_names(&db, &ids).await?;
+        suggestions.extend(names, SearchSuggestionKind::Image);
+    }
+
+    if suggestions.remaining() > 0 {
+        let names = db::search::tag_names(&db, &tsquery, suggestions.remaining()).await?;
+        suggestions.extend(names, SearchSuggestionKind::Tag);
+    }
+
+    if suggestions.remaining() > 0 {
+        let names = db::search::category_names(&db, &tsquery, suggestions.remaining()).await?;
+        suggestions.extend(names, SearchSuggestionKind::Category);
+    }
+
+    Ok(Json(SearchSuggestResponse {
+        suggestions: suggestions.suggestions,
+    }))
+}
+
+/// List the user's recent searches.
+#[api_v2_operation]
+async fn list_recent(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<search::ListRecent as ApiEndpoint>::Res>, error::Server> {
+    let queries = db::search::list_recent(
+        &db,
+        claims.0.user_id,
+        "",
+        config::RECENT_SEARCH_LIMIT as u16,
+    )
+    .await?;
+
+    Ok(Json(RecentSearchResponse { queries }))
+}
+
+/// Clear the user's recent searches.
+#[api_v2_operation]
+async fn clear_recent(db: Data<PgPool>, claims: TokenUser) -> Result<NoContent, error::Server> {
+    db::search::clear_recent(&db, claims.0.user_id).await?;
+
+    Ok(NoContent)
+}
+
 pub fn configure(cfg: &mut ServiceConfig<'_>) {
     cfg.route(
         search::CreateKey::PATH,
@@ -51,5 +177,17 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
     .route(
         search::WebImageSearch::PATH,
         search::WebImageSearch::METHOD.route().to(search_web_images),
+    )
+    .route(
+        search::Suggest::PATH,
+        search::Suggest::METHOD.route().to(suggest),
+    )
+    .route(
+        search::ListRecent::PATH,
+        search::ListRecent::METHOD.route().to(list_recent),
+    )
+    .route(
+        search::ClearRecent::PATH,
+        search::ClearRecent::METHOD.route().to(clear_recent),
     );
 }
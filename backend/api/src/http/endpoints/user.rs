@@ -22,8 +22,9 @@ use sendgrid::v3::Email;
 use shared::{
     api::endpoints::{
         user::{
-            ChangePassword, Create, CreateColor, CreateFont, Delete, DeleteColor, DeleteFont,
-            GetColors, GetFonts, Profile, PutProfile, ResetPassword, UpdateColor, UpdateFont,
+            ChangePassword, Create, CreateColor, CreateFont, CreateSavedSearch, Delete,
+            DeleteColor, DeleteFont, DeleteSavedSearch, GetColors, GetFonts, ListSavedSearches,
+            Profile, PutProfile, ResetPassword, UpdateColor, UpdateFont, UpdateSavedSearch,
             UserLookup, VerifyEmail,
         },
         ApiEndpoint,
@@ -39,6 +40,7 @@ use uuid::Uuid;
 
 mod color;
 mod font;
+mod saved_search;
 
 async fn send_verification_email(
     txn: &mut PgConnection,
@@ -582,5 +584,21 @@ pub fn configure(cfg: &mut ServiceConfig<'_>) {
         .route(
             DeleteFont::PATH,
             DeleteFont::METHOD.route().to(font::delete),
+        )
+        .route(
+            ListSavedSearches::PATH,
+            ListSavedSearches::METHOD.route().to(saved_search::list),
+        )
+        .route(
+            CreateSavedSearch::PATH,
+            CreateSavedSearch::METHOD.route().to(saved_search::create),
+        )
+        .route(
+            UpdateSavedSearch::PATH,
+            UpdateSavedSearch::METHOD.route().to(saved_search::update),
+        )
+        .route(
+            DeleteSavedSearch::PATH,
+            DeleteSavedSearch::METHOD.route().to(saved_search::delete),
         );
 }
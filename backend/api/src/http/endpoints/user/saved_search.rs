@@ -0,0 +1,88 @@
+use crate::{db, error, extractor::TokenUser};
+
+use paperclip::actix::{
+    api_v2_operation,
+    web::{Data, Json, Path},
+    CreatedJson, NoContent,
+};
+use shared::{
+    api::endpoints::{
+        user::{CreateSavedSearch, ListSavedSearches},
+        ApiEndpoint,
+    },
+    domain::{
+        user::saved_search::{
+            SavedSearchCreateRequest, SavedSearchId, SavedSearchListResponse,
+            SavedSearchUpdateRequest,
+        },
+        CreateResponse,
+    },
+};
+use sqlx::PgPool;
+
+#[api_v2_operation]
+pub async fn create(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<SavedSearchCreateRequest>,
+) -> Result<CreatedJson<<CreateSavedSearch as ApiEndpoint>::Res>, error::Server> {
+    let req = req.into_inner();
+
+    let id = db::saved_search::create(
+        db.as_ref(),
+        claims.0.user_id,
+        &req.name,
+        &req.query,
+        req.email_alerts,
+    )
+    .await?;
+
+    Ok(CreatedJson(CreateResponse { id }))
+}
+
+#[api_v2_operation]
+pub async fn list(
+    db: Data<PgPool>,
+    claims: TokenUser,
+) -> Result<Json<<ListSavedSearches as ApiEndpoint>::Res>, error::Server> {
+    let saved_searches = db::saved_search::list(db.as_ref(), claims.0.user_id).await?;
+
+    Ok(Json(SavedSearchListResponse { saved_searches }))
+}
+
+#[api_v2_operation]
+pub async fn update(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    req: Json<SavedSearchUpdateRequest>,
+    id: Path<SavedSearchId>,
+) -> Result<NoContent, error::NotFound> {
+    let req = req.into_inner();
+
+    let exists = db::saved_search::update(
+        db.as_ref(),
+        claims.0.user_id,
+        id.into_inner(),
+        req.name.as_deref(),
+        req.query.as_ref(),
+        req.email_alerts,
+    )
+    .await?;
+
+    if !exists {
+        return Err(error::NotFound::ResourceNotFound);
+    }
+
+    Ok(NoContent)
+}
+
+#[api_v2_operation]
+pub async fn delete(
+    db: Data<PgPool>,
+    claims: TokenUser,
+    id: Path<SavedSearchId>,
+) -> Result<NoContent, error::Delete> {
+    db::saved_search::delete(db.as_ref(), claims.0.user_id, id.into_inner()).await?;
+
+    Ok(NoContent)
+}
@@ -1,9 +1,28 @@
+use std::{convert::TryFrom, io::Cursor};
+
 use image::{
-    gif::GifDecoder, imageops::FilterType, AnimationDecoder, DynamicImage, GenericImageView,
-    ImageOutputFormat,
+    codecs::avif::AvifEncoder, gif::GifDecoder, imageops::FilterType, AnimationDecoder, ColorType,
+    DynamicImage, GenericImageView, ImageDecoder, ImageEncoder, ImageFormat, ImageOutputFormat,
+    RgbaImage,
+};
+use shared::domain::{
+    animation::{AnimationKind, SpritesheetFrames},
+    audio::AudioKind,
+    image::ImageKind,
 };
-use shared::domain::{animation::AnimationKind, audio::AudioKind, image::ImageKind};
-use shared::media::MediaKind as SharedMediaKind;
+use shared::media::{FileKind, MediaKind as SharedMediaKind, PngImageFile};
+
+use crate::error;
+
+/// Lossy WebP at this quality (out of 100) is hard to tell apart from the PNG.
+const WEBP_QUALITY: f32 = 80.0;
+
+/// Quality (out of 100) of the original of a lossy upload, when it has to be re-encoded to be the right way up.
+const ORIGINAL_QUALITY: u8 = 90;
+
+/// AVIF quality (out of 100) and encoding speed (from 1, the smallest files, to 10, the fastest).
+const AVIF_QUALITY: u8 = 70;
+const AVIF_SPEED: u8 = 8;
 
 /// Kinds of media used with the web media library
 #[repr(i16)]
@@ -37,15 +56,42 @@ impl MediaKind {
     }
 }
 
-// todo: use a better method for this
+/// Formats images can be uploaded in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UploadFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Svg,
+}
+
+/// Sniffs the format of an uploaded image from its contents (the content type it was uploaded with can't be trusted).
+pub fn detect_image_format(data: &[u8]) -> Result<UploadFormat, error::Upload> {
+    match image::guess_format(data) {
+        Ok(ImageFormat::Png) => Ok(UploadFormat::Png),
+        Ok(ImageFormat::Jpeg) => Ok(UploadFormat::Jpeg),
+        Ok(ImageFormat::Gif) => Ok(UploadFormat::Gif),
+        Ok(ImageFormat::WebP) => Ok(UploadFormat::Webp),
+        Ok(_) => Err(error::Upload::InvalidMedia),
+
+        // SVGs are XML, so they don't have a magic number.
+        Err(_) if String::from_utf8_lossy(&data[..data.len().min(1024)]).contains("<svg") => {
+            Ok(UploadFormat::Svg)
+        }
+
+        Err(_) => Err(error::Upload::InvalidMedia),
+    }
+}
+
 pub fn detect_image_kind(data: &[u8]) -> anyhow::Result<MediaKind> {
-    let decoder = GifDecoder::new(&*data);
+    match detect_image_format(data) {
+        Ok(UploadFormat::Gif) => {}
+        Ok(_) => return Ok(MediaKind::PngStickerImage),
+        Err(_) => anyhow::bail!("unrecognized image format"),
+    }
 
-    let frames = match decoder {
-        Ok(decoder) => decoder.into_frames().count(),
-        Err(image::ImageError::Decoding(_)) => return Ok(MediaKind::PngStickerImage),
-        Err(e) => return Err(e.into()),
-    };
+    let frames = GifDecoder::new(data)?.into_frames().take(2).count();
 
     if frames < 2 {
         Ok(MediaKind::PngStickerImage)
@@ -54,10 +100,126 @@ pub fn detect_image_kind(data: &[u8]) -> anyhow::Result<MediaKind> {
     }
 }
 
+/// Decodes an uploaded image (in any [`UploadFormat`]) the right way up.
+///
+/// SVGs are rasterized to fit `kind`'s size.
+pub fn load_image(data: &[u8], kind: ImageKind) -> Result<DynamicImage, error::Upload> {
+    let format = match detect_image_format(data)? {
+        UploadFormat::Png => ImageFormat::Png,
+        UploadFormat::Jpeg => ImageFormat::Jpeg,
+        UploadFormat::Gif => ImageFormat::Gif,
+        UploadFormat::Svg => return rasterize_svg(data, kind.size()),
+
+        // `image` only decodes simple lossy WebPs, not lossless ones or extended ones (which is what has EXIF).
+        UploadFormat::Webp => {
+            let image = webp::Decoder::new(data)
+                .decode()
+                .ok_or(error::Upload::InvalidMedia)?
+                .to_image();
+
+            return Ok(apply_orientation(image, exif_orientation(data)));
+        }
+    };
+
+    let image = image::load_from_memory_with_format(data, format)
+        .map_err(|_| error::Upload::InvalidMedia)?;
+
+    Ok(apply_orientation(image, exif_orientation(data)))
+}
+
+/// Returns the EXIF orientation of an image, or `1` (already the right way up) if it doesn't have one.
+fn exif_orientation(data: &[u8]) -> u32 {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(exif::Tag::Orientation, exif::In::PRIMARY)?
+                .value
+                .get_uint(0)
+        })
+        .unwrap_or(1)
+}
+
+/// Rotates and flips `image` the right way up, see <https://magnushoff.com/articles/jpeg-orientation/>.
+fn apply_orientation(image: DynamicImage, orientation: u32) -> DynamicImage {
+    match orientation {
+        2 => image.fliph(),
+        3 => image.rotate180(),
+        4 => image.flipv(),
+        5 => image.rotate90().fliph(),
+        6 => image.rotate90(),
+        7 => image.rotate270().fliph(),
+        8 => image.rotate270(),
+        _ => image,
+    }
+}
+
+fn rasterize_svg(data: &[u8], (width, height): (u32, u32)) -> Result<DynamicImage, error::Upload> {
+    let tree = usvg::Tree::from_data(data, &usvg::Options::default())
+        .map_err(|_| error::Upload::InvalidMedia)?;
+
+    let fit_to = usvg::FitTo::Size(width, height);
+
+    let size = fit_to
+        .fit_to(tree.svg_node().size.to_screen_size())
+        .ok_or(error::Upload::InvalidMedia)?;
+
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or(error::Upload::InvalidMedia)?;
+
+    resvg::render(&tree, fit_to, pixmap.as_mut()).ok_or(error::Upload::InvalidMedia)?;
+
+    // the pixmap is premultiplied, encoding it demultiplies it.
+    let png = pixmap
+        .encode_png()
+        .map_err(|_| error::Upload::InvalidMedia)?;
+
+    image::load_from_memory_with_format(&png, ImageFormat::Png)
+        .map_err(|_| error::Upload::InvalidMedia)
+}
+
+/// An image encoded in every [`ImageFileFormat`](shared::media::ImageFileFormat).
+pub struct ImageFiles {
+    pub png: Vec<u8>,
+    pub webp: Vec<u8>,
+    pub avif: Vec<u8>,
+}
+
+impl ImageFiles {
+    /// Failing to encode an image that decoded fine is the image's fault (it's never going to work), so it's [`InvalidMedia`](error::Upload::InvalidMedia).
+    fn encode(image: &DynamicImage) -> Result<Self, error::Upload> {
+        let mut png = Vec::new();
+        image
+            .write_to(&mut png, ImageOutputFormat::Png)
+            .map_err(|_| error::Upload::InvalidMedia)?;
+
+        let rgba = image.to_rgba8();
+        let (width, height) = rgba.dimensions();
+
+        let webp = webp::Encoder::from_rgba(&rgba, width, height)
+            .encode(WEBP_QUALITY)
+            .to_vec();
+
+        let mut avif = Vec::new();
+        AvifEncoder::new_with_speed_quality(&mut avif, AVIF_SPEED, AVIF_QUALITY)
+            .write_image(&rgba, width, height, ColorType::Rgba8)
+            .map_err(|_| error::Upload::InvalidMedia)?;
+
+        Ok(Self { png, webp, avif })
+    }
+}
+
+/// The original of an image, and the kind of file it's stored as (one of [`FileKind::ORIGINAL_IMAGE_FILES`]).
+pub struct OriginalFile {
+    pub data: Vec<u8>,
+    pub file_kind: FileKind,
+}
+
+/// Returns the resized image and thumbnail of `original`.
 pub fn regenerate_images(
     original: &DynamicImage,
     kind: ImageKind,
-) -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+) -> Result<(ImageFiles, ImageFiles), error::Upload> {
     let resized = {
         let (width, height) = kind.size();
         let new_image = match kind {
@@ -70,34 +232,298 @@ pub fn regenerate_images(
             ImageKind::Sticker => original.resize(width, height, FilterType::Nearest),
         };
 
-        let mut buffer = Vec::new();
-        new_image.write_to(&mut buffer, ImageOutputFormat::Png)?;
-        buffer
+        ImageFiles::encode(&new_image)?
     };
 
     let thumbnail = {
-        let mut buffer = Vec::new();
         let (width, height) = ImageKind::THUMBNAIL_SIZE;
-        original
-            .thumbnail(width, height)
-            .write_to(&mut buffer, ImageOutputFormat::Png)?;
-        buffer
+        ImageFiles::encode(&original.thumbnail(width, height))?
     };
 
     Ok((resized, thumbnail))
 }
 
+/// Returns the file to store as the original of the upload `data` (decoded as `original`), and its resized image and thumbnail.
+///
+/// The original is stripped of its metadata (EXIF can include where a photo was taken),
+/// see [`encode_original`].
 pub fn generate_images(
+    data: &[u8],
     original: &DynamicImage,
     kind: ImageKind,
-) -> anyhow::Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+) -> Result<(OriginalFile, ImageFiles, ImageFiles), error::Upload> {
     let (resized, thumbnail) = regenerate_images(original, kind)?;
 
-    let original = {
+    Ok((encode_original(data, original)?, resized, thumbnail))
+}
+
+/// Lossless uploads (and SVGs) are re-encoded as a PNG.
+///
+/// Lossy uploads (JPEGs and WebPs) are kept as they are, minus their metadata, and stored as a JPEG or WebP,
+/// since re-encoding them as a PNG would make them several times bigger (and re-encoding them lossily would make them worse).
+/// The ones that EXIF says are rotated are re-encoded the right way up instead, since stripping it would turn them.
+fn encode_original(data: &[u8], original: &DynamicImage) -> Result<OriginalFile, error::Upload> {
+    let format = detect_image_format(data)?;
+    let is_rotated = exif_orientation(data) != 1;
+
+    let file_kind = match format {
+        UploadFormat::Jpeg => FileKind::ImageJpeg(PngImageFile::Original),
+        UploadFormat::Webp => FileKind::ImageWebp(PngImageFile::Original),
+        UploadFormat::Png | UploadFormat::Gif | UploadFormat::Svg => {
+            FileKind::ImagePng(PngImageFile::Original)
+        }
+    };
+
+    let stripped = match format {
+        UploadFormat::Jpeg if !is_rotated => strip_jpeg_metadata(data),
+        UploadFormat::Webp if !is_rotated => strip_webp_metadata(data),
+        _ => None,
+    };
+
+    if let Some(data) = stripped {
+        return Ok(OriginalFile { data, file_kind });
+    }
+
+    let mut buffer = Vec::new();
+
+    match format {
+        UploadFormat::Jpeg => DynamicImage::ImageRgb8(original.to_rgb8())
+            .write_to(&mut buffer, ImageOutputFormat::Jpeg(ORIGINAL_QUALITY))
+            .map_err(|_| error::Upload::InvalidMedia)?,
+
+        UploadFormat::Webp => {
+            let rgba = original.to_rgba8();
+            let (width, height) = rgba.dimensions();
+
+            buffer = webp::Encoder::from_rgba(&rgba, width, height)
+                .encode(f32::from(ORIGINAL_QUALITY))
+                .to_vec();
+        }
+
+        UploadFormat::Png | UploadFormat::Gif | UploadFormat::Svg => original
+            .write_to(&mut buffer, ImageOutputFormat::Png)
+            .map_err(|_| error::Upload::InvalidMedia)?,
+    }
+
+    Ok(OriginalFile {
+        data: buffer,
+        file_kind,
+    })
+}
+
+/// Removes the EXIF, XMP, IPTC and comment segments of a JPEG, or returns `None` if it's malformed.
+///
+/// Everything from the start of the scan on is image data, and kept as is.
+fn strip_jpeg_metadata(data: &[u8]) -> Option<Vec<u8>> {
+    const APP1: u8 = 0xe1;
+    const APP13: u8 = 0xed;
+    const COM: u8 = 0xfe;
+    const SOS: u8 = 0xda;
+
+    if !data.starts_with(&[0xff, 0xd8]) {
+        return None;
+    }
+
+    let mut stripped = data[..2].to_vec();
+    let mut rest = &data[2..];
+
+    loop {
+        if rest.len() < 4 || rest[0] != 0xff {
+            return None;
+        }
+
+        let marker = rest[1];
+
+        if marker == SOS {
+            stripped.extend_from_slice(rest);
+            return Some(stripped);
+        }
+
+        // the length includes itself, but not the marker.
+        let len = usize::from(u16::from_be_bytes([rest[2], rest[3]])) + 2;
+        let segment = rest.get(..len)?;
+
+        if !matches!(marker, APP1 | APP13 | COM) {
+            stripped.extend_from_slice(segment);
+        }
+
+        rest = &rest[len..];
+    }
+}
+
+/// Removes the EXIF and XMP chunks of a WebP, or returns `None` if it's malformed.
+fn strip_webp_metadata(data: &[u8]) -> Option<Vec<u8>> {
+    // flags of the `VP8X` chunk saying there are EXIF and XMP chunks.
+    const EXIF_FLAG: u8 = 0x08;
+    const XMP_FLAG: u8 = 0x04;
+
+    if data.len() < 12 || &data[..4] != b"RIFF" || &data[8..12] != b"WEBP" {
+        return None;
+    }
+
+    let mut stripped = data[..12].to_vec();
+    let mut rest = &data[12..];
+
+    while !rest.is_empty() {
+        let header = rest.get(..8)?;
+        let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+
+        // chunks are padded to an even length.
+        let chunk = rest.get(..8 + len + len % 2)?;
+
+        match &header[..4] {
+            b"EXIF" | b"XMP " => {}
+
+            b"VP8X" if len > 0 => {
+                let start = stripped.len();
+                stripped.extend_from_slice(chunk);
+                stripped[start + 8] &= !(EXIF_FLAG | XMP_FLAG);
+            }
+
+            _ => stripped.extend_from_slice(chunk),
+        }
+
+        rest = &rest[chunk.len()..];
+    }
+
+    let riff_len = u32::try_from(stripped.len() - 8).ok()?;
+    stripped[4..8].copy_from_slice(&riff_len.to_le_bytes());
+
+    Some(stripped)
+}
+
+/// Returns a 64 bit difference hash of `image`, which stays (almost) the same when it's resized, re-encoded or recolored slightly.
+///
+/// The image is shrunk to 9x8 greyscale pixels, and each bit is whether a pixel is brighter than the one to its right,
+/// so the number of bits two hashes differ by is how different the images look.
+pub fn perceptual_hash(image: &DynamicImage) -> i64 {
+    let small = image
+        .grayscale()
+        .resize_exact(9, 8, FilterType::Triangle)
+        .to_luma8();
+
+    let mut hash = 0_u64;
+
+    for y in 0..8 {
+        for x in 0..8 {
+            let bit = small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(bit);
+        }
+    }
+
+    // stored as a `bigint`, only the bits matter.
+    hash as i64
+}
+
+/// Checks that `sheet` is a PNG that the `frames` JSON fits, and that it's small enough to be played.
+pub fn validate_spritesheet(sheet: &[u8], frames: &[u8]) -> Result<(), error::Upload> {
+    let frames: SpritesheetFrames =
+        serde_json::from_slice(frames).map_err(|_| error::Upload::InvalidMedia)?;
+
+    let sheet = image::load_from_memory_with_format(sheet, ImageFormat::Png)
+        .map_err(|_| error::Upload::InvalidMedia)?;
+
+    let (width, height) = sheet.dimensions();
+
+    if width > config::SPRITESHEET_MAX_SIZE
+        || height > config::SPRITESHEET_MAX_SIZE
+        || !frames.fits(width, height)
+    {
+        return Err(error::Upload::InvalidMedia);
+    }
+
+    Ok(())
+}
+
+/// Lays `frame_count` frames out as close to square as possible, returning the size of the sheet,
+/// or `None` if it would be too big to be played.
+///
+/// The sheet never gets smaller as frames are added.
+fn spritesheet_size(frame_count: u32, frame_width: u32, frame_height: u32) -> Option<(u32, u32)> {
+    let columns = (f64::from(frame_count).sqrt().ceil() as u32).max(1);
+    let rows = (frame_count + columns - 1) / columns;
+
+    let width = columns.checked_mul(frame_width)?;
+    let height = rows.checked_mul(frame_height)?;
+
+    if width <= config::SPRITESHEET_MAX_SIZE && height <= config::SPRITESHEET_MAX_SIZE {
+        Some((width, height))
+    } else {
+        None
+    }
+}
+
+/// Converts an animated GIF into a spritesheet, returning the sheet (as a PNG) and its frames (as JSON).
+///
+/// Frames are laid out as close to square as possible,
+/// and play at the GIF's average frame rate (spritesheets can't vary the delay between frames).
+///
+/// GIFs with too many (or too big) frames are rejected as soon as the sheet would be too big, rather than after decoding every frame.
+pub fn gif_to_spritesheet(gif: &[u8]) -> Result<(Vec<u8>, Vec<u8>), error::Upload> {
+    let decoder = GifDecoder::new(gif).map_err(|_| error::Upload::InvalidMedia)?;
+
+    // every frame is the size of the GIF's canvas.
+    let (frame_width, frame_height) = decoder.dimensions();
+
+    let mut gif_frames = Vec::new();
+
+    for frame in decoder.into_frames() {
+        let frame = frame.map_err(|_| error::Upload::InvalidMedia)?;
+
+        spritesheet_size(gif_frames.len() as u32 + 1, frame_width, frame_height)
+            .ok_or(error::Upload::InvalidMedia)?;
+
+        gif_frames.push(frame);
+    }
+
+    if gif_frames.is_empty() {
+        return Err(error::Upload::InvalidMedia);
+    }
+
+    let frame_count = gif_frames.len() as u32;
+
+    let (width, height) = spritesheet_size(frame_count, frame_width, frame_height)
+        .ok_or(error::Upload::InvalidMedia)?;
+
+    let total_delay_ms: u64 = gif_frames
+        .iter()
+        .map(|frame| {
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            let delay = u64::from(numer) / u64::from(denom.max(1));
+
+            // browsers play (almost) zero delay frames at 100ms, so do the same.
+            if delay <= 10 {
+                100
+            } else {
+                delay
+            }
+        })
+        .sum();
+
+    let frames = SpritesheetFrames {
+        frame_width,
+        frame_height,
+        frame_count,
+        frame_rate: (f64::from(frame_count) * 1000.0 / total_delay_ms as f64) as f32,
+    };
+
+    let mut sheet = RgbaImage::new(width, height);
+
+    for (index, frame) in gif_frames.iter().enumerate() {
+        let (x, y) = frames.frame_position(index as u32, width);
+        image::imageops::replace(&mut sheet, frame.buffer(), x, y);
+    }
+
+    // failing to encode the sheet is the GIF's fault, it's never going to work.
+    let sheet = {
         let mut buffer = Vec::new();
-        original.write_to(&mut buffer, ImageOutputFormat::Png)?;
+        DynamicImage::ImageRgba8(sheet)
+            .write_to(&mut buffer, ImageOutputFormat::Png)
+            .map_err(|_| error::Upload::InvalidMedia)?;
         buffer
     };
 
-    Ok((original, resized, thumbnail))
+    let frames = serde_json::to_vec(&frames).map_err(|_| error::Upload::InvalidMedia)?;
+
+    Ok((sheet, frames))
 }
@@ -0,0 +1,126 @@
+//! Reading and writing the zip archives used to export and import JIGs.
+//!
+//! See [`shared::domain::jig::archive`] for the layout.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+
+use anyhow::Context;
+use shared::domain::jig::{
+    archive::{
+        JigArchiveManifest, JIG_ARCHIVE_MANIFEST, JIG_ARCHIVE_MODULES_DIR, JIG_ARCHIVE_VERSION,
+    },
+    module::Module,
+};
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+pub struct JigArchive {
+    pub manifest: JigArchiveManifest,
+
+    /// The modules, in order.
+    pub modules: Vec<Module>,
+
+    /// Media files, by [`media_key`](shared::media::media_key).
+    pub media: HashMap<String, Vec<u8>>,
+}
+
+fn module_path(index: usize) -> String {
+    format!("{}/{}.json", JIG_ARCHIVE_MODULES_DIR, index)
+}
+
+fn module_index(path: &str) -> Option<usize> {
+    path.strip_prefix(JIG_ARCHIVE_MODULES_DIR)?
+        .strip_prefix('/')?
+        .strip_suffix(".json")?
+        .parse()
+        .ok()
+}
+
+impl JigArchive {
+    /// Note: this is blocking.
+    pub fn write(&self) -> anyhow::Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+
+        let json = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        // media is already compressed
+        let media = FileOptions::default().compression_method(CompressionMethod::Stored);
+
+        zip.start_file(JIG_ARCHIVE_MANIFEST, json)?;
+        serde_json::to_writer_pretty(&mut zip, &self.manifest)?;
+
+        for (index, module) in self.modules.iter().enumerate() {
+            zip.start_file(module_path(index), json)?;
+            serde_json::to_writer_pretty(&mut zip, module)?;
+        }
+
+        for (key, data) in &self.media {
+            zip.start_file(key.as_str(), media)?;
+            zip.write_all(data)?;
+        }
+
+        Ok(zip.finish()?.into_inner())
+    }
+
+    /// Returns an error if `data` isn't a valid archive, or is newer than this server understands.
+    ///
+    /// `size_limit` limits the total (uncompressed) size of the archive's contents.
+    ///
+    /// Note: this is blocking.
+    pub fn read(data: &[u8], size_limit: u64) -> anyhow::Result<Self> {
+        let mut zip = ZipArchive::new(Cursor::new(data))?;
+
+        let mut manifest = None;
+        let mut modules = Vec::new();
+        let mut media = HashMap::new();
+
+        let mut remaining = size_limit;
+
+        for idx in 0..zip.len() {
+            let file = zip.by_index(idx)?;
+            if file.is_dir() {
+                continue;
+            }
+
+            let name = file.name().to_owned();
+
+            let mut contents = Vec::new();
+
+            // don't trust the size in the header.
+            file.take(remaining + 1).read_to_end(&mut contents)?;
+
+            remaining = remaining
+                .checked_sub(contents.len() as u64)
+                .context("archive contents are too large")?;
+
+            if name == JIG_ARCHIVE_MANIFEST {
+                manifest = Some(serde_json::from_slice::<JigArchiveManifest>(&contents)?);
+            } else if let Some(index) = module_index(&name) {
+                let module: Module = serde_json::from_slice(&contents)
+                    .with_context(|| format!("invalid module `{}`", name))?;
+
+                modules.push((index, module));
+            } else {
+                media.insert(name, contents);
+            }
+        }
+
+        let manifest = manifest.context("archive is missing its manifest")?;
+
+        anyhow::ensure!(
+            manifest.version <= JIG_ARCHIVE_VERSION,
+            "unsupported archive version {}",
+            manifest.version
+        );
+
+        modules.sort_by_key(|(index, _)| *index);
+
+        Ok(Self {
+            manifest,
+            modules: modules.into_iter().map(|(_, module)| module).collect(),
+            media,
+        })
+    }
+}
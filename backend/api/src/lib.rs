@@ -31,6 +31,7 @@
 )]
 
 pub mod algolia;
+mod audio_ops;
 pub mod db;
 mod domain;
 pub mod error;
@@ -39,11 +40,19 @@ pub mod google;
 pub mod http;
 mod image_ops;
 pub mod image_search;
+mod jig_archive;
 pub mod jwk;
 pub mod logger;
+pub mod media_gc;
+mod module_media;
+pub mod module_text;
 pub(crate) mod more_futures;
 pub mod s3;
+pub mod saved_search;
+pub mod scheduler;
+pub mod search;
 pub mod service;
+pub mod storage;
 pub mod token;
 
 // todo: make this configurable?
@@ -16,7 +16,10 @@ use std::thread;
 use anyhow::Context;
 use core::settings::{self, SettingsManager};
 
-use ji_cloud_api::{algolia, db, http, jwk, logger, s3, service};
+use ji_cloud_api::{
+    algolia, db, http, jwk, logger, media_gc, module_text, saved_search, scheduler, search,
+    service, storage,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -26,14 +29,14 @@ async fn main() -> anyhow::Result<()> {
 
     let (
         runtime_settings,
-        s3,
-        gcs,
+        storage,
         algolia_client,
         algolia_key_store,
         algolia_manager,
         db_pool,
         jwk_verifier,
         mail_client,
+        notifications,
         _guard,
     ) = {
         log::trace!("initializing settings and processes");
@@ -45,17 +48,7 @@ async fn main() -> anyhow::Result<()> {
 
         let runtime_settings = settings.runtime_settings().await?;
 
-        let s3 = settings
-            .s3_settings()
-            .await?
-            .map(s3::Client::new)
-            .transpose()?;
-
-        let gcs = settings
-            .google_cloud_storage_settings()
-            .await?
-            .map(service::storage::Client::new)
-            .transpose()?;
+        let storage = storage::from_settings(&settings).await?;
 
         let algolia_settings = settings.algolia_settings().await?;
 
@@ -90,16 +83,22 @@ async fn main() -> anyhow::Result<()> {
             .await?
             .map(service::mail::Client::new);
 
+        let notifications = settings
+            .fcm_settings()
+            .await?
+            .map(service::notifications::Client::new)
+            .transpose()?;
+
         (
             runtime_settings,
-            s3,
-            gcs,
+            storage,
             algolia_client,
             algolia_key_store,
             algolia_manager,
             db_pool,
             jwk_verifier,
             mail_client,
+            notifications,
             guard,
         )
     };
@@ -114,12 +113,36 @@ async fn main() -> anyhow::Result<()> {
         let _ = algolia_manager.spawn();
     }
 
+    if let Some(storage) = storage.clone() {
+        let _ = media_gc::Manager::new(db_pool.clone(), storage.clone()).spawn();
+        let _ = service::uploads::spawn_perceptual_hash_backfill(db_pool.clone(), storage);
+    }
+
+    let _ = scheduler::Manager::new(db_pool.clone(), notifications).spawn();
+
+    // digests can only be sent with a template for them.
+    if let Some(mail) = mail_client
+        .clone()
+        .filter(|it| it.saved_search_digest_template().is_ok())
+    {
+        let search = search::Client::new(algolia_client.clone(), db_pool.clone());
+
+        let _ = saved_search::Manager::new(
+            db_pool.clone(),
+            search,
+            mail,
+            runtime_settings.remote_target(),
+        )
+        .spawn();
+    }
+
+    let _ = module_text::spawn_backfill(db_pool.clone());
+
     let handle = thread::spawn(|| {
         http::build_and_run(
             db_pool,
             runtime_settings,
-            s3,
-            gcs,
+            storage,
             algolia_client,
             algolia_key_store,
             jwk_verifier,
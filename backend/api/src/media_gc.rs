@@ -0,0 +1,208 @@
+//! Garbage collection for media that no module uses.
+//!
+//! User library images and audio files, and web media library entries, are deleted along with their files once they're
+//! older than [`MEDIA_GC_GRACE_PERIOD_SECS`](config::MEDIA_GC_GRACE_PERIOD_SECS) and no module uses them,
+//! either directly or through a saved jig version (see [`module_media_ref`](crate::db::module_media_ref)).
+//!
+//! Global library media is never collected, it's managed by admins.
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use shared::media::{FileKind, MediaLibrary};
+use sqlx::PgPool;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{image_ops::MediaKind, storage::MediaStorage};
+
+pub struct Manager {
+    db: PgPool,
+    storage: Arc<dyn MediaStorage>,
+}
+
+impl Manager {
+    #[must_use]
+    pub const fn new(db: PgPool, storage: Arc<dyn MediaStorage>) -> Self {
+        Self { db, storage }
+    }
+
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            loop {
+                let iteration_start = Instant::now();
+
+                if let Err(e) = self.collect().await.context("media gc task errored") {
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+                }
+
+                tokio::time::delay_until(
+                    (iteration_start + Duration::from_secs(config::MEDIA_GC_INTERVAL_SECS)).into(),
+                )
+                .await;
+            }
+        })
+    }
+
+    async fn collect(&self) -> anyhow::Result<()> {
+        let cutoff = Utc::now() - chrono::Duration::seconds(config::MEDIA_GC_GRACE_PERIOD_SECS);
+
+        let (mut images, mut audio_files, mut web_media) = (0, 0, 0);
+
+        loop {
+            let count = self.collect_user_images(cutoff).await?;
+            images += count;
+            if count < config::MEDIA_GC_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        loop {
+            let count = self.collect_user_audio(cutoff).await?;
+            audio_files += count;
+            if count < config::MEDIA_GC_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        loop {
+            let count = self.collect_web_media(cutoff).await?;
+            web_media += count;
+            if count < config::MEDIA_GC_BATCH_SIZE as usize {
+                break;
+            }
+        }
+
+        if images + audio_files + web_media > 0 {
+            log::info!(
+                "media gc: deleted {} user images, {} user audio files and {} web media",
+                images,
+                audio_files,
+                web_media
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn collect_user_images(&self, cutoff: DateTime<Utc>) -> anyhow::Result<usize> {
+        let mut txn = self.db.begin().await?;
+
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+select id
+from user_image_library
+where created_at < $1
+  and not exists(select 1 from module_media_ref where media_id = user_image_library.id and kind = 0 and library = 1)
+  and not exists(select 1 from jig_version_media_ref where media_id = user_image_library.id and kind = 0 and library = 1)
+limit $2
+for update skip locked
+"#,
+            cutoff,
+            config::MEDIA_GC_BATCH_SIZE,
+        )
+        .fetch_all(&mut txn)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect();
+
+        sqlx::query!(
+            "delete from user_image_upload where image_id = any($1)",
+            &ids
+        )
+        .execute(&mut txn)
+        .await?;
+
+        sqlx::query!("delete from user_image_library where id = any($1)", &ids)
+            .execute(&mut txn)
+            .await?;
+
+        txn.commit().await?;
+
+        for &id in &ids {
+            self.storage.delete_image(MediaLibrary::User, id).await;
+        }
+
+        Ok(ids.len())
+    }
+
+    async fn collect_user_audio(&self, cutoff: DateTime<Utc>) -> anyhow::Result<usize> {
+        let ids: Vec<Uuid> = sqlx::query!(
+            r#"
+delete from user_audio_library
+where id in (
+    select id
+    from user_audio_library
+    where created_at < $1
+      and not exists(select 1 from module_media_ref where media_id = user_audio_library.id and kind = 1 and library = 1)
+      and not exists(select 1 from jig_version_media_ref where media_id = user_audio_library.id and kind = 1 and library = 1)
+    limit $2
+    for update skip locked
+)
+returning id
+"#,
+            cutoff,
+            config::MEDIA_GC_BATCH_SIZE,
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect();
+
+        for &id in &ids {
+            self.storage
+                .delete_media(MediaLibrary::User, FileKind::AudioMp3, id)
+                .await;
+        }
+
+        Ok(ids.len())
+    }
+
+    async fn collect_web_media(&self, cutoff: DateTime<Utc>) -> anyhow::Result<usize> {
+        // only the kinds that we know how to delete the files of (see `http::endpoints::media::delete_media`).
+        let media = sqlx::query!(
+            r#"
+delete from web_media_library
+where id in (
+    select id
+    from web_media_library
+    where created_at < $1
+      and kind in (0, 1)
+      and not exists(select 1 from module_media_ref where media_id = web_media_library.id and library = 2)
+      and not exists(select 1 from jig_version_media_ref where media_id = web_media_library.id and library = 2)
+    limit $2
+    for update skip locked
+)
+returning id, kind as "kind: MediaKind"
+"#,
+            cutoff,
+            config::MEDIA_GC_BATCH_SIZE,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        for record in &media {
+            let delete = |file_kind| {
+                self.storage
+                    .delete_media(MediaLibrary::Web, file_kind, record.id)
+            };
+            match record.kind {
+                MediaKind::GifAnimation => delete(FileKind::AnimationGif).await,
+                _ => {
+                    self.storage
+                        .delete_image(MediaLibrary::Web, record.id)
+                        .await
+                }
+            }
+        }
+
+        Ok(media.len())
+    }
+}
@@ -0,0 +1,261 @@
+//! Finds (and rewrites) the images and audio referenced by module bodies.
+//!
+//! Every [`Body`] variant lists its media through [`ModuleMedia`], which is implemented for each type a body is made of:
+//! * images are `Sprite::image`, `Background::Image` and `Card::Image`
+//! * audio is `Instructions::audio` and the drag & drop / tapping board item `audio`
+//!
+//! Note: the `module_media_ref` backfill migration walks the serialized bodies for the same fields.
+
+use std::collections::HashMap;
+
+use shared::{
+    domain::{
+        audio::AudioId,
+        image::ImageId,
+        jig::module::body::{
+            Audio, Background, Body, Image, Instructions,
+            _groups::{cards, design},
+            drag_drop, tapping_board,
+        },
+    },
+    media::{FileKind, MediaLibrary},
+};
+use uuid::Uuid;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(i16)]
+pub enum MediaRefKind {
+    Image = 0,
+    Audio = 1,
+}
+
+impl MediaRefKind {
+    /// The files stored for this kind of media.
+    pub const fn files(self) -> &'static [FileKind] {
+        match self {
+            // everything else is regenerated from the original, which is only stored as one of these.
+            Self::Image => FileKind::ORIGINAL_IMAGE_FILES,
+            Self::Audio => &[FileKind::AudioMp3],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MediaRef {
+    pub kind: MediaRefKind,
+    pub library: MediaLibrary,
+    pub id: Uuid,
+}
+
+/// Implemented by module bodies, and the types they're made of, to visit the media they reference.
+pub trait ModuleMedia {
+    /// Calls `f` with every media reference, replacing it with what `f` returns (if anything).
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>);
+}
+
+impl<T: ModuleMedia> ModuleMedia for Option<T> {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        if let Some(it) = self {
+            it.visit_media(f);
+        }
+    }
+}
+
+impl<T: ModuleMedia> ModuleMedia for Vec<T> {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        for it in self {
+            it.visit_media(f);
+        }
+    }
+}
+
+impl ModuleMedia for Image {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        let media = MediaRef {
+            kind: MediaRefKind::Image,
+            library: self.lib,
+            id: self.id.0,
+        };
+
+        if let Some(new) = f(media) {
+            self.id = ImageId(new.id);
+            self.lib = new.library;
+        }
+    }
+}
+
+impl ModuleMedia for Audio {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        let media = MediaRef {
+            kind: MediaRefKind::Audio,
+            library: self.lib,
+            id: self.id.0,
+        };
+
+        if let Some(new) = f(media) {
+            self.id = AudioId(new.id);
+            self.lib = new.library;
+        }
+    }
+}
+
+impl ModuleMedia for Instructions {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.audio.visit_media(f);
+    }
+}
+
+impl ModuleMedia for Background {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        match self {
+            Self::Image(image) => image.visit_media(f),
+            Self::Color(_) => {}
+        }
+    }
+}
+
+impl ModuleMedia for cards::Card {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        match self {
+            Self::Image(image) => image.visit_media(f),
+            Self::Text(_) => {}
+        }
+    }
+}
+
+impl ModuleMedia for cards::CardPair {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.0.visit_media(f);
+        self.1.visit_media(f);
+    }
+}
+
+impl ModuleMedia for cards::BaseContent {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.instructions.visit_media(f);
+        self.pairs.visit_media(f);
+        self.background.visit_media(f);
+    }
+}
+
+impl ModuleMedia for design::Sticker {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        match self {
+            Self::Sprite(sprite) => sprite.image.visit_media(f),
+            Self::Text(_) => {}
+        }
+    }
+}
+
+impl ModuleMedia for design::Backgrounds {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.layer_1.visit_media(f);
+        self.layer_2.visit_media(f);
+    }
+}
+
+impl ModuleMedia for design::BaseContent {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.instructions.visit_media(f);
+        self.backgrounds.visit_media(f);
+        self.stickers.visit_media(f);
+    }
+}
+
+impl ModuleMedia for tapping_board::TappingTrace {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.audio.visit_media(f);
+    }
+}
+
+impl ModuleMedia for drag_drop::Item {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        self.sticker.visit_media(f);
+
+        match &mut self.kind {
+            drag_drop::ItemKind::Interactive(interactive) => interactive.audio.visit_media(f),
+            drag_drop::ItemKind::Static => {}
+        }
+    }
+}
+
+impl ModuleMedia for Body {
+    fn visit_media(&mut self, f: &mut dyn FnMut(MediaRef) -> Option<MediaRef>) {
+        match self {
+            Self::MemoryGame(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::Matching(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::Flashcards(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::CardQuiz(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::Poster(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::Cover(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                }
+            }
+
+            Self::TappingBoard(body) => {
+                if let Some(content) = &mut body.content {
+                    content.base.visit_media(f);
+                    content.traces.visit_media(f);
+                }
+            }
+
+            Self::DragDrop(body) => {
+                if let Some(content) = &mut body.content {
+                    content.instructions.visit_media(f);
+                    content.backgrounds.visit_media(f);
+                    content.items.visit_media(f);
+                    content.feedback.visit_media(f);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Returns every (distinct) media referenced by the module body, in the order they're found.
+pub fn media_refs(body: &Body) -> Vec<MediaRef> {
+    let mut refs = Vec::new();
+
+    // nothing is replaced, since the callback always returns `None`.
+    body.clone().visit_media(&mut |media| {
+        if !refs.contains(&media) {
+            refs.push(media);
+        }
+
+        None
+    });
+
+    refs
+}
+
+/// Replaces every media reference in the module body that's in `map`.
+pub fn remap_media_refs(body: &mut Body, map: &HashMap<MediaRef, MediaRef>) {
+    body.visit_media(&mut |media| map.get(&media).copied());
+}
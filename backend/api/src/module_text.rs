@@ -0,0 +1,236 @@
+//! Extracts the text shown by module bodies, so that jigs can be searched by it.
+//!
+//! Like [`module_media`](crate::module_media), every [`Body`] variant lists its text through [`ModuleText`]:
+//! * `Card::Text` and `Sticker::Text`
+//! * `Instructions::text` and the tapping board trace `text`
+//!
+//! Card and sticker text can be the rich text the `text_editor` component produces, which is itself JSON
+//! (`{ "version": "0.1.0", "content": [{ "children": [{ "text": .. }] }] }`), only the text of its leaves is kept.
+
+use serde_json::Value;
+use shared::domain::jig::module::body::{
+    _groups::{cards, design},
+    drag_drop, tapping_board, Body, Instructions,
+};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+
+use crate::db;
+
+/// Collects the text of every leaf under `value`, rich text leaves are the objects with a `text` key.
+fn rich_text_leaves(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => match map.get("text") {
+            Some(Value::String(text)) => out.push_str(text),
+            _ => {
+                if let Some(children) = map.get("children") {
+                    rich_text_leaves(children, out);
+                }
+            }
+        },
+
+        Value::Array(values) => {
+            for value in values {
+                rich_text_leaves(value, out);
+            }
+        }
+
+        _ => {}
+    }
+}
+
+/// Pushes the (rich or plain) text.
+fn push_raw_text(raw: &str, out: &mut Vec<String>) {
+    let mut push = |text: &str| {
+        let text = text.trim();
+        if !text.is_empty() {
+            out.push(text.to_owned());
+        }
+    };
+
+    match serde_json::from_str::<Value>(raw) {
+        // every element (paragraph) of the rich text is its own piece of text.
+        Ok(Value::Object(map)) if map.contains_key("version") => {
+            let content = map.get("content").and_then(Value::as_array);
+
+            for element in content.into_iter().flatten() {
+                let mut text = String::new();
+                rich_text_leaves(element, &mut text);
+                push(&text);
+            }
+        }
+
+        // plain text (which might happen to be valid JSON, like a number).
+        _ => push(raw),
+    }
+}
+
+/// Implemented by module bodies, and the types they're made of, to list the text they show.
+pub trait ModuleText {
+    /// Pushes every piece of text onto `out`, in the order they're shown.
+    fn push_text(&self, out: &mut Vec<String>);
+}
+
+impl<T: ModuleText> ModuleText for Option<T> {
+    fn push_text(&self, out: &mut Vec<String>) {
+        if let Some(it) = self {
+            it.push_text(out);
+        }
+    }
+}
+
+impl<T: ModuleText> ModuleText for Vec<T> {
+    fn push_text(&self, out: &mut Vec<String>) {
+        for it in self {
+            it.push_text(out);
+        }
+    }
+}
+
+impl ModuleText for String {
+    fn push_text(&self, out: &mut Vec<String>) {
+        push_raw_text(self, out);
+    }
+}
+
+impl ModuleText for Instructions {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.text.push_text(out);
+    }
+}
+
+impl ModuleText for cards::Card {
+    fn push_text(&self, out: &mut Vec<String>) {
+        match self {
+            Self::Text(text) => text.push_text(out),
+            Self::Image(_) => {}
+        }
+    }
+}
+
+impl ModuleText for cards::CardPair {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.0.push_text(out);
+        self.1.push_text(out);
+    }
+}
+
+impl ModuleText for cards::BaseContent {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.instructions.push_text(out);
+        self.pairs.push_text(out);
+    }
+}
+
+impl ModuleText for design::Sticker {
+    fn push_text(&self, out: &mut Vec<String>) {
+        match self {
+            Self::Text(text) => text.value.push_text(out),
+            Self::Sprite(_) => {}
+        }
+    }
+}
+
+impl ModuleText for design::BaseContent {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.instructions.push_text(out);
+        self.stickers.push_text(out);
+    }
+}
+
+impl ModuleText for tapping_board::TappingTrace {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.text.push_text(out);
+    }
+}
+
+impl ModuleText for drag_drop::Item {
+    fn push_text(&self, out: &mut Vec<String>) {
+        self.sticker.push_text(out);
+    }
+}
+
+impl ModuleText for Body {
+    fn push_text(&self, out: &mut Vec<String>) {
+        match self {
+            Self::MemoryGame(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::Matching(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::Flashcards(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::CardQuiz(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::Poster(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::Cover(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                }
+            }
+
+            Self::TappingBoard(body) => {
+                if let Some(content) = &body.content {
+                    content.base.push_text(out);
+                    content.traces.push_text(out);
+                }
+            }
+
+            Self::DragDrop(body) => {
+                if let Some(content) = &body.content {
+                    content.instructions.push_text(out);
+                    content.items.push_text(out);
+                    content.feedback.push_text(out);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Returns the text shown by the module body, one piece of text per line.
+pub fn module_text(body: &Body) -> String {
+    let mut out = Vec::new();
+    body.push_text(&mut out);
+    out.join("\n")
+}
+
+/// Extracts the module text of jigs created before it was stored.
+#[must_use]
+pub fn spawn_backfill(db: PgPool) -> JoinHandle<()> {
+    tokio::task::spawn(async move {
+        loop {
+            match db::module_text::backfill(&db).await {
+                Ok(0) => return,
+                Ok(count) => log::debug!("Extracted the module text of {} jig(s)", count),
+                Err(e) => {
+                    let e = anyhow::Error::from(e).context("module text backfill errored");
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+                    return;
+                }
+            }
+        }
+    })
+}
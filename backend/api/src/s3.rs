@@ -7,7 +7,7 @@ use rusoto_core::{
 use rusoto_s3::{
     CopyObjectRequest, DeleteObjectRequest, GetObjectError, GetObjectRequest, PutObjectRequest, S3,
 };
-use shared::media::{self, media_key, FileKind, MediaLibrary, PngImageFile};
+use shared::media::{self, FileKind, MediaLibrary};
 use tokio::io::AsyncReadExt;
 use uuid::Uuid;
 
@@ -61,86 +61,16 @@ impl Client {
         &self.processing_bucket
     }
 
-    pub async fn upload_png_images_copy_original(
-        &self,
-        library: MediaLibrary,
-        image: Uuid,
-        resized: Vec<u8>,
-        thumbnail: Vec<u8>,
-    ) -> anyhow::Result<()> {
-        futures::future::try_join(
-            self.copy_processed_file(library, image, FileKind::ImagePng(PngImageFile::Original)),
-            self.upload_png_images_resized_thumb(library, image, resized, thumbnail),
-        )
-        .await
-        .map(drop)
-    }
-
-    pub async fn upload_png_images_resized_thumb(
-        &self,
-        library: MediaLibrary,
-        image: Uuid,
-        resized: Vec<u8>,
-        thumbnail: Vec<u8>,
-    ) -> anyhow::Result<()> {
-        let upload = |data, file| self.upload_media(data, library, image, FileKind::ImagePng(file));
-
-        let resized = upload(resized, PngImageFile::Resized);
-        let thumbnail = upload(thumbnail, PngImageFile::Thumbnail);
-
-        futures::future::try_join(resized, thumbnail).await?;
-
-        Ok(())
-    }
-
-    pub async fn upload_png_images(
+    // note: does nothing if object doesn't exist.
+    pub async fn delete_media_file(
         &self,
         library: MediaLibrary,
-        image: Uuid,
-        original: Vec<u8>,
-        resized: Vec<u8>,
-        thumbnail: Vec<u8>,
+        id: Uuid,
+        file_kind: FileKind,
     ) -> anyhow::Result<()> {
-        let upload = |data, file| self.upload_media(data, library, image, FileKind::ImagePng(file));
-
-        let original = upload(original, PngImageFile::Original);
-        let resized = upload(resized, PngImageFile::Resized);
-        let thumbnail = upload(thumbnail, PngImageFile::Thumbnail);
-
-        futures::future::try_join3(original, resized, thumbnail).await?;
-
-        Ok(())
-    }
-
-    pub async fn delete_media(&self, library: MediaLibrary, file: FileKind, id: Uuid) {
-        let key = media_key(library, id, file);
-        if let Err(err) = self.try_delete(key.clone()).await {
-            log::warn!("failed to delete {} from s3: {}", key, err);
-
-            sentry::with_scope(
-                |scope| scope.set_level(Some(sentry::Level::Warning)),
-                || {
-                    sentry::add_breadcrumb(sentry::Breadcrumb {
-                        ty: "info".to_owned(),
-                        data: {
-                            let mut map = sentry::protocol::Map::new();
-                            map.insert("key".to_owned(), key.clone().into());
-                            map
-                        },
-                        ..Default::default()
-                    });
-
-                    sentry::integrations::anyhow::capture_anyhow(&err);
-                },
-            );
-        }
-    }
-
-    // note: does nothing if object doesn't exist.
-    async fn try_delete(&self, key: String) -> anyhow::Result<()> {
         self.client
             .delete_object(DeleteObjectRequest {
-                key,
+                key: media::media_key(library, id, file_kind),
                 bucket: self.media_bucket.clone(),
                 ..DeleteObjectRequest::default()
             })
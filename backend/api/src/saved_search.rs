@@ -0,0 +1,184 @@
+//! Emails users digests of newly published jigs matching their saved searches
+//! (see [`user::CreateSavedSearch`](shared::api::endpoints::user::CreateSavedSearch)).
+//!
+//! Every saved search with email alerts is re-run (at most) every [`SAVED_SEARCH_DIGEST_PERIOD_SECS`](config::SAVED_SEARCH_DIGEST_PERIOD_SECS),
+//! jigs published since it was last checked are new, and jigs that were already sent for it are skipped.
+//!
+//! Jigs published shortly before a check might not have been synced to the search index yet, so each check looks
+//! [`SAVED_SEARCH_SYNC_GRACE_SECS`](config::SAVED_SEARCH_SYNC_GRACE_SECS) further back than the last one,
+//! and relies on the jigs already sent being skipped.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use chrono::Utc;
+use config::RemoteTarget;
+use sendgrid::v3::Email;
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    db::{self, saved_search::DueSavedSearch},
+    search::{self, SearchResults},
+    service::mail,
+};
+
+/// How many saved searches get claimed per query.
+const BATCH_SIZE: i64 = 10;
+
+pub struct Manager {
+    db: PgPool,
+    search: search::Client,
+    mail: mail::Client,
+    remote_target: RemoteTarget,
+}
+
+impl Manager {
+    #[must_use]
+    pub const fn new(
+        db: PgPool,
+        search: search::Client,
+        mail: mail::Client,
+        remote_target: RemoteTarget,
+    ) -> Self {
+        Self {
+            db,
+            search,
+            mail,
+            remote_target,
+        }
+    }
+
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            loop {
+                let iteration_start = Instant::now();
+
+                if let Err(e) = self
+                    .send_due()
+                    .await
+                    .context("saved search digest task errored")
+                {
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+                }
+
+                tokio::time::delay_until(
+                    (iteration_start
+                        + Duration::from_secs(config::SAVED_SEARCH_DIGEST_INTERVAL_SECS))
+                    .into(),
+                )
+                .await;
+            }
+        })
+    }
+
+    async fn send_due(&self) -> anyhow::Result<()> {
+        let cutoff =
+            Utc::now() - chrono::Duration::seconds(config::SAVED_SEARCH_DIGEST_PERIOD_SECS);
+
+        loop {
+            let due = db::saved_search::claim_due(&self.db, cutoff, BATCH_SIZE).await?;
+
+            for saved_search in &due {
+                if let Err(e) = self.send(saved_search).await {
+                    let e = e.context(format!(
+                        "failed to send digest for saved search {}",
+                        saved_search.id.0
+                    ));
+
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+
+                    // try again next time, if it can't be put back it's only checked again next period.
+                    if let Err(e) =
+                        db::saved_search::unclaim(&self.db, saved_search.id, saved_search.since)
+                            .await
+                    {
+                        log::warn!(
+                            "failed to unclaim saved search {}: {:?}",
+                            saved_search.id.0,
+                            e
+                        );
+                    }
+                }
+            }
+
+            if due.len() < BATCH_SIZE as usize {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn send(&self, saved_search: &DueSavedSearch) -> anyhow::Result<()> {
+        let query = &saved_search.query;
+
+        // only the most relevant hits are looked at, new jigs further down are left out of the digest.
+        let SearchResults { ids: hits, .. } = self
+            .search
+            .search_jig(
+                &query.q,
+                query.transliterate,
+                None,
+                Some(true),
+                &query.age_ranges,
+                &query.affiliations,
+                &query.categories,
+                &query.goals,
+                query.language.as_deref(),
+                &query.module_kinds,
+                query.author,
+                None,
+                Some(config::SAVED_SEARCH_MAX_HITS),
+            )
+            .await?;
+
+        let since =
+            saved_search.since - chrono::Duration::seconds(config::SAVED_SEARCH_SYNC_GRACE_SECS);
+
+        let jigs = db::saved_search::new_jigs(&self.db, saved_search.id, &hits, since).await?;
+
+        if jigs.is_empty() {
+            return Ok(());
+        }
+
+        let pages_url = self.remote_target.pages_url();
+
+        let list = jigs
+            .iter()
+            .map(|(id, name)| format!("{} - {}/jig/play/{}", name, pages_url, id.0))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let template = self
+            .mail
+            .saved_search_digest_template()
+            .ok()
+            .context("the saved search digest template isn't configured")?;
+
+        self.mail
+            .send_saved_search_digest(
+                template,
+                Email::new(saved_search.email.clone()),
+                saved_search.name.clone(),
+                list,
+                jigs.len(),
+                pages_url,
+            )
+            .await?;
+
+        let ids: Vec<Uuid> = jigs.iter().map(|(id, _)| id.0).collect();
+
+        db::saved_search::mark_sent(&self.db, saved_search.id, &ids).await?;
+
+        log::info!(
+            "sent a digest of {} jig(s) for saved search {}",
+            ids.len(),
+            saved_search.id.0
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+//! Publishes drafts that were scheduled with [`jig::draft::Schedule`](shared::api::endpoints::jig::draft::Schedule).
+//!
+//! Algolia picks up the published jigs on its own, since publishing marks them as needing to be synced.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use shared::domain::{firebase::MessageTarget, jig::JigId};
+use sqlx::{PgConnection, PgPool};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::{
+    db, error,
+    service::notifications::{self, MessageRequest},
+};
+
+pub struct Manager {
+    db: PgPool,
+    notifications: Option<notifications::Client>,
+}
+
+impl Manager {
+    #[must_use]
+    pub const fn new(db: PgPool, notifications: Option<notifications::Client>) -> Self {
+        Self { db, notifications }
+    }
+
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::task::spawn(async move {
+            loop {
+                let iteration_start = Instant::now();
+
+                if let Err(e) = self.publish_due().await.context("scheduler task errored") {
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+                }
+
+                tokio::time::delay_until(
+                    (iteration_start + Duration::from_secs(config::SCHEDULER_INTERVAL_SECS)).into(),
+                )
+                .await;
+            }
+        })
+    }
+
+    async fn publish_due(&self) -> anyhow::Result<()> {
+        // schedules that failed to publish this time around, they're retried next time.
+        let mut failed = Vec::new();
+
+        while let Some(live_id) = self.publish_next(&failed).await? {
+            failed.push(live_id.0);
+        }
+
+        Ok(())
+    }
+
+    /// Publishes due drafts (other than the ones in `skip`) until one fails, returning its jig,
+    /// or until there are none left, returning [`None`].
+    async fn publish_next(&self, skip: &[Uuid]) -> anyhow::Result<Option<JigId>> {
+        loop {
+            let mut txn = self.db.begin().await?;
+
+            // the schedule stays locked (so other instances of the api skip it) until it's deleted along with the publish.
+            let schedule = sqlx::query!(
+                r#"
+select live_id as "live_id: JigId", publish_at as "publish_at: DateTime<Utc>", scheduled_by
+from jig_draft_schedule
+where publish_at <= now()
+  and live_id <> all($1)
+order by publish_at
+limit 1
+for update skip locked
+"#,
+                skip
+            )
+            .fetch_optional(&mut txn)
+            .await?;
+
+            let schedule = match schedule {
+                Some(schedule) => schedule,
+                None => return Ok(None),
+            };
+
+            let live_id = schedule.live_id;
+
+            let published = match self
+                .publish(
+                    &mut txn,
+                    live_id,
+                    schedule.publish_at,
+                    schedule.scheduled_by,
+                )
+                .await
+            {
+                Ok(published) => published,
+                Err(e) => {
+                    let e = e.context(format!(
+                        "failed to publish scheduled draft of jig {}",
+                        live_id.0
+                    ));
+
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+
+                    txn.rollback().await?;
+
+                    return Ok(Some(live_id));
+                }
+            };
+
+            sqlx::query!(
+                "delete from jig_draft_schedule where live_id = $1",
+                live_id.0
+            )
+            .execute(&mut txn)
+            .await?;
+
+            txn.commit().await?;
+
+            if published {
+                log::info!("published scheduled draft of jig {}", live_id.0);
+
+                if let Err(e) = self.notify(live_id).await {
+                    log::error!("{:?}", e);
+                    sentry::integrations::anyhow::capture_anyhow(&e);
+                }
+            }
+        }
+    }
+
+    /// Publishes the draft in `txn`, returns `false` if there's nothing to publish (anymore).
+    async fn publish(
+        &self,
+        txn: &mut PgConnection,
+        live_id: JigId,
+        publish_at: DateTime<Utc>,
+        scheduled_by: Uuid,
+    ) -> anyhow::Result<bool> {
+        // the user might have lost access to the jig since scheduling it.
+        match db::jig::authz(&self.db, scheduled_by, Some(live_id)).await {
+            Ok(()) => {}
+            Err(error::Auth::Forbidden) => {
+                log::warn!(
+                    "not publishing scheduled draft of jig {}, user {} is no longer allowed to",
+                    live_id.0,
+                    scheduled_by
+                );
+
+                return Ok(false);
+            }
+            Err(error::Auth::InternalServerError(e)) => return Err(e),
+        }
+
+        let draft_id = match db::jig::get_draft(&self.db, live_id).await {
+            Ok(draft_id) => draft_id,
+
+            // the draft was published (or deleted) by hand.
+            Err(error::JigCloneDraft::ResourceNotFound) => return Ok(false),
+
+            Err(error::JigCloneDraft::InternalServerError(e)) => return Err(e),
+
+            Err(_) => anyhow::bail!("jig {} can't have its draft published", live_id.0),
+        };
+
+        db::jig::publish_draft_in(&mut *txn, live_id, draft_id, scheduled_by).await?;
+
+        // drafts go live when they're scheduled to, even if the live jig wasn't public yet.
+        sqlx::query!(
+            "update jig set publish_at = $2 where id = $1 and (publish_at is null or publish_at > $2)",
+            live_id.0,
+            publish_at
+        )
+        .execute(&mut *txn)
+        .await?;
+
+        Ok(true)
+    }
+
+    async fn notify(&self, live_id: JigId) -> anyhow::Result<()> {
+        if let Some(notifications) = &self.notifications {
+            let mut data = HashMap::new();
+            data.insert("jig_id".to_owned(), live_id.0.to_string());
+            data.insert("event".to_owned(), "published".to_owned());
+
+            let message =
+                MessageRequest::with_data(MessageTarget::Topic(live_id.0.to_string()), data);
+
+            notifications.send_message(message).await.with_context(|| {
+                format!("failed to notify that jig {} was published", live_id.0)
+            })?;
+        }
+
+        Ok(())
+    }
+}
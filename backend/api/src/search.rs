@@ -0,0 +1,291 @@
+//! Searching jigs, images and animations.
+//!
+//! Search is backed by Algolia when it's configured, and by Postgres full text search otherwise
+//! (for local development and environments without an Algolia account).
+//!
+//! Both backends take the same filters, but rank differently. Postgres also matches on less: its `search_document`s
+//! cover names and descriptions (and the module text, for jigs), where Algolia also matches on the names of
+//! categories, styles, goals and the like.
+//!
+//! Queries are [`normalize`]d here, the same way the indexed text is.
+
+use shared::{
+    domain::{
+        animation::{AnimationId, AnimationKind},
+        category::CategoryId,
+        image::{ImageId, ImageSearchFacets},
+        jig::{JigId, JigSearchFacets, JigSort, ModuleKind},
+        meta::{AffiliationId, AgeRangeId, AnimationStyleId, GoalId, ImageStyleId, TagId},
+    },
+    search::{normalize, transliterate},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+mod postgres;
+
+/// A page of search results.
+#[derive(Default)]
+pub struct SearchResults<F> {
+    /// The ids of the hits on this page.
+    pub ids: Vec<Uuid>,
+
+    /// How many pages there are.
+    pub pages: u32,
+
+    /// The total number of hits.
+    pub total_hits: u64,
+
+    /// How many hits have each filter value.
+    pub facets: F,
+}
+
+/// Normalizes the `query`, and transliterates it if asked to.
+fn prepare_query(query: &str, transliterate: bool) -> (String, Option<String>) {
+    let query = normalize(query);
+
+    let transliteration = if transliterate {
+        self::transliterate(&query)
+    } else {
+        None
+    };
+
+    (query, transliteration)
+}
+
+/// Builds a `tsquery` where every word of the (normalized) `query` has to match, and the last one only as a prefix.
+///
+/// Returns [`None`] if there are no words to match.
+pub fn prefix_tsquery(query: &str) -> Option<String> {
+    let mut words: Vec<String> = query
+        .split_whitespace()
+        .map(|word| word.chars().filter(|c| c.is_alphanumeric()).collect())
+        .filter(|word: &String| !word.is_empty())
+        .collect();
+
+    let last = words.pop()?;
+    words.push(format!("{}:*", last));
+
+    Some(words.join(" & "))
+}
+
+/// How many records each search index has.
+pub struct IndexCounts {
+    pub images: u64,
+    pub animations: u64,
+    pub jigs: u64,
+}
+
+#[derive(Clone)]
+pub enum Client {
+    Algolia(crate::algolia::Client),
+    Postgres(postgres::Client),
+}
+
+impl Client {
+    /// Uses algolia if it's configured, Postgres otherwise.
+    pub fn new(algolia: Option<crate::algolia::Client>, db: PgPool) -> Self {
+        match algolia {
+            Some(algolia) => Self::Algolia(algolia),
+            None => {
+                log::info!("algolia is disabled, falling back to postgres for search");
+                Self::Postgres(postgres::Client::new(db))
+            }
+        }
+    }
+
+    pub async fn search_image(
+        &self,
+        query: &str,
+        transliterate: bool,
+        page: Option<u32>,
+        is_premium: Option<bool>,
+        is_published: Option<bool>,
+        styles: &[ImageStyleId],
+        age_ranges: &[AgeRangeId],
+        affiliations: &[AffiliationId],
+        categories: &[CategoryId],
+        tags: &[TagId],
+    ) -> anyhow::Result<SearchResults<ImageSearchFacets>> {
+        let (query, transliteration) = prepare_query(query, transliterate);
+        let (query, transliteration) = (query.as_str(), transliteration.as_deref());
+
+        match self {
+            Self::Algolia(client) => client
+                .search_image(
+                    query,
+                    transliteration,
+                    page,
+                    is_premium,
+                    is_published,
+                    styles,
+                    age_ranges,
+                    affiliations,
+                    categories,
+                    tags,
+                )
+                .await
+                .map(Option::unwrap_or_default),
+
+            Self::Postgres(client) => {
+                client
+                    .search_image(
+                        query,
+                        transliteration,
+                        page,
+                        is_premium,
+                        is_published,
+                        styles,
+                        age_ranges,
+                        affiliations,
+                        categories,
+                        tags,
+                    )
+                    .await
+            }
+        }
+    }
+
+    pub async fn search_animation(
+        &self,
+        query: &str,
+        kind: Option<AnimationKind>,
+        page: Option<u32>,
+        is_premium: Option<bool>,
+        is_published: Option<bool>,
+        styles: &[AnimationStyleId],
+    ) -> anyhow::Result<SearchResults<()>> {
+        let query = normalize(query);
+        let query = query.as_str();
+
+        match self {
+            Self::Algolia(client) => client
+                .search_animation(query, kind, page, is_premium, is_published, styles)
+                .await
+                .map(Option::unwrap_or_default),
+
+            Self::Postgres(client) => {
+                client
+                    .search_animation(query, kind, page, is_premium, is_published, styles)
+                    .await
+            }
+        }
+    }
+
+    pub async fn search_jig(
+        &self,
+        query: &str,
+        transliterate: bool,
+        page: Option<u32>,
+        is_published: Option<bool>,
+        age_ranges: &[AgeRangeId],
+        affiliations: &[AffiliationId],
+        categories: &[CategoryId],
+        goals: &[GoalId],
+        language: Option<&str>,
+        module_kinds: &[ModuleKind],
+        author: Option<Uuid>,
+        sort: Option<JigSort>,
+        hits_per_page: Option<u16>,
+    ) -> anyhow::Result<SearchResults<JigSearchFacets>> {
+        let (query, transliteration) = prepare_query(query, transliterate);
+        let (query, transliteration) = (query.as_str(), transliteration.as_deref());
+
+        match self {
+            Self::Algolia(client) => client
+                .search_jig(
+                    query,
+                    transliteration,
+                    page,
+                    is_published,
+                    age_ranges,
+                    affiliations,
+                    categories,
+                    goals,
+                    language,
+                    module_kinds,
+                    author,
+                    sort,
+                    hits_per_page,
+                )
+                .await
+                .map(Option::unwrap_or_default),
+
+            Self::Postgres(client) => {
+                client
+                    .search_jig(
+                        query,
+                        transliteration,
+                        page,
+                        is_published,
+                        age_ranges,
+                        affiliations,
+                        categories,
+                        goals,
+                        language,
+                        module_kinds,
+                        author,
+                        sort,
+                        hits_per_page,
+                    )
+                    .await
+            }
+        }
+    }
+
+    /// Returns the ids of published jigs whose names complete the (normalized) `query`.
+    pub async fn suggest_jigs(&self, query: &str, limit: u16) -> anyhow::Result<Vec<Uuid>> {
+        match self {
+            Self::Algolia(client) => client
+                .suggest_jigs(query, limit)
+                .await
+                .map(Option::unwrap_or_default),
+
+            Self::Postgres(client) => client.suggest_jigs(query, limit).await,
+        }
+    }
+
+    /// Returns the ids of published images whose names complete the (normalized) `query`.
+    pub async fn suggest_images(&self, query: &str, limit: u16) -> anyhow::Result<Vec<Uuid>> {
+        match self {
+            Self::Algolia(client) => client
+                .suggest_images(query, limit)
+                .await
+                .map(Option::unwrap_or_default),
+
+            Self::Postgres(client) => client.suggest_images(query, limit).await,
+        }
+    }
+
+    /// Counts the records in each index, `None` when searching Postgres (there's no index to count).
+    pub async fn index_counts(&self) -> anyhow::Result<Option<IndexCounts>> {
+        match self {
+            Self::Algolia(client) => client.index_counts().await.map(Some),
+            Self::Postgres(_) => Ok(None),
+        }
+    }
+
+    pub async fn delete_image(&self, id: ImageId) {
+        match self {
+            Self::Algolia(client) => client.delete_image(id).await,
+            // the search document is deleted along with the image.
+            Self::Postgres(_) => {}
+        }
+    }
+
+    pub async fn delete_animation(&self, id: AnimationId) {
+        match self {
+            Self::Algolia(client) => client.delete_animation(id).await,
+            // the search document is deleted along with the animation.
+            Self::Postgres(_) => {}
+        }
+    }
+
+    pub async fn delete_jig(&self, id: JigId) {
+        match self {
+            Self::Algolia(client) => client.delete_jig(id).await,
+            // the search document is deleted along with the jig.
+            Self::Postgres(_) => {}
+        }
+    }
+}
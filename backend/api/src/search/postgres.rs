@@ -0,0 +1,317 @@
+//! Full text search over the `search_document` columns of `jig`, `image_metadata` and `animation_metadata`.
+//!
+//! Mirrors the Algolia filters: every id filter has to match (rather than any of them).
+//!
+//! Only the requested page of hits is fetched, the total and the facets are counted over every hit in SQL.
+//!
+//! A transliteration of the query is `or`ed with it, matching either one is enough.
+
+use std::{collections::HashMap, hash::Hash};
+
+use shared::domain::{
+    animation::AnimationKind,
+    category::CategoryId,
+    image::ImageSearchFacets,
+    jig::{JigSearchFacets, JigSort, ModuleKind},
+    meta::{AffiliationId, AgeRangeId, AnimationStyleId, GoalId, ImageStyleId, TagId},
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::SearchResults;
+
+/// Algolia's default page size.
+const DEFAULT_HITS_PER_PAGE: u16 = 20;
+
+#[derive(Clone)]
+pub struct Client {
+    db: PgPool,
+}
+
+fn ids<T: Into<Uuid> + Copy>(ids: &[T]) -> Vec<Uuid> {
+    ids.iter().copied().map(Into::into).collect()
+}
+
+fn counts<T: Hash + Eq>(rows: Vec<(Uuid, i64)>, f: fn(Uuid) -> T) -> HashMap<T, u64> {
+    rows.into_iter()
+        .map(|(id, count)| (f(id), count as u64))
+        .collect()
+}
+
+/// Returns the `limit` and `offset` of the page.
+fn page_bounds(page: Option<u32>, hits_per_page: u16) -> (i64, i64) {
+    let hits_per_page = i64::from(hits_per_page);
+
+    (hits_per_page, i64::from(page.unwrap_or(0)) * hits_per_page)
+}
+
+fn results<F>(ids: Vec<Uuid>, total_hits: i64, hits_per_page: u16, facets: F) -> SearchResults<F> {
+    let total_hits = total_hits as u64;
+    let hits_per_page = u64::from(hits_per_page);
+
+    SearchResults {
+        ids,
+        pages: ((total_hits + hits_per_page - 1) / hits_per_page) as u32,
+        total_hits,
+        facets,
+    }
+}
+
+impl Client {
+    pub const fn new(db: PgPool) -> Self {
+        Self { db }
+    }
+
+    pub async fn search_image(
+        &self,
+        query: &str,
+        transliteration: Option<&str>,
+        page: Option<u32>,
+        is_premium: Option<bool>,
+        is_published: Option<bool>,
+        styles: &[ImageStyleId],
+        age_ranges: &[AgeRangeId],
+        affiliations: &[AffiliationId],
+        categories: &[CategoryId],
+        tags: &[TagId],
+    ) -> anyhow::Result<SearchResults<ImageSearchFacets>> {
+        let (limit, offset) = page_bounds(page, DEFAULT_HITS_PER_PAGE);
+
+        let res = sqlx::query!(
+            r#"
+with hit as (
+    select id, ts_rank(search_document, q.query) as rank, coalesce(updated_at, created_at) as edited_at
+    from image_metadata,
+         (select websearch_to_tsquery('simple', $1) || websearch_to_tsquery('simple', coalesce($9::text, '')) as query) as q
+    where ($1 = '' or search_document @@ q.query)
+      and ($2::bool is null or is_premium = $2)
+      and ($3::bool is null or (publish_at < now() is true) = $3)
+      and $4::uuid[] <@ array(select style_id from image_style where image_id = image_metadata.id)
+      and $5::uuid[] <@ array(select age_range_id from image_age_range where image_id = image_metadata.id)
+      and $6::uuid[] <@ array(select affiliation_id from image_affiliation where image_id = image_metadata.id)
+      and $7::uuid[] <@ array(select category_id from image_category where image_id = image_metadata.id)
+      and $8::uuid[] <@ array(select tag_id from image_tag_join where image_id = image_metadata.id)
+)
+select array(select id from hit order by rank desc, edited_at desc, id limit $10 offset $11) as "ids!",
+       (select count(*) from hit)                                                              as "total!",
+       array(select row (style_id, count(*))
+             from image_style inner join hit on hit.id = image_id group by style_id)         as "styles!: Vec<(Uuid, i64)>",
+       array(select row (age_range_id, count(*))
+             from image_age_range inner join hit on hit.id = image_id group by age_range_id) as "age_ranges!: Vec<(Uuid, i64)>",
+       array(select row (affiliation_id, count(*))
+             from image_affiliation inner join hit on hit.id = image_id group by affiliation_id) as "affiliations!: Vec<(Uuid, i64)>",
+       array(select row (category_id, count(*))
+             from image_category inner join hit on hit.id = image_id group by category_id)   as "categories!: Vec<(Uuid, i64)>",
+       array(select row (tag_id, count(*))
+             from image_tag_join inner join hit on hit.id = image_id group by tag_id)        as "tags!: Vec<(Uuid, i64)>"
+"#,
+            query,
+            is_premium,
+            is_published,
+            &ids(styles),
+            &ids(age_ranges),
+            &ids(affiliations),
+            &ids(categories),
+            &ids(tags),
+            transliteration,
+            limit,
+            offset,
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        let facets = ImageSearchFacets {
+            styles: counts(res.styles, ImageStyleId),
+            age_ranges: counts(res.age_ranges, AgeRangeId),
+            affiliations: counts(res.affiliations, AffiliationId),
+            categories: counts(res.categories, CategoryId),
+            tags: counts(res.tags, TagId),
+        };
+
+        Ok(results(res.ids, res.total, DEFAULT_HITS_PER_PAGE, facets))
+    }
+
+    pub async fn search_animation(
+        &self,
+        query: &str,
+        kind: Option<AnimationKind>,
+        page: Option<u32>,
+        is_premium: Option<bool>,
+        is_published: Option<bool>,
+        styles: &[AnimationStyleId],
+    ) -> anyhow::Result<SearchResults<()>> {
+        let (limit, offset) = page_bounds(page, DEFAULT_HITS_PER_PAGE);
+
+        let res = sqlx::query!(
+            r#"
+with hit as (
+    select id,
+           ts_rank(search_document, websearch_to_tsquery('simple', $1)) as rank,
+           coalesce(updated_at, created_at)                            as edited_at
+    from animation_metadata
+    where ($1 = '' or search_document @@ websearch_to_tsquery('simple', $1))
+      and ($2::smallint is null or kind = $2)
+      and ($3::bool is null or is_premium = $3)
+      and ($4::bool is null or (publish_at < now() is true) = $4)
+      and $5::uuid[] <@ array(select style_id from animation_style where animation_id = animation_metadata.id)
+)
+select array(select id from hit order by rank desc, edited_at desc, id limit $6 offset $7) as "ids!",
+       (select count(*) from hit)                                                            as "total!"
+"#,
+            query,
+            kind.map(|it| it as i16),
+            is_premium,
+            is_published,
+            &ids(styles),
+            limit,
+            offset,
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        Ok(results(res.ids, res.total, DEFAULT_HITS_PER_PAGE, ()))
+    }
+
+    pub async fn search_jig(
+        &self,
+        query: &str,
+        transliteration: Option<&str>,
+        page: Option<u32>,
+        is_published: Option<bool>,
+        age_ranges: &[AgeRangeId],
+        affiliations: &[AffiliationId],
+        categories: &[CategoryId],
+        goals: &[GoalId],
+        language: Option<&str>,
+        module_kinds: &[ModuleKind],
+        author: Option<Uuid>,
+        sort: Option<JigSort>,
+        hits_per_page: Option<u16>,
+    ) -> anyhow::Result<SearchResults<JigSearchFacets>> {
+        let hits_per_page = hits_per_page.unwrap_or(DEFAULT_HITS_PER_PAGE);
+        let (limit, offset) = page_bounds(page, hits_per_page);
+
+        let res = sqlx::query!(
+            r#"
+with hit as (
+    select id,
+           case $11::smallint
+               when 0 then extract(epoch from updated_at)
+               when 1 then coalesce((select plays from jig_metrics where jig_id = jig.id), 0)
+               when 2 then (select count(*) from jig_like where jig_id = jig.id)
+               when 3 then (select count(*) from jig_view where jig_id = jig.id)
+               end                               as sort_key,
+           ts_rank(search_document, q.query) as rank,
+           coalesce(updated_at, created_at)  as edited_at
+    from jig,
+         (select websearch_to_tsquery('simple', $1) || websearch_to_tsquery('simple', coalesce($10::text, '')) as query) as q
+    where ($1 = '' or search_document @@ q.query)
+      and ($2::bool is null or (publish_at < now() is true) = $2)
+      and ($3::uuid is null or author_id = $3)
+      and $4::uuid[] <@ array(select age_range_id from jig_age_range where jig_id = jig.id)
+      and $5::uuid[] <@ array(select affiliation_id from jig_affiliation where jig_id = jig.id)
+      and $6::uuid[] <@ array(select category_id from jig_category where jig_id = jig.id)
+      and $7::uuid[] <@ array(select goal_id from jig_goal where jig_id = jig.id)
+      and ($8::text is null or language = $8)
+      and $9::smallint[] <@ array(select kind from jig_module where jig_id = jig.id)
+)
+select array(select id
+             from hit
+             order by sort_key desc nulls last, rank desc, edited_at desc, id
+             limit $12 offset $13)                                                           as "ids!",
+       (select count(*) from hit)                                                            as "total!",
+       array(select row (age_range_id, count(*))
+             from jig_age_range inner join hit on hit.id = jig_id group by age_range_id)     as "age_ranges!: Vec<(Uuid, i64)>",
+       array(select row (affiliation_id, count(*))
+             from jig_affiliation inner join hit on hit.id = jig_id group by affiliation_id) as "affiliations!: Vec<(Uuid, i64)>",
+       array(select row (category_id, count(*))
+             from jig_category inner join hit on hit.id = jig_id group by category_id)       as "categories!: Vec<(Uuid, i64)>",
+       array(select row (goal_id, count(*))
+             from jig_goal inner join hit on hit.id = jig_id group by goal_id)               as "goals!: Vec<(Uuid, i64)>"
+"#,
+            query,
+            is_published,
+            author,
+            &ids(age_ranges),
+            &ids(affiliations),
+            &ids(categories),
+            &ids(goals),
+            language,
+            &module_kinds
+                .iter()
+                .map(|&kind| kind as i16)
+                .collect::<Vec<_>>(),
+            transliteration,
+            sort.map(|sort| sort as i16),
+            limit,
+            offset,
+        )
+        .fetch_one(&self.db)
+        .await?;
+
+        let facets = JigSearchFacets {
+            age_ranges: counts(res.age_ranges, AgeRangeId),
+            affiliations: counts(res.affiliations, AffiliationId),
+            categories: counts(res.categories, CategoryId),
+            goals: counts(res.goals, GoalId),
+        };
+
+        Ok(results(res.ids, res.total, hits_per_page, facets))
+    }
+
+    pub async fn suggest_jigs(&self, query: &str, limit: u16) -> anyhow::Result<Vec<Uuid>> {
+        let tsquery = match super::prefix_tsquery(query) {
+            Some(tsquery) => tsquery,
+            None => return Ok(Vec::new()),
+        };
+
+        let ids = sqlx::query!(
+            r#"
+select id
+from jig
+where search_document @@ to_tsquery('simple', $1)
+  and publish_at < now()
+order by ts_rank(search_document, to_tsquery('simple', $1)) desc,
+         coalesce(updated_at, created_at) desc
+limit $2
+"#,
+            tsquery,
+            i64::from(limit),
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect();
+
+        Ok(ids)
+    }
+
+    pub async fn suggest_images(&self, query: &str, limit: u16) -> anyhow::Result<Vec<Uuid>> {
+        let tsquery = match super::prefix_tsquery(query) {
+            Some(tsquery) => tsquery,
+            None => return Ok(Vec::new()),
+        };
+
+        let ids = sqlx::query!(
+            r#"
+select id
+from image_metadata
+where search_document @@ to_tsquery('simple', $1)
+  and publish_at < now()
+order by ts_rank(search_document, to_tsquery('simple', $1)) desc,
+         coalesce(updated_at, created_at) desc
+limit $2
+"#,
+            tsquery,
+            i64::from(limit),
+        )
+        .fetch_all(&self.db)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect();
+
+        Ok(ids)
+    }
+}
@@ -24,17 +24,22 @@ impl Service for crate::algolia::Client {
     const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::Algolia;
 }
 
+impl Service for crate::search::Client {
+    // never disabled, since it falls back to postgres.
+    const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::Algolia;
+}
+
 impl Service for crate::algolia::SearchKeyStore {
     // todo: this should have a different error?
     const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::Algolia;
 }
 
-impl Service for crate::s3::Client {
+impl Service for dyn crate::storage::MediaStorage {
     const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::S3;
 }
 
-impl Service for storage::Client {
-    const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::GoogleCloudStorage;
+impl Service for crate::storage::CloudStorage {
+    const DISABLED_ERROR: error::ServiceKind = error::ServiceKind::S3;
 }
 
 impl Service for crate::service::event_arc::Client {
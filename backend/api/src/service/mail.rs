@@ -6,6 +6,7 @@ use crate::error;
 
 use super::Service;
 
+#[derive(Clone)]
 pub struct Client {
     client: Sender,
 
@@ -14,6 +15,8 @@ pub struct Client {
     signup_verify_template: Option<String>,
 
     password_reset_template: Option<String>,
+
+    saved_search_digest_template: Option<String>,
 }
 
 impl Client {
@@ -23,6 +26,7 @@ impl Client {
             sender_email: Email::new(settings.sender_email),
             signup_verify_template: settings.signup_verify_template,
             password_reset_template: settings.password_reset_template,
+            saved_search_digest_template: settings.saved_search_digest_template,
         }
     }
 
@@ -62,6 +66,31 @@ impl Client {
         Ok(())
     }
 
+    /// Sends a digest of new jigs matching a saved search.
+    pub async fn send_saved_search_digest(
+        &self,
+        template: SavedSearchDigestTemplate<'_>,
+        to: Email,
+        search_name: String,
+        jigs: String,
+        jig_count: usize,
+        link: String,
+    ) -> anyhow::Result<()> {
+        let mut template_data = SGMap::new();
+        template_data.insert("search_name".to_string(), search_name);
+        template_data.insert("jigs".to_string(), jigs);
+        template_data.insert("jig_count".to_string(), jig_count.to_string());
+        template_data.insert("url".to_string(), link);
+
+        let message = Message::new(self.sender_email.clone())
+            .set_template_id(&template.0)
+            .add_personalization(Personalization::new(to).add_dynamic_template_data(template_data));
+
+        self.client.send(&message).await?;
+
+        Ok(())
+    }
+
     pub fn signup_verify_template(&self) -> Result<SignupVerifyTemplate<'_>, error::ServiceKind> {
         // todo: make the error more specific?
         self.signup_verify_template
@@ -77,6 +106,15 @@ impl Client {
             .map(PasswordResetTemplate)
             .ok_or(error::ServiceKind::Mail)
     }
+
+    pub fn saved_search_digest_template(
+        &self,
+    ) -> Result<SavedSearchDigestTemplate<'_>, error::ServiceKind> {
+        self.saved_search_digest_template
+            .as_deref()
+            .map(SavedSearchDigestTemplate)
+            .ok_or(error::ServiceKind::Mail)
+    }
 }
 
 impl Service for Client {
@@ -90,3 +128,7 @@ pub struct SignupVerifyTemplate<'a>(&'a str);
 #[repr(transparent)]
 #[derive(Debug, Clone, Copy)]
 pub struct PasswordResetTemplate<'a>(&'a str);
+
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy)]
+pub struct SavedSearchDigestTemplate<'a>(&'a str);
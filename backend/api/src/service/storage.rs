@@ -5,7 +5,7 @@ use anyhow::Context;
 use core::settings::GoogleCloudStorageSettings;
 use http::StatusCode;
 use reqwest::{self, header};
-use shared::media::{self, FileKind, MediaLibrary, PngImageFile};
+use shared::media::{self, FileKind, MediaLibrary};
 use uuid::Uuid;
 
 pub struct Client {
@@ -120,14 +120,6 @@ impl Client {
         )
         .await
     }
-
-    pub fn file_size_limit(&self, file_kind: &FileKind) -> Option<usize> {
-        match file_kind {
-            FileKind::AnimationGif => Some(config::ANIMATION_BODY_SIZE_LIMIT),
-            FileKind::ImagePng(PngImageFile::Original) => Some(config::IMAGE_BODY_SIZE_LIMIT),
-            _ => unimplemented!("File type size limit undefined!"),
-        }
-    }
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -1,15 +1,21 @@
 //! TODO: profile this. load test?
 
 use crate::service::notifications::MessageRequest;
-use crate::{error, service};
+use crate::{error, service, storage::MediaStorage};
 use shared::{
     domain::{animation::AnimationKind, firebase::MessageTarget, image::ImageKind},
-    media::{FileKind, MediaLibrary, PngImageFile},
+    media::{FileKind, MediaLibrary, PngImageFile, SpritesheetFile},
 };
 use sqlx::PgPool;
+use std::sync::Arc;
+use tokio::task::JoinHandle;
 use uuid::Uuid;
 
-pub async fn process_image(db: &PgPool, s3: &crate::s3::Client, id: Uuid) -> anyhow::Result<bool> {
+pub async fn process_image(
+    db: &PgPool,
+    storage: &dyn MediaStorage,
+    id: Uuid,
+) -> anyhow::Result<bool> {
     let mut txn = db.begin().await?;
 
     let kind = sqlx::query!(
@@ -36,7 +42,7 @@ skip locked
         }
     };
 
-    let file = s3
+    let file = storage
         .download_media_for_processing(
             MediaLibrary::Global,
             id,
@@ -58,15 +64,20 @@ skip locked
     };
 
     let processed = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
-        let original = image::load_from_memory(&file).map_err(|_| error::Upload::InvalidMedia)?;
-        Ok(crate::image_ops::regenerate_images(&original, kind)?)
+        let original = crate::image_ops::load_image(&file, kind)?;
+        let hash = crate::image_ops::perceptual_hash(&original);
+        Ok((
+            crate::image_ops::generate_images(&file, &original, kind)?,
+            hash,
+        ))
     })
     .await
     .unwrap();
 
-    let (resized, thumbnail) = match processed {
+    let ((original, resized, thumbnail), hash) = match processed {
         Ok(it) => it,
-        Err(error::Upload::InvalidMedia) => {
+        Err(error::Upload::InternalServerError(e)) => return Err(e),
+        Err(_) => {
             sqlx::query!("update image_upload set processed_at = now(), processing_result = false where image_id = $1", id)
                 .execute(&mut txn)
                 .await?;
@@ -74,14 +85,13 @@ skip locked
             txn.commit().await?;
             return Ok(true);
         }
-        Err(error::Upload::InternalServerError(e)) => return Err(e),
-        Err(_) => unreachable!(),
     };
 
-    s3.upload_png_images_copy_original(MediaLibrary::Global, id, resized, thumbnail)
+    storage
+        .upload_images(MediaLibrary::Global, id, original, resized, thumbnail)
         .await?;
 
-    sqlx::query!("update image_upload set processed_at = now(), processing_result = true where image_id = $1", id)
+    sqlx::query!("update image_upload set processed_at = now(), processing_result = true, perceptual_hash = $2 where image_id = $1", id, hash)
         .execute(&mut txn)
         .await?;
 
@@ -92,7 +102,7 @@ skip locked
 
 pub async fn process_user_image(
     db: &PgPool,
-    s3: &crate::s3::Client,
+    storage: &dyn MediaStorage,
     id: Uuid,
 ) -> anyhow::Result<bool> {
     let mut txn = db.begin().await?;
@@ -119,9 +129,9 @@ skip locked
         return Ok(false);
     }
 
-    let file = s3
+    let file = storage
         .download_media_for_processing(
-            MediaLibrary::Global,
+            MediaLibrary::User,
             id,
             FileKind::ImagePng(PngImageFile::Original),
         )
@@ -141,18 +151,20 @@ skip locked
     };
 
     let processed = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
-        let original = image::load_from_memory(&file).map_err(|_| error::Upload::InvalidMedia)?;
-        Ok(crate::image_ops::regenerate_images(
-            &original,
-            ImageKind::Sticker,
-        )?)
+        let original = crate::image_ops::load_image(&file, ImageKind::Sticker)?;
+        let hash = crate::image_ops::perceptual_hash(&original);
+        Ok((
+            crate::image_ops::generate_images(&file, &original, ImageKind::Sticker)?,
+            hash,
+        ))
     })
     .await
     .unwrap();
 
-    let (resized, thumbnail) = match processed {
+    let ((original, resized, thumbnail), hash) = match processed {
         Ok(it) => it,
-        Err(error::Upload::InvalidMedia) => {
+        Err(error::Upload::InternalServerError(e)) => return Err(e),
+        Err(_) => {
             sqlx::query!("update user_image_upload set processed_at = now(), processing_result = false where image_id = $1", id)
                 .execute(&mut txn)
                 .await?;
@@ -160,25 +172,192 @@ skip locked
             txn.commit().await?;
             return Ok(true);
         }
-        Err(error::Upload::InternalServerError(e)) => return Err(e),
-        Err(_) => unreachable!(),
     };
 
-    s3.upload_png_images_copy_original(MediaLibrary::Global, id, resized, thumbnail)
+    storage
+        .upload_images(MediaLibrary::User, id, original, resized, thumbnail)
         .await?;
 
-    sqlx::query!("update user_image_upload set processed_at = now(), processing_result = true where image_id = $1", id).execute(&mut txn).await?;
+    sqlx::query!("update user_image_upload set processed_at = now(), processing_result = true, perceptual_hash = $2 where image_id = $1", id, hash).execute(&mut txn).await?;
 
     txn.commit().await?;
 
     Ok(true)
 }
 
+/// How many images the perceptual hash backfill hashes at a time.
+const HASH_BACKFILL_BATCH_SIZE: i64 = 100;
+
+/// Hashes the images processed before they had perceptual hashes, from their (processed) originals.
+///
+/// Images that can't be hashed are skipped (and logged) until the next time it runs.
+#[must_use]
+pub fn spawn_perceptual_hash_backfill(
+    db: PgPool,
+    storage: Arc<dyn MediaStorage>,
+) -> JoinHandle<()> {
+    tokio::task::spawn(async move {
+        for &library in &[MediaLibrary::Global, MediaLibrary::User] {
+            let mut after = Uuid::nil();
+
+            loop {
+                match backfill_perceptual_hashes(&db, &*storage, library, after).await {
+                    Ok(Some(last)) => after = last,
+                    Ok(None) => break,
+                    Err(e) => {
+                        let e = e.context("perceptual hash backfill errored");
+                        log::error!("{:?}", e);
+                        sentry::integrations::anyhow::capture_anyhow(&e);
+                        return;
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Hashes a batch of the images in `library` that don't have a perceptual hash yet (with ids after `after`),
+/// returns the id of the last one, or `None` if there weren't any left.
+async fn backfill_perceptual_hashes(
+    db: &PgPool,
+    storage: &dyn MediaStorage,
+    library: MediaLibrary,
+    after: Uuid,
+) -> anyhow::Result<Option<Uuid>> {
+    let ids: Vec<Uuid> = match library {
+        MediaLibrary::Global => sqlx::query!(
+            r#"
+select image_id as "id"
+from image_upload
+where perceptual_hash is null and processing_result is true and image_id > $1
+order by image_id
+limit $2
+"#,
+            after,
+            HASH_BACKFILL_BATCH_SIZE
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect(),
+
+        MediaLibrary::User => sqlx::query!(
+            r#"
+select image_id as "id"
+from user_image_upload
+where perceptual_hash is null and processing_result is true and image_id > $1
+order by image_id
+limit $2
+"#,
+            after,
+            HASH_BACKFILL_BATCH_SIZE
+        )
+        .fetch_all(db)
+        .await?
+        .into_iter()
+        .map(|it| it.id)
+        .collect(),
+
+        // web library images aren't hashed.
+        MediaLibrary::Web => return Ok(None),
+    };
+
+    for &id in &ids {
+        let original = storage.download_image_original(library, id).await?;
+
+        let original = match original {
+            Some(it) => it,
+            None => {
+                log::warn!("Image {} has no original to hash", id);
+                continue;
+            }
+        };
+
+        let hash = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
+            // processed originals are never SVGs, so the kind doesn't matter.
+            let original = crate::image_ops::load_image(&original, ImageKind::Sticker)?;
+            Ok(crate::image_ops::perceptual_hash(&original))
+        })
+        .await
+        .unwrap();
+
+        let hash = match hash {
+            Ok(it) => it,
+            Err(error::Upload::InternalServerError(e)) => return Err(e),
+            Err(_) => {
+                log::warn!("Image {}'s original couldn't be hashed", id);
+                continue;
+            }
+        };
+
+        // an image that was reprocessed in the meantime already has (a newer) one.
+        match library {
+            MediaLibrary::Global => {
+                sqlx::query!(
+                    "update image_upload set perceptual_hash = $2 where image_id = $1 and perceptual_hash is null",
+                    id,
+                    hash
+                )
+                .execute(db)
+                .await?;
+            }
+
+            MediaLibrary::User => {
+                sqlx::query!(
+                    "update user_image_upload set perceptual_hash = $2 where image_id = $1 and perceptual_hash is null",
+                    id,
+                    hash
+                )
+                .execute(db)
+                .await?;
+            }
+
+            MediaLibrary::Web => {}
+        }
+    }
+
+    Ok(ids.last().copied())
+}
+
+/// What processing an animation resulted in.
+enum ProcessedAnimation {
+    /// The uploaded file(s) can be used as is.
+    Valid,
+
+    /// A GIF was converted to a spritesheet (and frames).
+    Converted(Vec<u8>, Vec<u8>),
+}
+
 pub async fn process_animation(
     db: &PgPool,
-    s3: &crate::s3::Client,
+    storage: &dyn MediaStorage,
     id: Uuid,
+    file_kind: FileKind,
 ) -> anyhow::Result<bool> {
+    let download =
+        |file_kind| storage.download_media_for_processing(MediaLibrary::Global, id, file_kind);
+
+    // the sheet and its frames are uploaded separately but share a row, so they're only processed once both are there.
+    // this is checked before locking the row, otherwise the second file could be skipped while the first one holds it.
+    let spritesheet = match file_kind {
+        FileKind::Spritesheet(_) => {
+            let (sheet, frames) = futures::future::try_join(
+                download(FileKind::Spritesheet(SpritesheetFile::Sheet)),
+                download(FileKind::Spritesheet(SpritesheetFile::Frames)),
+            )
+            .await?;
+
+            match (sheet, frames) {
+                (Some(sheet), Some(frames)) => Some((sheet, frames)),
+                (Some(_), None) | (None, Some(_)) => return Ok(false),
+                (None, None) => None,
+            }
+        }
+
+        _ => None,
+    };
+
     let mut txn = db.begin().await?;
 
     let row = sqlx::query!(
@@ -204,16 +383,49 @@ skip locked
         }
     };
 
-    if !matches!(row.kind, AnimationKind::Gif) {
-        return Err(anyhow::anyhow!("Unimplemented Animation Kind: {:?}", row.kind).into());
-    }
-
-    let file = s3
-        .download_media_for_processing(MediaLibrary::Global, id, FileKind::AnimationGif)
-        .await?;
+    let res = match (row.kind, file_kind) {
+        (AnimationKind::Gif, FileKind::AnimationGif) => match download(file_kind).await? {
+            Some(file) => Some(
+                tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
+                    let _ = image::load_from_memory_with_format(&file, image::ImageFormat::Gif)
+                        .or(Err(error::Upload::InvalidMedia))?;
+                    Ok(ProcessedAnimation::Valid)
+                })
+                .await
+                .unwrap(),
+            ),
+            None => None,
+        },
+
+        (AnimationKind::Spritesheet, FileKind::AnimationGif) => match download(file_kind).await? {
+            Some(file) => Some(
+                tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
+                    let (sheet, frames) = crate::image_ops::gif_to_spritesheet(&file)?;
+                    Ok(ProcessedAnimation::Converted(sheet, frames))
+                })
+                .await
+                .unwrap(),
+            ),
+            None => None,
+        },
+
+        (AnimationKind::Spritesheet, FileKind::Spritesheet(_)) => match spritesheet {
+            Some((sheet, frames)) => Some(
+                tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
+                    crate::image_ops::validate_spritesheet(&sheet, &frames)?;
+                    Ok(ProcessedAnimation::Valid)
+                })
+                .await
+                .unwrap(),
+            ),
+            None => None,
+        },
+
+        (_, _) => Some(Err(error::Upload::InvalidMedia)),
+    };
 
-    let file = match file {
-        Some(it) => it,
+    let res = match res {
+        Some(res) => res,
         None => {
             sqlx::query!("update global_animation_upload set processed_at = now(), processing_result = false where animation_id = $1", id)
                 .execute(&mut txn)
@@ -225,17 +437,27 @@ skip locked
         }
     };
 
-    let res = tokio::task::spawn_blocking(move || -> Result<_, error::Upload> {
-        let _ = image::load_from_memory_with_format(&file, image::ImageFormat::Gif)
-            .or(Err(error::Upload::InvalidMedia))?;
-        Ok(())
-    })
-    .await
-    .unwrap();
-
     match res {
-        Ok(()) => {}
-        Err(error::Upload::InvalidMedia) => {
+        Ok(ProcessedAnimation::Valid) => match file_kind {
+            FileKind::Spritesheet(_) => {
+                storage
+                    .copy_processed_spritesheet(MediaLibrary::Global, id)
+                    .await?
+            }
+            _ => {
+                storage
+                    .copy_processed_file(MediaLibrary::Global, id, file_kind)
+                    .await?
+            }
+        },
+        Ok(ProcessedAnimation::Converted(sheet, frames)) => {
+            storage
+                .upload_spritesheet(MediaLibrary::Global, id, sheet, frames)
+                .await?
+        }
+        Err(error::Upload::InternalServerError(e)) => return Err(e),
+        // anything else means the upload can't be used.
+        Err(_) => {
             sqlx::query!("update global_animation_upload set processed_at = now(), processing_result = false where animation_id = $1", id)
                 .execute(&mut txn)
                 .await?;
@@ -243,13 +465,8 @@ skip locked
             txn.commit().await?;
             return Ok(true);
         }
-        Err(error::Upload::InternalServerError(e)) => return Err(e),
-        Err(_) => unreachable!(),
     };
 
-    s3.copy_processed_file(MediaLibrary::Global, id, FileKind::AnimationGif)
-        .await?;
-
     sqlx::query!("update global_animation_upload set processed_at = now(), processing_result = true where animation_id = $1", id).execute(&mut txn).await?;
 
     txn.commit().await?;
@@ -257,6 +474,32 @@ skip locked
     Ok(true)
 }
 
+/// Processes an uploaded file.
+///
+/// Returns `None` if `file_kind` files aren't uploaded to `library` for processing.
+pub async fn process(
+    db: &PgPool,
+    storage: &dyn MediaStorage,
+    library: MediaLibrary,
+    id: Uuid,
+    file_kind: FileKind,
+) -> anyhow::Result<Option<bool>> {
+    let res = match (file_kind, library) {
+        (FileKind::ImagePng(PngImageFile::Original), MediaLibrary::Global) => {
+            process_image(db, storage, id).await?
+        }
+        (FileKind::ImagePng(PngImageFile::Original), MediaLibrary::User) => {
+            process_user_image(db, storage, id).await?
+        }
+        (FileKind::AnimationGif, _) | (FileKind::Spritesheet(_), _) => {
+            process_animation(db, storage, id, file_kind).await?
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(res))
+}
+
 pub async fn finalize_upload(
     fcm: &service::notifications::Client,
     library: &MediaLibrary,
@@ -0,0 +1,259 @@
+//! Where media files are stored.
+//!
+//! Deployed, media is stored in S3 (and uploaded to Google Cloud Storage), see [`CloudStorage`].
+//! Locally, it can instead be stored in a directory, see [`LocalStorage`].
+
+use std::sync::Arc;
+
+use core::settings::SettingsManager;
+use futures::future::BoxFuture;
+use shared::media::{FileKind, ImageFileFormat, MediaLibrary, PngImageFile, SpritesheetFile};
+use uuid::Uuid;
+
+use crate::{
+    error,
+    image_ops::{ImageFiles, OriginalFile},
+    s3, service,
+};
+
+mod cloud;
+mod local;
+
+pub use cloud::CloudStorage;
+pub use local::LocalStorage;
+
+/// A place to store media files in.
+///
+/// Files are stored under their [`media_key`](shared::media::media_key),
+/// in one place for finished media, and in another for uploads that haven't been processed yet.
+pub trait MediaStorage: Send + Sync {
+    fn upload_media(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>>;
+
+    fn upload_media_for_processing(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>>;
+
+    /// Returns `None` if the file doesn't exist.
+    fn download_media_for_processing(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>>;
+
+    /// Returns `None` if the file doesn't exist.
+    fn download_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>>;
+
+    /// Copies a file that passed processing as is to the finished media.
+    fn copy_processed_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>>;
+
+    /// note: does nothing if the file doesn't exist.
+    fn delete_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>>;
+
+    /// Returns a url that the client can upload a file (of `upload_content_length` bytes) to for processing.
+    fn get_url_for_resumable_upload_for_processing(
+        &self,
+        upload_content_length: usize,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, Result<String, error::Storage>>;
+
+    /// Adds any routes needed to serve or accept files. Must be called before `wrap_api`.
+    fn configure(&self, _cfg: &mut actix_web::web::ServiceConfig) {}
+}
+
+impl<'a> dyn MediaStorage + 'a {
+    pub async fn upload_images(
+        &self,
+        library: MediaLibrary,
+        image: Uuid,
+        original: OriginalFile,
+        resized: ImageFiles,
+        thumbnail: ImageFiles,
+    ) -> anyhow::Result<()> {
+        futures::future::try_join(
+            self.upload_media(original.data, library, image, original.file_kind),
+            self.upload_resized_images(library, image, resized, thumbnail),
+        )
+        .await
+        .map(drop)
+    }
+
+    /// Uploads every format of the resized image and thumbnail.
+    pub async fn upload_resized_images(
+        &self,
+        library: MediaLibrary,
+        image: Uuid,
+        resized: ImageFiles,
+        thumbnail: ImageFiles,
+    ) -> anyhow::Result<()> {
+        let uploads = vec![
+            (resized, PngImageFile::Resized),
+            (thumbnail, PngImageFile::Thumbnail),
+        ]
+        .into_iter()
+        .flat_map(|(files, file)| {
+            vec![
+                (files.png, ImageFileFormat::Png.file_kind(file)),
+                (files.webp, ImageFileFormat::Webp.file_kind(file)),
+                (files.avif, ImageFileFormat::Avif.file_kind(file)),
+            ]
+        })
+        .map(|(data, file_kind)| self.upload_media(data, library, image, file_kind));
+
+        futures::future::try_join_all(uploads).await?;
+
+        Ok(())
+    }
+
+    /// Deletes every file of an image.
+    pub async fn delete_image(&self, library: MediaLibrary, image: Uuid) {
+        futures::future::join_all(
+            FileKind::IMAGE_FILES
+                .iter()
+                .map(|&file_kind| self.delete_media(library, file_kind, image)),
+        )
+        .await;
+    }
+
+    /// Downloads the original of a processed image, whichever of [`FileKind::ORIGINAL_IMAGE_FILES`] it's stored as.
+    ///
+    /// Returns `None` if the image doesn't have one.
+    pub async fn download_image_original(
+        &self,
+        library: MediaLibrary,
+        image: Uuid,
+    ) -> anyhow::Result<Option<Vec<u8>>> {
+        for &file_kind in FileKind::ORIGINAL_IMAGE_FILES {
+            if let Some(data) = self.download_media_file(library, image, file_kind).await? {
+                return Ok(Some(data));
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub async fn upload_spritesheet(
+        &self,
+        library: MediaLibrary,
+        animation: Uuid,
+        sheet: Vec<u8>,
+        frames: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let upload =
+            |data, file| self.upload_media(data, library, animation, FileKind::Spritesheet(file));
+
+        let sheet = upload(sheet, SpritesheetFile::Sheet);
+        let frames = upload(frames, SpritesheetFile::Frames);
+
+        futures::future::try_join(sheet, frames).await?;
+
+        Ok(())
+    }
+
+    pub async fn copy_processed_spritesheet(
+        &self,
+        library: MediaLibrary,
+        animation: Uuid,
+    ) -> anyhow::Result<()> {
+        let copy = |file| self.copy_processed_file(library, animation, FileKind::Spritesheet(file));
+
+        futures::future::try_join(copy(SpritesheetFile::Sheet), copy(SpritesheetFile::Frames))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Deletes a file, logging (rather than returning) any error.
+    pub async fn delete_media(&self, library: MediaLibrary, file: FileKind, id: Uuid) {
+        if let Err(err) = self.delete_media_file(library, id, file).await {
+            let key = shared::media::media_key(library, id, file);
+
+            log::warn!("failed to delete {} from storage: {}", key, err);
+
+            sentry::with_scope(
+                |scope| scope.set_level(Some(sentry::Level::Warning)),
+                || {
+                    sentry::add_breadcrumb(sentry::Breadcrumb {
+                        ty: "info".to_owned(),
+                        data: {
+                            let mut map = sentry::protocol::Map::new();
+                            map.insert("key".to_owned(), key.clone().into());
+                            map
+                        },
+                        ..Default::default()
+                    });
+
+                    sentry::integrations::anyhow::capture_anyhow(&err);
+                },
+            );
+        }
+    }
+
+    /// The largest a file of `file_kind` can be uploaded as, if there is a limit.
+    pub fn file_size_limit(&self, file_kind: &FileKind) -> Option<usize> {
+        match file_kind {
+            FileKind::AnimationGif => Some(config::ANIMATION_BODY_SIZE_LIMIT),
+            FileKind::ImagePng(PngImageFile::Original) => Some(config::IMAGE_BODY_SIZE_LIMIT),
+            FileKind::Spritesheet(SpritesheetFile::Sheet) => {
+                Some(config::ANIMATION_BODY_SIZE_LIMIT)
+            }
+            FileKind::Spritesheet(SpritesheetFile::Frames) => {
+                Some(config::JSON_BODY_LIMIT as usize)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Creates the storage selected by `settings`.
+///
+/// A local directory is used if one is configured, otherwise S3 (and Google Cloud Storage, if configured).
+/// Returns `None` if no storage is configured, in which case all related routes return "501 - Not Implemented".
+pub async fn from_settings(
+    settings: &SettingsManager,
+) -> anyhow::Result<Option<Arc<dyn MediaStorage>>> {
+    if let Some(local) = settings.local_storage_settings().await? {
+        return Ok(Some(Arc::new(LocalStorage::new(local)?)));
+    }
+
+    let s3 = settings
+        .s3_settings()
+        .await?
+        .map(s3::Client::new)
+        .transpose()?;
+
+    let gcs = settings
+        .google_cloud_storage_settings()
+        .await?
+        .map(service::storage::Client::new)
+        .transpose()?;
+
+    Ok(s3.map(|s3| Arc::new(CloudStorage::new(s3, gcs)) as Arc<dyn MediaStorage>))
+}
@@ -0,0 +1,109 @@
+//! Media stored in S3, with uploads going through Google Cloud Storage.
+
+use futures::future::{BoxFuture, FutureExt};
+use shared::media::{FileKind, MediaLibrary};
+use uuid::Uuid;
+
+use super::MediaStorage;
+use crate::{error, s3, service};
+
+pub struct CloudStorage {
+    s3: s3::Client,
+    gcs: Option<service::storage::Client>,
+}
+
+impl CloudStorage {
+    #[must_use]
+    pub const fn new(s3: s3::Client, gcs: Option<service::storage::Client>) -> Self {
+        Self { s3, gcs }
+    }
+
+    /// The bucket that uploads are stored in until they're processed.
+    #[must_use]
+    pub fn processing_bucket(&self) -> &str {
+        self.s3.processing_bucket()
+    }
+}
+
+impl MediaStorage for CloudStorage {
+    fn upload_media(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        self.s3.upload_media(data, library, id, file_kind).boxed()
+    }
+
+    fn upload_media_for_processing(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        self.s3
+            .upload_media_for_processing(data, library, id, file_kind)
+            .boxed()
+    }
+
+    fn download_media_for_processing(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>> {
+        self.s3
+            .download_media_for_processing(library, id, file_kind)
+            .boxed()
+    }
+
+    fn download_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>> {
+        self.s3.download_media_file(library, id, file_kind).boxed()
+    }
+
+    fn copy_processed_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        self.s3.copy_processed_file(library, id, file_kind).boxed()
+    }
+
+    fn delete_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        self.s3.delete_media_file(library, id, file_kind).boxed()
+    }
+
+    fn get_url_for_resumable_upload_for_processing(
+        &self,
+        upload_content_length: usize,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, Result<String, error::Storage>> {
+        async move {
+            let gcs = self.gcs.as_ref().ok_or(error::Storage::Disabled)?;
+
+            gcs.get_url_for_resumable_upload_for_processing(
+                upload_content_length,
+                library,
+                id,
+                file_kind,
+            )
+            .await
+        }
+        .boxed()
+    }
+}
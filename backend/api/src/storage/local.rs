@@ -0,0 +1,277 @@
+//! Media stored in a local directory, for running without S3 or Google Cloud Storage.
+//!
+//! Finished media is served from `/uploads`, and uploads are accepted at `/processing`
+//! (for media that was given an upload url and hasn't been processed since),
+//! where they're processed as soon as they're uploaded (there's no Eventarc to trigger processing).
+
+use std::{io, path::PathBuf, str::FromStr};
+
+use actix_web::{
+    web::{self, Bytes, Data, ServiceConfig},
+    HttpResponse,
+};
+use config::RemoteTarget;
+use core::settings::LocalStorageSettings;
+use futures::future::{BoxFuture, FutureExt};
+use shared::media::{media_key, FileKind, MediaLibrary, PngImageFile};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::MediaStorage;
+use crate::{
+    error,
+    service::{uploads, ServiceData},
+};
+
+/// The subdirectory finished media is stored in.
+const MEDIA_DIRECTORY: &str = "uploads";
+
+/// The subdirectory uploads are stored in until they're processed.
+const PROCESSING_DIRECTORY: &str = "processing";
+
+pub struct LocalStorage {
+    directory: PathBuf,
+    api_url: String,
+}
+
+impl LocalStorage {
+    /// Fails unless running locally, since uploads for processing are accepted without any credentials.
+    pub fn new(settings: LocalStorageSettings) -> anyhow::Result<Self> {
+        let LocalStorageSettings {
+            remote_target,
+            directory,
+            api_url,
+        } = settings;
+
+        anyhow::ensure!(
+            remote_target == RemoteTarget::Local,
+            "media can only be stored in a local directory when running locally (not {:?})",
+            remote_target
+        );
+
+        std::fs::create_dir_all(directory.join(MEDIA_DIRECTORY))?;
+        std::fs::create_dir_all(directory.join(PROCESSING_DIRECTORY))?;
+
+        Ok(Self { directory, api_url })
+    }
+
+    fn path(
+        &self,
+        subdirectory: &str,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> PathBuf {
+        self.directory
+            .join(subdirectory)
+            .join(media_key(library, id, file_kind))
+    }
+}
+
+async fn write(path: PathBuf, data: Vec<u8>) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    tokio::fs::write(path, data).await?;
+
+    Ok(())
+}
+
+async fn read(path: PathBuf) -> anyhow::Result<Option<Vec<u8>>> {
+    match tokio::fs::read(path).await {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+async fn copy(from: PathBuf, to: PathBuf) -> anyhow::Result<()> {
+    if let Some(parent) = to.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+
+    tokio::fs::copy(from, to).await?;
+
+    Ok(())
+}
+
+async fn delete(path: PathBuf) -> anyhow::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+impl MediaStorage for LocalStorage {
+    fn upload_media(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        write(self.path(MEDIA_DIRECTORY, library, id, file_kind), data).boxed()
+    }
+
+    fn upload_media_for_processing(
+        &self,
+        data: Vec<u8>,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        write(
+            self.path(PROCESSING_DIRECTORY, library, id, file_kind),
+            data,
+        )
+        .boxed()
+    }
+
+    fn download_media_for_processing(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>> {
+        read(self.path(PROCESSING_DIRECTORY, library, id, file_kind)).boxed()
+    }
+
+    fn download_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<Option<Vec<u8>>>> {
+        read(self.path(MEDIA_DIRECTORY, library, id, file_kind)).boxed()
+    }
+
+    fn copy_processed_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        copy(
+            self.path(PROCESSING_DIRECTORY, library, id, file_kind),
+            self.path(MEDIA_DIRECTORY, library, id, file_kind),
+        )
+        .boxed()
+    }
+
+    fn delete_media_file(
+        &self,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, anyhow::Result<()>> {
+        delete(self.path(MEDIA_DIRECTORY, library, id, file_kind)).boxed()
+    }
+
+    fn get_url_for_resumable_upload_for_processing(
+        &self,
+        _upload_content_length: usize,
+        library: MediaLibrary,
+        id: Uuid,
+        file_kind: FileKind,
+    ) -> BoxFuture<'_, Result<String, error::Storage>> {
+        let url = format!(
+            "{}/{}/{}",
+            self.api_url,
+            PROCESSING_DIRECTORY,
+            media_key(library, id, file_kind)
+        );
+
+        futures::future::ok(url).boxed()
+    }
+
+    fn configure(&self, cfg: &mut ServiceConfig) {
+        cfg.service(actix_files::Files::new(
+            &format!("/{}", MEDIA_DIRECTORY),
+            self.directory.join(MEDIA_DIRECTORY),
+        ))
+        .service(
+            web::resource(&format!(
+                "/{}/media/{{library}}/{{id}}/{{file}}",
+                PROCESSING_DIRECTORY
+            ))
+            .app_data(web::PayloadConfig::new(
+                config::ANIMATION_BODY_SIZE_LIMIT.max(config::IMAGE_BODY_SIZE_LIMIT),
+            ))
+            .route(web::put().to(upload_for_processing)),
+        );
+    }
+}
+
+/// Stands in for a Google Cloud Storage resumable upload (in a single request), and processes the file right away.
+async fn upload_for_processing(
+    db: Data<PgPool>,
+    storage: ServiceData<dyn MediaStorage>,
+    path: web::Path<(String, Uuid, String)>,
+    data: Bytes,
+) -> Result<HttpResponse, error::Upload> {
+    let (library, id, file) = path.into_inner();
+
+    let library = MediaLibrary::from_str(&library).map_err(|_| error::Upload::ResourceNotFound)?;
+    let file_kind = FileKind::from_str(&file).map_err(|_| error::Upload::ResourceNotFound)?;
+
+    // there's no signed url to check, so only files that were given an upload url, and haven't been processed since, are accepted.
+    if !is_pending(&db, library, id, file_kind).await? {
+        return Err(error::Upload::ResourceNotFound);
+    }
+
+    storage
+        .upload_media_for_processing(data.to_vec(), library, id, file_kind)
+        .await?;
+
+    match uploads::process(&db, &**storage, library, id, file_kind).await? {
+        Some(_) => Ok(HttpResponse::Ok().finish()),
+        None => Err(error::Upload::ResourceNotFound),
+    }
+}
+
+/// Whether an upload url was given out for `file_kind` of `id`, and it hasn't been processed since.
+async fn is_pending(
+    db: &PgPool,
+    library: MediaLibrary,
+    id: Uuid,
+    file_kind: FileKind,
+) -> sqlx::Result<bool> {
+    let pending = match (library, file_kind) {
+        (MediaLibrary::Global, FileKind::ImagePng(PngImageFile::Original)) => {
+            sqlx::query!(
+                r#"select exists(select 1 from image_upload where image_id = $1 and uploaded_at is not null and processed_at >= uploaded_at is not true) as "exists!""#,
+                id
+            )
+            .fetch_one(db)
+            .await?
+            .exists
+        }
+
+        (MediaLibrary::User, FileKind::ImagePng(PngImageFile::Original)) => {
+            sqlx::query!(
+                r#"select exists(select 1 from user_image_upload where image_id = $1 and uploaded_at is not null and processed_at >= uploaded_at is not true) as "exists!""#,
+                id
+            )
+            .fetch_one(db)
+            .await?
+            .exists
+        }
+
+        (MediaLibrary::Global, FileKind::AnimationGif)
+        | (MediaLibrary::Global, FileKind::Spritesheet(_)) => {
+            sqlx::query!(
+                r#"select exists(select 1 from global_animation_upload where animation_id = $1 and uploaded_at is not null and processed_at >= uploaded_at is not true) as "exists!""#,
+                id
+            )
+            .fetch_one(db)
+            .await?
+            .exists
+        }
+
+        _ => false,
+    };
+
+    Ok(pending)
+}
@@ -3,7 +3,11 @@ use chrono::{DateTime, Duration, Utc};
 use http::StatusCode;
 use paseto::{PasetoBuilder, TimeBackend};
 use rand::Rng;
-use shared::domain::{session::AUTH_COOKIE_NAME, user::UserScope};
+use shared::domain::{
+    class::assignment::{AssignmentId, AssignmentStudentId},
+    session::AUTH_COOKIE_NAME,
+    user::UserScope,
+};
 use sqlx::PgPool;
 use uuid::Uuid;
 
@@ -16,6 +20,13 @@ pub struct SessionClaims {
     pub token: String,
 }
 
+/// Claims for a student who joined an assignment (they don't have a user).
+pub struct AssignmentSessionClaims {
+    pub student_id: AssignmentStudentId,
+    pub assignment_id: AssignmentId,
+    pub token: String,
+}
+
 /// The claims that are used as part of the user's token.
 #[derive(Debug, serde::Serialize, serde::Deserialize, Clone)]
 struct AuthorizedTokenClaims {
@@ -34,6 +45,7 @@ bitflags::bitflags! {
         const VERIFY_EMAIL = 0b0000_0000_0000_0100;
         const CHANGE_PASSWORD = 0b0000_0000_0000_1000;
         const DELETE_ACCOUNT = 0b0000_0000_0001_0000;
+        const PLAY_ASSIGNMENT = 0b0000_0000_0010_0000;
 
         const GENERAL = Self::GENERAL_API.bits | Self::DELETE_ACCOUNT.bits;
         const ONE_TIME = Self::CHANGE_PASSWORD.bits | Self::VERIFY_EMAIL.bits;
@@ -77,14 +89,11 @@ fn validate_token(
     Err(e.into())
 }
 
-// todo: accept a transaction instead so that we can do `for share` row locks
-pub async fn check_login_token(
-    db: &PgPool,
+fn validate_authorized_token(
     token_string: &str,
     csrf: &str,
     token_key: &[u8; 32],
-    min_mask: SessionMask,
-) -> Result<SessionClaims, actix_web::Error> {
+) -> Result<AuthorizedTokenClaims, actix_web::Error> {
     let token = validate_token(token_string, AUTHORIZED_FOOTER, token_key)?;
 
     let claims: AuthorizedTokenClaims = serde_json::from_value(token)
@@ -95,14 +104,28 @@ pub async fn check_login_token(
         return Err(BasicError::new(StatusCode::UNAUTHORIZED).into());
     }
 
+    Ok(claims)
+}
+
+// todo: accept a transaction instead so that we can do `for share` row locks
+pub async fn check_login_token(
+    db: &PgPool,
+    token_string: &str,
+    csrf: &str,
+    token_key: &[u8; 32],
+    min_mask: SessionMask,
+) -> Result<SessionClaims, actix_web::Error> {
+    let claims = validate_authorized_token(token_string, csrf, token_key)?;
+
     let mut txn = db.begin().await.map_err(Into::into).map_err(error::ise)?;
 
     let session_info = sqlx::query!(
         r#"
-select user_id
+select user_id as "user_id!"
 from session
 where 
     token = $1 and
+    user_id is not null and
     expires_at < now() is not true and
     (scope_mask & $2) = $2 and
     (impersonator_id is null or exists(select 1 from user_scope where user_scope.user_id = impersonator_id and user_scope.scope = $3))
@@ -131,6 +154,41 @@ where
     })
 }
 
+pub async fn check_assignment_token(
+    db: &PgPool,
+    token_string: &str,
+    csrf: &str,
+    token_key: &[u8; 32],
+) -> Result<AssignmentSessionClaims, actix_web::Error> {
+    let claims = validate_authorized_token(token_string, csrf, token_key)?;
+
+    let session_info = sqlx::query!(
+        r#"
+select assignment_student.id as "student_id: AssignmentStudentId",
+       assignment_student.assignment_id as "assignment_id: AssignmentId"
+from session
+inner join assignment_student on assignment_student.id = session.assignment_student_id
+where
+    token = $1 and
+    expires_at < now() is not true and
+    (scope_mask & $2) = $2
+"#,
+        &claims.sub,
+        SessionMask::PLAY_ASSIGNMENT.bits as i16,
+    )
+    .fetch_optional(db)
+    .await
+    .map_err(anyhow::Error::from)
+    .map_err(error::ise)?
+    .ok_or_else(|| BasicError::new(StatusCode::UNAUTHORIZED))?;
+
+    Ok(AssignmentSessionClaims {
+        student_id: session_info.student_id,
+        assignment_id: session_info.assignment_id,
+        token: claims.sub,
+    })
+}
+
 pub fn create_auth_token(
     token_secret: &[u8; 32],
     local_insecure: bool,
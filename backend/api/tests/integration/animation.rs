@@ -1,6 +1,12 @@
+use std::io::Cursor;
+
 use http::StatusCode;
+use image::{Delay, Frame, GenericImageView, ImageFormat, Rgba, RgbaImage};
 use serde_json::json;
-use shared::domain::{animation::AnimationId, CreateResponse};
+use shared::domain::{
+    animation::{AnimationId, AnimationSearchResponse, AnimationUploadResponse},
+    CreateResponse,
+};
 
 use crate::{
     fixture::Fixture,
@@ -77,7 +83,244 @@ async fn get_metadata() -> anyhow::Result<()> {
     Ok(())
 }
 
-// todo: upload; s3
+#[actix_rt::test]
+async fn search() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Animation]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/animation", port))
+        .query(&[("q", "testest"), ("kind", "Gif")])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: AnimationSearchResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.total_animation_count, 1);
+    assert_eq!(
+        body.animations[0].metadata.id.0,
+        "3de4bcb6-7d23-11eb-8c8a-fbaee84afb0a".parse::<uuid::Uuid>()?
+    );
+
+    Ok(())
+}
+
+async fn create_spritesheet(client: &reqwest::Client, port: u16) -> anyhow::Result<AnimationId> {
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/animation", port))
+        .login()
+        .json(&json!({
+            "name": "test",
+            "description": "testest",
+            "is_premium": false,
+            "publish_at": (),
+            "styles": [],
+            "kind": "Spritesheet",
+            "is_looping": true,
+        }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: CreateResponse<AnimationId> = resp.json().await?;
+
+    Ok(body.id)
+}
+
+/// Uploads a file of an animation the way the frontend does, it's processed right away when stored locally.
+async fn upload(
+    client: &reqwest::Client,
+    port: u16,
+    id: AnimationId,
+    spritesheet_file: Option<&str>,
+    file: Vec<u8>,
+) -> anyhow::Result<()> {
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/animation/{}/raw",
+            port, id.0
+        ))
+        .json(&json!({ "file_size": file.len(), "spritesheet_file": spritesheet_file }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp: AnimationUploadResponse = resp.json().await?;
+
+    client
+        .put(&resp.session_uri)
+        .header(reqwest::header::CONTENT_LENGTH, &file.len().to_string())
+        .body(file)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+async fn get_file(
+    client: &reqwest::Client,
+    port: u16,
+    id: AnimationId,
+    file: &str,
+) -> anyhow::Result<reqwest::Response> {
+    Ok(client
+        .get(&format!(
+            "http://0.0.0.0:{}/uploads/media/global/{}/{}",
+            port, id.0, file
+        ))
+        .send()
+        .await?)
+}
+
+/// A 64x32 sheet, with room for 4 columns and 2 rows of 16x16 frames.
+fn sheet() -> anyhow::Result<Vec<u8>> {
+    let mut sheet = Vec::new();
+
+    image::DynamicImage::ImageRgba8(RgbaImage::from_pixel(64, 32, Rgba([255, 0, 0, 255])))
+        .write_to(&mut sheet, ImageFormat::Png)?;
+
+    Ok(sheet)
+}
+
+#[actix_rt::test]
+async fn upload_spritesheet() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let id = create_spritesheet(&client, port).await?;
+
+    let frames = json!({ "frameWidth": 16, "frameHeight": 16, "frameCount": 7, "frameRate": 10.0 });
+
+    upload(&client, port, id, Some("sheet"), sheet()?).await?;
+
+    // nothing is processed until both files are there.
+    let resp = get_file(&client, port, id, "spritesheet.png").await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    upload(
+        &client,
+        port,
+        id,
+        Some("frames"),
+        serde_json::to_vec(&frames)?,
+    )
+    .await?;
+
+    let resp = get_file(&client, port, id, "spritesheet.png").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = get_file(&client, port, id, "spritesheet.json").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body, frames);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_spritesheet_mismatched() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let mismatched = [
+        // needs a third row
+        json!({ "frameWidth": 16, "frameHeight": 16, "frameCount": 9, "frameRate": 10.0 }),
+        // leaves an empty row
+        json!({ "frameWidth": 16, "frameHeight": 16, "frameCount": 4, "frameRate": 10.0 }),
+        // doesn't divide the sheet
+        json!({ "frameWidth": 20, "frameHeight": 16, "frameCount": 6, "frameRate": 10.0 }),
+    ];
+
+    for frames in &mismatched {
+        let id = create_spritesheet(&client, port).await?;
+
+        upload(&client, port, id, Some("sheet"), sheet()?).await?;
+        upload(
+            &client,
+            port,
+            id,
+            Some("frames"),
+            serde_json::to_vec(frames)?,
+        )
+        .await?;
+
+        let resp = get_file(&client, port, id, "spritesheet.png").await?;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{}", frames);
+    }
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_gif_as_spritesheet() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let id = create_spritesheet(&client, port).await?;
+
+    // 5 frames of 8x6, 200ms each.
+    let mut gif = Vec::new();
+
+    image::gif::GifEncoder::new(Cursor::new(&mut gif)).encode_frames((0..5u8).map(|it| {
+        Frame::from_parts(
+            RgbaImage::from_pixel(8, 6, Rgba([it * 50, 0, 0, 255])),
+            0,
+            0,
+            Delay::from_numer_denom_ms(200, 1),
+        )
+    }))?;
+
+    upload(&client, port, id, None, gif).await?;
+
+    let resp = get_file(&client, port, id, "spritesheet.json").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let frames: serde_json::Value = resp.json().await?;
+
+    let resp = get_file(&client, port, id, "spritesheet.png").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let sheet = image::load_from_memory_with_format(&resp.bytes().await?, ImageFormat::Png)?;
+
+    app.stop(false).await;
+
+    assert_eq!(
+        frames,
+        json!({ "frameWidth": 8, "frameHeight": 6, "frameCount": 5, "frameRate": 5.0 })
+    );
+
+    // laid out 3 frames wide, as close to square as possible.
+    assert_eq!((sheet.width(), sheet.height()), (24, 12));
+
+    Ok(())
+}
+
 // todo: delete; s3
 // todo: delete: edge case (never uploaded, should work even without s3)
 // todo: update (no route)
@@ -1,5 +1,23 @@
+use std::f64::consts::PI;
+
 use http::StatusCode;
-use shared::domain::{audio::AudioId, CreateResponse};
+use serde_json::json;
+use shared::{
+    domain::{
+        audio::{
+            user::{UserAudioResponse, UserAudioSearchResponse},
+            AudioId,
+        },
+        jig::module::{
+            body::{_groups::cards::BaseContent, memory, Audio, Instructions},
+            ModuleBody, ModuleUpdateRequest,
+        },
+        media::MediaUsageResponse,
+        CreateResponse,
+    },
+    error::{ApiError, MediaInUse},
+    media::MediaLibrary,
+};
 
 use crate::{
     fixture::Fixture,
@@ -31,3 +49,363 @@ async fn create_returns_created() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_rt::test]
+async fn update_and_search() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let mut ids = Vec::new();
+
+    for (name, tags) in &[("greeting", json!(["shalom"])), ("farewell", json!([]))] {
+        let resp = client
+            .post(&format!("http://0.0.0.0:{}/v1/user/me/audio", port))
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let CreateResponse { id } = resp.json::<CreateResponse<AudioId>>().await?;
+
+        let resp = client
+            .patch(&format!(
+                "http://0.0.0.0:{}/v1/user/me/audio/{}",
+                port, id.0
+            ))
+            .login()
+            .json(&json!({ "name": name, "tags": tags }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+        ids.push(id);
+    }
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/audio/search", port))
+        .query(&[("q", "shalom")])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: UserAudioSearchResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.total_audio_count, 1);
+    assert_eq!(body.audio_files[0].metadata.id, ids[0]);
+    assert_eq!(body.audio_files[0].metadata.name, "greeting");
+    // never uploaded, so never processed.
+    assert_eq!(body.audio_files[0].metadata.duration_ms, None);
+    assert!(body.audio_files[0].metadata.waveform.is_empty());
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn update_not_owned() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Audio]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/9b2ff0e6-ee1d-11eb-8d1a-5b1a42b2e4b5",
+            port
+        ))
+        .login()
+        .json(&json!({ "name": "mine now" }))
+        .send()
+        .await?;
+
+    app.stop(false).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn usage() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let audio_id = AudioId("65f6f260-af1d-11eb-97df-8366e4dbf88e".parse()?);
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            port
+        ))
+        .login()
+        .json(&ModuleUpdateRequest {
+            body: Some(ModuleBody::MemoryGame(memory::ModuleData {
+                content: Some(memory::Content {
+                    base: BaseContent {
+                        instructions: Instructions {
+                            text: None,
+                            audio: Some(Audio {
+                                id: audio_id,
+                                lib: MediaLibrary::User,
+                            }),
+                        },
+                        ..BaseContent::default()
+                    },
+                    ..memory::Content::default()
+                }),
+            })),
+            ..ModuleUpdateRequest::default()
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/{}/usage",
+            port, audio_id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: MediaUsageResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.usages.len(), 1);
+    assert_eq!(
+        body.usages[0].module_id.0,
+        "0cc03a02-7c83-11eb-9f77-f77f9ad65e9a".parse::<uuid::Uuid>()?
+    );
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn delete_in_use() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/audio", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let CreateResponse { id: audio_id } = resp.json::<CreateResponse<AudioId>>().await?;
+
+    client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            port
+        ))
+        .login()
+        .json(&ModuleUpdateRequest {
+            body: Some(ModuleBody::MemoryGame(memory::ModuleData {
+                content: Some(memory::Content {
+                    base: BaseContent {
+                        instructions: Instructions {
+                            text: None,
+                            audio: Some(Audio {
+                                id: audio_id,
+                                lib: MediaLibrary::User,
+                            }),
+                        },
+                        ..BaseContent::default()
+                    },
+                    ..memory::Content::default()
+                }),
+            })),
+            ..ModuleUpdateRequest::default()
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/{}",
+            port, audio_id.0
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let body: ApiError<MediaInUse> = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.extra.jigs.len(), 1);
+    assert_eq!(
+        body.extra.jigs[0].0,
+        "0cc084bc-7c83-11eb-9f77-e3218dffb008".parse::<uuid::Uuid>()?
+    );
+
+    Ok(())
+}
+
+/// A second of a 440 Hz tone, as a 16 bit mono WAV.
+fn wav() -> Vec<u8> {
+    const SAMPLE_RATE: u32 = 44100;
+
+    let data_len = SAMPLE_RATE * 2;
+
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16_u32.to_le_bytes());
+    // PCM, mono
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&1_u16.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    // bytes per second, bytes per sample and bits per sample
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes());
+    wav.extend_from_slice(&2_u16.to_le_bytes());
+    wav.extend_from_slice(&16_u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+
+    for i in 0..SAMPLE_RATE {
+        let sample = (f64::from(i) * 440.0 * 2.0 * PI / f64::from(SAMPLE_RATE)).sin() * 16384.0;
+        wav.extend_from_slice(&(sample as i16).to_le_bytes());
+    }
+
+    wav
+}
+
+async fn create(client: &reqwest::Client, port: u16) -> anyhow::Result<AudioId> {
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/audio", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(resp.json::<CreateResponse<AudioId>>().await?.id)
+}
+
+async fn get(
+    client: &reqwest::Client,
+    port: u16,
+    id: AudioId,
+) -> anyhow::Result<UserAudioResponse> {
+    Ok(client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/{}",
+            port, id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+// uploads are processed with ffmpeg, which has to be installed to run this.
+#[actix_rt::test]
+async fn upload_wav() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let id = create(&client, port).await?;
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/{}/raw",
+            port, id.0
+        ))
+        .login()
+        .body(wav())
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let audio = get(&client, port, id).await?.metadata;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/uploads/media/user/{}/audio.mp3",
+            port, id.0
+        ))
+        .send()
+        .await?;
+
+    app.stop(false).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // give or take the MP3 encoder's padding.
+    let duration_ms = audio.duration_ms.expect("the duration wasn't recorded");
+    assert!((900..=1100).contains(&duration_ms), "{}", duration_ms);
+
+    assert_eq!(audio.waveform.len(), config::AUDIO_WAVEFORM_POINTS);
+    assert!(audio.waveform.iter().all(|&peak| peak > 0.0 && peak <= 1.0));
+
+    Ok(())
+}
+
+// uploads are processed with ffmpeg, which has to be installed to run this.
+#[actix_rt::test]
+async fn upload_not_audio() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let id = create(&client, port).await?;
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/user/me/audio/{}/raw",
+            port, id.0
+        ))
+        .login()
+        .body(&b"RIFF\0\0\0\0WAVE this isn't audio at all"[..])
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+    let audio = get(&client, port, id).await?.metadata;
+
+    app.stop(false).await;
+
+    assert_eq!(audio.duration_ms, None);
+    assert!(audio.waveform.is_empty());
+
+    Ok(())
+}
@@ -98,7 +98,6 @@ async fn image_patch() -> anyhow::Result<()> {
 }
 
 #[actix_rt::test]
-#[ignore] // no s3
 async fn image_delete() -> anyhow::Result<()> {
     forbidden(
         "v1/image/00000000-0000-0000-0000-000000000000",
@@ -205,7 +204,6 @@ async fn animation_patch() -> anyhow::Result<()> {
 }
 
 #[actix_rt::test]
-#[ignore] // no s3
 async fn animation_delete() -> anyhow::Result<()> {
     forbidden(
         "v1/animation/00000000-0000-0000-0000-000000000000",
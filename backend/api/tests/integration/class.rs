@@ -0,0 +1,230 @@
+use http::StatusCode;
+use serde_json::json;
+use shared::domain::{
+    class::{assignment::AssignmentId, ClassId},
+    CreateResponse,
+};
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server, LoginExt},
+};
+
+#[actix_rt::test]
+async fn create_and_browse() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "name": "Grade 3" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: CreateResponse<ClassId> = resp.json().await?;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/class/browse", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let browse: serde_json::Value = resp.json().await?;
+
+    assert_eq!(browse["total_class_count"], json!(1));
+    assert_eq!(browse["classes"][0]["id"], json!(body.id.0));
+    assert_eq!(browse["classes"][0]["name"], json!("Grade 3"));
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn join_and_play_assignment() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let class: CreateResponse<ClassId> = client
+        .post(&format!("http://0.0.0.0:{}/v1/class", port))
+        .json(&json!({ "name": "Grade 3" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let class_id = class.id.0;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/class/{}/assignment",
+            port, class_id
+        ))
+        .json(&json!({ "jig_id": "0cc084bc-7c83-11eb-9f77-e3218dffb008" }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let assignment: CreateResponse<AssignmentId> = resp.json().await?;
+
+    let assignment_id = assignment.id.0;
+
+    let body: serde_json::Value = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/class/{}/assignment/{}",
+            port, class_id, assignment_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let code = body["assignment"]["code"].as_str().unwrap().to_owned();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/assignment/join", port))
+        .json(&json!({ "code": "nope", "name": "Lost" }))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // codes are case insensitive.
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/assignment/join", port))
+        .json(&json!({ "code": code.to_lowercase(), "name": "Sam" }))
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let cookie = resp
+        .headers()
+        .get("set-cookie")
+        .unwrap()
+        .to_str()?
+        .split(';')
+        .next()
+        .unwrap()
+        .to_owned();
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["assignment_id"], json!(assignment_id));
+    assert_eq!(
+        body["jig_id"],
+        json!("0cc084bc-7c83-11eb-9f77-e3218dffb008")
+    );
+
+    let csrf = body["csrf"].as_str().unwrap().to_owned();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+            port
+        ))
+        .header("X-CSRF", &csrf)
+        .header("Cookie", &cookie)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // students can only see the jig they were assigned.
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/3a71522a-cd77-11eb-8dc1-af3e35f7c743",
+            port
+        ))
+        .header("X-CSRF", &csrf)
+        .header("Cookie", &cookie)
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/assignment/play/session",
+            port
+        ))
+        .header("X-CSRF", &csrf)
+        .header("Cookie", &cookie)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    let session_id = body["id"].as_str().unwrap().to_owned();
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/assignment/play/session/{}/module",
+            port, session_id
+        ))
+        .json(&json!({
+            "module_id": "0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            "score": 3,
+            "attempts": 4,
+            "time_spent": 20,
+        }))
+        .header("X-CSRF", &csrf)
+        .header("Cookie", &cookie)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/class/{}/assignment/{}/result",
+            port, class_id, assignment_id
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    let students = body["students"].as_array().unwrap();
+
+    assert_eq!(students.len(), 1);
+    assert_eq!(students[0]["name"], json!("Sam"));
+    assert_eq!(students[0]["sessions"][0]["id"], json!(session_id));
+    assert_eq!(
+        students[0]["sessions"][0]["module_results"][0]["score"],
+        json!(3)
+    );
+
+    app.stop(false).await;
+
+    Ok(())
+}
@@ -16,6 +16,7 @@ pub enum Fixture {
     MetaImage,
     MetaAnimation,
     MetaAudio,
+    JigCollaborator,
 }
 
 impl Fixture {
@@ -37,6 +38,7 @@ impl Fixture {
             Self::MetaImage => include_str!("../../fixtures/15_meta_kinds_image.sql"),
             Self::MetaAnimation => include_str!("../../fixtures/16_meta_kinds_animation.sql"),
             Self::MetaAudio => include_str!("../../fixtures/17_meta_kinds_audio.sql"),
+            Self::JigCollaborator => include_str!("../../fixtures/18_jig_collaborator.sql"),
         }
     }
 }
@@ -1,12 +1,20 @@
-use std::{collections::HashSet, sync::Mutex};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
 
 use chrono::{Duration, Utc};
 use config::RemoteTarget;
-use core::settings::{EmailClientSettings, GoogleCloudStorageSettings, RuntimeSettings};
+use core::settings::{EmailClientSettings, LocalStorageSettings, RuntimeSettings};
 use rand::Rng;
-use sqlx::{Connection, Executor};
+use sqlx::{Connection, Executor, PgPool};
 
-use ji_cloud_api::{google, http::Application, service, service::mail};
+use ji_cloud_api::{
+    google,
+    http::Application,
+    service::mail,
+    storage::{LocalStorage, MediaStorage},
+};
 
 use crate::fixture::Fixture;
 
@@ -137,9 +145,9 @@ static DB_URL_MANAGER: once_cell::sync::Lazy<DbManager> = once_cell::sync::Lazy:
 pub static PASETO_KEY: once_cell::sync::Lazy<Box<[u8; 32]>> =
     once_cell::sync::Lazy::new(|| Box::new(generate_paseto_key()));
 
-pub async fn initialize_server(fixtures: &[Fixture]) -> Application {
+/// Creates a (migrated) database with the `fixtures`, returning its name and a pool for it.
+pub async fn initialize_db(fixtures: &[Fixture]) -> (String, PgPool) {
     log_init();
-    let jwk_verifier = ji_cloud_api::jwk::create_verifier("".to_string());
 
     let db_name = DB_URL_MANAGER.create().await.expect("failed to create db");
 
@@ -155,10 +163,26 @@ pub async fn initialize_server(fixtures: &[Fixture]) -> Application {
             .expect("failed to execute fixture");
     }
 
+    (db_name, db)
+}
+
+pub async fn initialize_server(fixtures: &[Fixture]) -> Application {
+    let jwk_verifier = ji_cloud_api::jwk::create_verifier("".to_string());
+
+    let (db_name, db) = initialize_db(fixtures).await;
+
+    // the storage needs to know the api's url (to give out upload urls), so the api binds first.
+    let listener = ji_cloud_api::http::listen(0).expect("failed to bind the api");
+
+    let api_port = listener
+        .local_addr()
+        .expect("failed to get the api's address")
+        .port();
+
     // todo: cache this.
     let settings = RuntimeSettings::new(
         RemoteTarget::Local,
-        0,
+        api_port,
         0,
         0,
         None,
@@ -167,21 +191,20 @@ pub async fn initialize_server(fixtures: &[Fixture]) -> Application {
         None,
     );
 
-    // TODO: use token from .json credentials file
-    let mock_gcs_client = Some(GoogleCloudStorageSettings {
-        oauth2_token: "".to_owned(),
-        processing_bucket: "test-processing-bucket".to_owned(),
-        media_bucket: "test-bucket".to_owned(),
+    let storage = LocalStorage::new(LocalStorageSettings {
+        remote_target: RemoteTarget::Local,
+        directory: std::env::temp_dir()
+            .join("ji-cloud-test-storage")
+            .join(&db_name),
+        api_url: format!("http://0.0.0.0:{}", api_port),
     })
-    .map(service::storage::Client::new)
-    .transpose()
-    .unwrap();
+    .expect("failed to initialize storage");
 
-    let app = ji_cloud_api::http::build(
+    let app = ji_cloud_api::http::build_with_listener(
+        listener,
         db,
         settings,
-        None,
-        mock_gcs_client,
+        Some(Arc::new(storage) as Arc<dyn MediaStorage>),
         None,
         None,
         jwk_verifier,
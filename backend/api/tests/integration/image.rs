@@ -1,9 +1,18 @@
 mod recent;
 mod tag;
+mod upload;
 
 use http::StatusCode;
 use serde_json::json;
-use shared::domain::{image::ImageId, CreateResponse};
+use shared::domain::{
+    admin::AdminImageDuplicatesResponse,
+    image::{
+        ImageId, ImageRelatedResponse, ImageSearchResponse, ImageSimilarResponse,
+        ImageUpdateRequest,
+    },
+    meta::{ImageStyleId, TagId},
+    CreateResponse, Publish,
+};
 use std::io;
 use std::io::prelude::*;
 use uuid::Uuid;
@@ -185,7 +194,79 @@ async fn get_metadata() -> anyhow::Result<()> {
     Ok(())
 }
 
-// todo: search; missing algolia, s3
+// note: without algolia, this uses postgres' full text search.
+#[actix_rt::test]
+async fn search() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/image?q=test1", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: ImageSearchResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    let ids: Vec<_> = body.images.into_iter().map(|it| it.metadata.id).collect();
+
+    assert_eq!(
+        ids,
+        vec![ImageId("8cca6f3a-c4bb-11eb-8edf-13c75672da8f".parse()?)]
+    );
+    assert_eq!(body.total_image_count, 1);
+    assert_eq!(body.pages, 1);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn search_facets() -> anyhow::Result<()> {
+    let app = initialize_server(&[
+        Fixture::User,
+        Fixture::MetaKinds,
+        Fixture::Image,
+        Fixture::MetaImage,
+    ])
+    .await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/image?q=test", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: ImageSearchResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.total_image_count, 5);
+
+    let style = ImageStyleId("6389eaa0-de76-11ea-b7ab-0399bcf84df2".parse()?);
+    assert_eq!(
+        body.facets.styles.into_iter().collect::<Vec<_>>(),
+        vec![(style, 1)]
+    );
+    assert!(body.facets.categories.is_empty());
+
+    Ok(())
+}
+
 // todo: upload; missing algolia, s3
 // todo: delete; missing algolia, s3
 // todo: delete: edge case (never uploaded, should work even without s3), missing algolia
@@ -251,8 +332,196 @@ async fn update_tags() -> anyhow::Result<()> {
     update(&json!({"tags": ["591a2a64-a3a4-11eb-96e7-6bc0e819bc5f", "5b032222-a3a4-11eb-96e7-dbc5742f1640"]})).await
 }
 
+#[actix_rt::test]
+async fn related() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::MetaKinds, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let style = ImageStyleId("6389eaa0-de76-11ea-b7ab-0399bcf84df2".parse()?);
+    let tag = TagId("591a2a64-a3a4-11eb-96e7-6bc0e819bc5f".parse()?);
+
+    let published = Some(Some(Publish::At("2021-01-01T00:00:00Z".parse()?)));
+
+    // a shared tag counts for more than a shared style, and unpublished images are left out.
+    let updates = [
+        (
+            "3095d05e-f2c7-11ea-89c3-3b621dd74a1f",
+            Some(vec![style]),
+            Some(vec![tag]),
+            published,
+        ),
+        (
+            "8cca6f3a-c4bb-11eb-8edf-13c75672da8f",
+            None,
+            Some(vec![tag]),
+            published,
+        ),
+        (
+            "8cca7124-c4bb-11eb-8edf-7b42383ed8f5",
+            Some(vec![style]),
+            None,
+            published,
+        ),
+        (
+            "8cca719c-c4bb-11eb-8edf-f7accb638a15",
+            Some(vec![style]),
+            Some(vec![tag]),
+            None,
+        ),
+    ];
+
+    for (id, styles, tags, publish_at) in updates.iter().cloned() {
+        client
+            .patch(&format!("http://0.0.0.0:{}/v1/image/{}", port, id))
+            .json(&ImageUpdateRequest {
+                styles,
+                tags,
+                publish_at,
+                ..ImageUpdateRequest::default()
+            })
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+    }
+
+    let body: ImageRelatedResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/image/3095d05e-f2c7-11ea-89c3-3b621dd74a1f/related",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let ids: Vec<_> = body.images.into_iter().map(|it| it.metadata.id.0).collect();
+
+    assert_eq!(
+        ids,
+        vec![
+            "8cca6f3a-c4bb-11eb-8edf-13c75672da8f".parse()?,
+            "8cca7124-c4bb-11eb-8edf-7b42383ed8f5".parse()?,
+        ]
+    );
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/image/00000000-0000-0000-0000-000000000000/related",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn similar() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let body: ImageSimilarResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/image/3095d05e-f2c7-11ea-89c3-3b621dd74a1f/similar",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let ids: Vec<_> = body.images.into_iter().map(|it| it.metadata.id.0).collect();
+
+    assert_eq!(ids, vec!["8cca6f3a-c4bb-11eb-8edf-13c75672da8f".parse()?]);
+
+    // never uploaded, so nothing to compare it to.
+    let body: ImageSimilarResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/image/8cca720a-c4bb-11eb-8edf-63da1d86939c/similar",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert!(body.images.is_empty());
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/image/00000000-0000-0000-0000-000000000000/similar",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn duplicates() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let body: AdminImageDuplicatesResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/admin/image/duplicates",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let pairs: Vec<_> = body
+        .duplicates
+        .into_iter()
+        .map(|it| (it.image_id.0, it.duplicate_id.0, it.distance))
+        .collect();
+
+    assert_eq!(
+        pairs,
+        vec![(
+            "3095d05e-f2c7-11ea-89c3-3b621dd74a1f".parse()?,
+            "8cca6f3a-c4bb-11eb-8edf-13c75672da8f".parse()?,
+            3
+        )]
+    );
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
 // https://cloud.google.com/storage/docs/performing-resumable-uploads#single-chunk-upload
-#[ignore]
 #[actix_rt::test]
 async fn upload_with_url() -> anyhow::Result<()> {
     let file: Vec<u8> = include_bytes!("../../fixtures/ji-logo.png").to_vec();
@@ -288,6 +557,17 @@ async fn upload_with_url() -> anyhow::Result<()> {
 
     assert_eq!(resp.status(), StatusCode::OK);
 
+    // the upload is processed right away when stored locally.
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/uploads/media/global/8cca720a-c4bb-11eb-8edf-63da1d86939c/resized.png",
+            port,
+        ))
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
     Ok(())
 }
 
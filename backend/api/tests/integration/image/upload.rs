@@ -0,0 +1,305 @@
+use std::io::Cursor;
+
+use http::StatusCode;
+use image::{DynamicImage, GenericImageView, ImageOutputFormat, Rgb, RgbImage};
+use serde_json::json;
+use shared::domain::image::ImageUploadResponse;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server, LoginExt},
+};
+
+/// Uploads a file for the (not yet uploaded) image `test4` the way the frontend does, it's processed right away when stored locally.
+async fn upload(client: &reqwest::Client, port: u16, file: Vec<u8>) -> anyhow::Result<()> {
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/image/8cca720a-c4bb-11eb-8edf-63da1d86939c/raw",
+            port,
+        ))
+        .json(&json!({ "file_size": file.len() }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let resp: ImageUploadResponse = resp.json().await?;
+
+    client
+        .put(&resp.session_uri)
+        .header(reqwest::header::CONTENT_LENGTH, &file.len().to_string())
+        .body(file)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    Ok(())
+}
+
+async fn get_file(
+    client: &reqwest::Client,
+    port: u16,
+    file: &str,
+) -> anyhow::Result<reqwest::Response> {
+    Ok(client
+        .get(&format!(
+            "http://0.0.0.0:{}/uploads/media/global/8cca720a-c4bb-11eb-8edf-63da1d86939c/{}",
+            port, file
+        ))
+        .send()
+        .await?)
+}
+
+/// Checks that the resized image and thumbnail were generated in every format.
+async fn assert_resized(client: &reqwest::Client, port: u16) -> anyhow::Result<()> {
+    for file in &[
+        "resized.png",
+        "resized.webp",
+        "resized.avif",
+        "thumbnail.png",
+        "thumbnail.webp",
+        "thumbnail.avif",
+    ] {
+        let resp = get_file(client, port, file).await?;
+        assert_eq!(resp.status(), StatusCode::OK, "{}", file);
+    }
+
+    Ok(())
+}
+
+/// A 20x10 gradient (lossy formats don't keep a flat color as is, so it's not that either).
+fn photo() -> DynamicImage {
+    DynamicImage::ImageRgb8(RgbImage::from_fn(20, 10, |x, y| {
+        Rgb([(x * 12) as u8, (y * 25) as u8, 128])
+    }))
+}
+
+fn jpeg(image: &DynamicImage) -> anyhow::Result<Vec<u8>> {
+    let mut jpeg = Vec::new();
+    image.write_to(&mut jpeg, ImageOutputFormat::Jpeg(90))?;
+    Ok(jpeg)
+}
+
+/// Big endian TIFF data with only an orientation, like the EXIF a phone camera writes.
+fn exif(orientation: u16) -> Vec<u8> {
+    let mut tiff = b"MM\x00\x2a\x00\x00\x00\x08".to_vec();
+
+    // one entry: the orientation (0x0112), a single SHORT (3).
+    tiff.extend_from_slice(&[0x00, 0x01, 0x01, 0x12, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    tiff.extend_from_slice(&orientation.to_be_bytes());
+    tiff.extend_from_slice(&[0x00, 0x00]);
+
+    // no next IFD.
+    tiff.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+
+    tiff
+}
+
+/// Adds an `APP1` segment with `exif` to a JPEG, right after its start of image marker.
+fn jpeg_with_exif(jpeg: &[u8], exif: &[u8]) -> Vec<u8> {
+    let mut segment = b"Exif\x00\x00".to_vec();
+    segment.extend_from_slice(exif);
+
+    let len = (segment.len() + 2) as u16;
+
+    let mut data = jpeg[..2].to_vec();
+    data.extend_from_slice(&[0xff, 0xe1]);
+    data.extend_from_slice(&len.to_be_bytes());
+    data.extend_from_slice(&segment);
+    data.extend_from_slice(&jpeg[2..]);
+    data
+}
+
+fn has_exif(data: &[u8]) -> bool {
+    exif::Reader::new()
+        .read_from_container(&mut Cursor::new(data))
+        .is_ok()
+}
+
+#[actix_rt::test]
+async fn upload_jpeg() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let jpeg = jpeg(&photo())?;
+
+    upload(&client, port, jpeg_with_exif(&jpeg, &exif(1))).await?;
+
+    // lossy originals are kept in their own format.
+    let resp = get_file(&client, port, "original.png").await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = get_file(&client, port, "original.jpg").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[reqwest::header::CONTENT_TYPE].to_str()?,
+        "image/jpeg"
+    );
+
+    let original = resp.bytes().await?;
+
+    assert_resized(&client, port).await?;
+
+    app.stop(false).await;
+
+    // the metadata is stripped, and nothing else.
+    assert!(!has_exif(&original));
+    assert_eq!(&original[..], &jpeg[..]);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_jpeg_rotated() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    // turned a quarter clockwise to be the right way up.
+    upload(&client, port, jpeg_with_exif(&jpeg(&photo())?, &exif(6))).await?;
+
+    let resp = get_file(&client, port, "original.jpg").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let original = resp.bytes().await?;
+
+    let resp = get_file(&client, port, "thumbnail.png").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let thumbnail = resp.bytes().await?;
+
+    app.stop(false).await;
+
+    // re-encoded the right way up, without the orientation that would turn it again.
+    assert!(!has_exif(&original));
+    assert_eq!(image::load_from_memory(&original)?.dimensions(), (10, 20));
+
+    let (width, height) = image::load_from_memory(&thumbnail)?.dimensions();
+    assert!(height > width);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_webp() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let rgb = photo().to_rgb8();
+    let webp = webp::Encoder::from_rgb(&rgb, 20, 10).encode(80.0).to_vec();
+
+    // an extended WebP with an `EXIF` chunk: a `VP8X` chunk (flagging the EXIF) before the image, and the EXIF after it.
+    let mut extended = webp[..12].to_vec();
+    extended.extend_from_slice(b"VP8X\x0a\x00\x00\x00\x08\x00\x00\x00\x13\x00\x00\x09\x00\x00");
+    extended.extend_from_slice(&webp[12..]);
+
+    let exif = exif(1);
+    extended.extend_from_slice(b"EXIF");
+    extended.extend_from_slice(&(exif.len() as u32).to_le_bytes());
+    extended.extend_from_slice(&exif);
+
+    let riff_len = (extended.len() - 8) as u32;
+    extended[4..8].copy_from_slice(&riff_len.to_le_bytes());
+
+    upload(&client, port, extended).await?;
+
+    let resp = get_file(&client, port, "original.png").await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = get_file(&client, port, "original.webp").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()[reqwest::header::CONTENT_TYPE].to_str()?,
+        "image/webp"
+    );
+
+    let original = resp.bytes().await?;
+
+    assert_resized(&client, port).await?;
+
+    app.stop(false).await;
+
+    assert!(!original.windows(4).any(|chunk| chunk == b"EXIF"));
+
+    let original = webp::Decoder::new(&original)
+        .decode()
+        .expect("the original should be a valid WebP");
+    assert_eq!((original.width(), original.height()), (20, 10));
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_svg() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let svg = br#"<svg xmlns="http://www.w3.org/2000/svg" width="20" height="10"><rect width="20" height="10" fill="red"/></svg>"#;
+
+    upload(&client, port, svg.to_vec()).await?;
+
+    // rasterized, so the original is a PNG.
+    let resp = get_file(&client, port, "original.png").await?;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let original = resp.bytes().await?;
+
+    assert_resized(&client, port).await?;
+
+    app.stop(false).await;
+
+    let original = image::load_from_memory(&original)?;
+    let (width, height) = original.dimensions();
+
+    // scaled up to fit the image kind's size, keeping its aspect ratio.
+    assert_eq!(width, height * 2);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn upload_not_pending() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Image]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let file = include_bytes!("../../../fixtures/ji-logo.png").to_vec();
+
+    let put = |file: Vec<u8>| {
+        client
+            .put(&format!(
+                "http://0.0.0.0:{}/processing/media/global/8cca720a-c4bb-11eb-8edf-63da1d86939c/original.png",
+                port
+            ))
+            .header(reqwest::header::CONTENT_LENGTH, &file.len().to_string())
+            .body(file)
+            .send()
+    };
+
+    // no upload url was given out for it.
+    let resp = put(file.clone()).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    upload(&client, port, file.clone()).await?;
+
+    // it's already been processed.
+    let resp = put(file).await?;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
@@ -1,6 +1,19 @@
 use http::StatusCode;
 use serde_json::json;
-use shared::domain::{jig::JigId, CreateResponse};
+use shared::domain::{
+    jig::{
+        module::{
+            body::{
+                _groups::cards::{BaseContent, Card, CardPair},
+                memory,
+            },
+            ModuleBody, ModuleUpdateRequest,
+        },
+        JigBrowseResponse, JigId, JigLikedResponse, JigRelatedResponse, JigResponse,
+        JigSearchResponse,
+    },
+    CreateResponse,
+};
 
 use crate::{
     fixture::Fixture,
@@ -8,9 +21,12 @@ use crate::{
 };
 
 mod additional_resource;
+mod collaborator;
 mod cover;
 mod draft;
 mod module;
+mod player;
+mod version;
 
 #[actix_rt::test]
 async fn create_default() -> anyhow::Result<()> {
@@ -186,6 +202,184 @@ async fn get() -> anyhow::Result<()> {
     Ok(())
 }
 
+// note: without algolia, this uses postgres' full text search.
+#[actix_rt::test]
+async fn search() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig?q=description&is_published=true",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: JigSearchResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    let ids: Vec<_> = body.jigs.into_iter().map(|it| it.jig.id).collect();
+
+    assert_eq!(
+        ids,
+        vec![
+            JigId("bdc17474-d4a8-11eb-b8bc-0242ac130003".parse()?),
+            JigId("ce4edd68-d4a8-11eb-b8bc-0242ac130003".parse()?),
+        ]
+    );
+    assert_eq!(body.total_image_count, 2);
+
+    Ok(())
+}
+
+// note: without algolia, this uses postgres' full text search.
+#[actix_rt::test]
+async fn search_module_text() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let rich_text = json!({
+        "version": "0.1.0",
+        "content": [{ "children": [{ "text": "sha" }, { "text": "lom", "underline": true }] }],
+    });
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            port
+        ))
+        .login()
+        .json(&ModuleUpdateRequest {
+            body: Some(ModuleBody::MemoryGame(memory::ModuleData {
+                content: Some(memory::Content {
+                    base: BaseContent {
+                        pairs: vec![CardPair(
+                            Card::Text("aleph bet".to_owned()),
+                            Card::Text(rich_text.to_string()),
+                        )],
+                        ..BaseContent::default()
+                    },
+                    ..memory::Content::default()
+                }),
+            })),
+            ..ModuleUpdateRequest::default()
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    for q in &["aleph", "shalom"] {
+        let resp = client
+            .get(&format!("http://0.0.0.0:{}/v1/jig", port))
+            .query(&[("q", q)])
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: JigSearchResponse = resp.json().await?;
+
+        let ids: Vec<_> = body.jigs.into_iter().map(|it| it.jig.id).collect();
+
+        assert_eq!(
+            ids,
+            vec![JigId("0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?)]
+        );
+    }
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn search_hebrew() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            port
+        ))
+        .login()
+        .json(&ModuleUpdateRequest {
+            body: Some(ModuleBody::MemoryGame(memory::ModuleData {
+                content: Some(memory::Content {
+                    base: BaseContent {
+                        pairs: vec![CardPair(
+                            Card::Text("שַׁבָּת".to_owned()),
+                            Card::Text("שָׁלוֹם".to_owned()),
+                        )],
+                        ..BaseContent::default()
+                    },
+                    ..memory::Content::default()
+                }),
+            })),
+            ..ModuleUpdateRequest::default()
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // without niqqud, with a final letter form, and transliterated.
+    let queries = [("שבת", "false"), ("שלום", "false"), ("shabbat", "true")];
+
+    for (q, transliterate) in &queries {
+        let resp = client
+            .get(&format!("http://0.0.0.0:{}/v1/jig", port))
+            .query(&[("q", q), ("transliterate", transliterate)])
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let body: JigSearchResponse = resp.json().await?;
+
+        let ids: Vec<_> = body.jigs.into_iter().map(|it| it.jig.id).collect();
+
+        assert_eq!(
+            ids,
+            vec![JigId("0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?)]
+        );
+    }
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/jig", port))
+        .query(&[("q", "shabbat")])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigSearchResponse = resp.json().await?;
+
+    assert_eq!(body.total_image_count, 0);
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
 // todo: test-exhaustiveness: create a `JigBrowse` Fixture, actually test the cases (paging, jig count, etc)
 #[actix_rt::test]
 async fn browse_simple() -> anyhow::Result<()> {
@@ -219,6 +413,55 @@ async fn browse_simple() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[actix_rt::test]
+async fn browse_language_module_kinds() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/browse?language=en&moduleKinds=Cover,Memory",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: JigBrowseResponse = resp.json().await?;
+
+    let ids: Vec<_> = body.jigs.into_iter().map(|it| it.id).collect();
+
+    assert_eq!(
+        ids,
+        vec![JigId("0cc084bc-7c83-11eb-9f77-e3218dffb008".parse()?)]
+    );
+    assert_eq!(body.total_jig_count, 1);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/browse?language=he",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigBrowseResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body.total_jig_count, 0);
+
+    Ok(())
+}
+
 // todo: test-exhaustiveness: create a `JigBrowse` Fixture, actually test the cases (paging, jig count, etc)
 #[actix_rt::test]
 async fn browse_own_simple() -> anyhow::Result<()> {
@@ -280,3 +523,159 @@ async fn count() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_rt::test]
+async fn like() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let url = format!(
+        "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+        port
+    );
+
+    // liking twice only counts once.
+    for _ in 0..2 {
+        let resp = client
+            .put(&format!("{}/like", url))
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    let resp = client
+        .get(&format!("{}/like", url))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigLikedResponse = resp.json().await?;
+
+    assert!(body.is_liked);
+
+    let resp = client.get(&url).login().send().await?.error_for_status()?;
+
+    let body: JigResponse = resp.json().await?;
+
+    assert_eq!(body.jig.likes, 1);
+
+    let resp = client
+        .delete(&format!("{}/like", url))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!("{}/like", url))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigLikedResponse = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert!(!body.is_liked);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn play() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let url = format!(
+        "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008",
+        port
+    );
+
+    // playing twice in a row only counts once.
+    for _ in 0..2 {
+        let resp = client
+            .post(&format!("{}/play", url))
+            .login()
+            .send()
+            .await?
+            .error_for_status()?;
+
+        assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    }
+
+    let resp = client.get(&url).login().send().await?.error_for_status()?;
+
+    let body: JigResponse = resp.json().await?;
+
+    assert_eq!(body.jig.plays, 1);
+
+    // plays need a player
+    let resp = client.post(&format!("{}/play", url)).send().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn related() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let body: JigRelatedResponse = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/related",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    // only the published jigs, which all share a language and author with it.
+    let mut ids: Vec<_> = body.jigs.into_iter().map(|it| it.jig.id.0).collect();
+    ids.sort();
+
+    assert_eq!(
+        ids,
+        vec![
+            "bdc17474-d4a8-11eb-b8bc-0242ac130003".parse()?,
+            "ce4edd68-d4a8-11eb-b8bc-0242ac130003".parse()?,
+        ]
+    );
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/00000000-0000-0000-0000-000000000000/related",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server, LoginExt},
+};
+use http::StatusCode;
+use serde_json::json;
+use shared::domain::jig::collaborator::{JigCollaboratorListResponse, JigCollaboratorRole};
+
+#[actix_rt::test]
+async fn accept_edit_and_leave() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::UserDefaultPerms, Fixture::JigCollaborator]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let update = || {
+        client
+            .patch(&format!(
+                "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0",
+                port
+            ))
+            .json(&json!({
+                "description": "edited by a collaborator",
+            }))
+            .login()
+            .send()
+    };
+
+    // pending invites don't grant access
+    let resp = update().await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0/collaborator/accept",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = update().await?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0/collaborator",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigCollaboratorListResponse = resp.json().await?;
+
+    assert_eq!(body.collaborators.len(), 1);
+    assert_eq!(body.collaborators[0].role, JigCollaboratorRole::Editor);
+    assert!(body.collaborators[0].accepted_at.is_some());
+
+    // editors can't delete the jig or manage its collaborators
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0",
+            port
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0/collaborator",
+            port
+        ))
+        .json(&json!({
+            "email": "author@test.test",
+            "role": "Viewer",
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/jig/7ae2a5a6-e2d4-11eb-9fcd-07f1a8c7b8d0/collaborator/1f241e1b-b537-493f-a230-075cb16315be",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = update().await?;
+
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn invite_unknown_email() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/collaborator",
+            port
+        ))
+        .json(&json!({
+            "email": "nobody@test.test",
+            "role": "Editor",
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
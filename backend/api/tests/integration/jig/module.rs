@@ -100,3 +100,122 @@ async fn update_contents() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_rt::test]
+async fn update_if_match() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let url = format!(
+        "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cbfdd82-7c83-11eb-9f77-d7d86264c3bc",
+        port
+    );
+
+    let resp = client
+        .patch(&url)
+        .header("If-Match", "\"0\"")
+        .json(&ModuleUpdateRequest {
+            is_complete: Some(true),
+            ..ModuleUpdateRequest::default()
+        })
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(resp.headers()["ETag"], "\"1\"");
+
+    // the same (now stale) revision should be rejected, returning the current module
+    let resp = client
+        .patch(&url)
+        .header("If-Match", "\"0\"")
+        .json(&ModuleUpdateRequest {
+            is_complete: Some(false),
+            ..ModuleUpdateRequest::default()
+        })
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+    assert_eq!(resp.headers()["ETag"], "\"1\"");
+
+    let body: serde_json::Value = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body["module"]["revision"], 1);
+    assert_eq!(body["module"]["is_complete"], true);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn update_if_match_after_failed_save() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let url = format!(
+        "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/module/0cbfdd82-7c83-11eb-9f77-d7d86264c3bc",
+        port
+    );
+
+    // a save that fails (here, because the session is gone) leaves the module alone
+    let resp = client
+        .patch(&url)
+        .header("If-Match", "\"0\"")
+        .json(&ModuleUpdateRequest {
+            is_complete: Some(false),
+            ..ModuleUpdateRequest::default()
+        })
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+
+    // someone else saves in the meantime
+    let resp = client
+        .patch(&url)
+        .header("If-Match", "\"0\"")
+        .json(&ModuleUpdateRequest {
+            is_complete: Some(true),
+            ..ModuleUpdateRequest::default()
+        })
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    assert_eq!(resp.headers()["ETag"], "\"1\"");
+
+    // retrying against the revision the failed save was made at conflicts, rather than overwriting their save
+    let resp = client
+        .patch(&url)
+        .header("If-Match", "\"0\"")
+        .json(&ModuleUpdateRequest {
+            is_complete: Some(false),
+            ..ModuleUpdateRequest::default()
+        })
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::PRECONDITION_FAILED);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    app.stop(false).await;
+
+    assert_eq!(body["module"]["revision"], 1);
+    assert_eq!(body["module"]["is_complete"], true);
+
+    Ok(())
+}
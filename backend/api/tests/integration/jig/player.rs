@@ -0,0 +1,135 @@
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server, LoginExt},
+};
+use http::StatusCode;
+use serde_json::json;
+use shared::domain::{jig::player::JigPlayerSessionId, CreateResponse};
+
+#[actix_rt::test]
+async fn create_session_and_results() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body: CreateResponse<JigPlayerSessionId> = resp.json().await?;
+
+    let session_id = body.id.0;
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session/{}/module",
+            port, session_id
+        ))
+        .json(&json!({
+            "module_id": "0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            "score": 4,
+            "attempts": 6,
+            "time_spent": 30,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // modules that can't be scored leave the score out
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session/{}/module",
+            port, session_id
+        ))
+        .json(&json!({
+            "module_id": "0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            "attempts": 0,
+            "time_spent": 12,
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    // module from a different jig
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session/{}/module",
+            port, session_id
+        ))
+        .json(&json!({
+            "module_id": "d52b9ff8-cd74-11eb-8dc1-b760927dc672",
+            "score": 1,
+            "attempts": 1,
+            "time_spent": 1,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // doesn't fit in the database
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session/{}/module",
+            port, session_id
+        ))
+        .json(&json!({
+            "module_id": "0cc03a02-7c83-11eb-9f77-f77f9ad65e9a",
+            "score": u32::MAX,
+            "attempts": 1,
+            "time_spent": 1,
+        }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/player/session",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: serde_json::Value = resp.json().await?;
+
+    assert_eq!(body["total_session_count"], json!(1));
+    assert_eq!(body["sessions"][0]["id"], json!(session_id));
+
+    let results = &body["sessions"][0]["module_results"];
+
+    assert_eq!(results.as_array().map(Vec::len), Some(2));
+    assert_eq!(results[0]["score"], json!(4));
+    assert_eq!(results[0]["attempts"], json!(6));
+    assert_eq!(results[0]["time_spent"], json!(30));
+    assert_eq!(results[1].get("score"), None);
+    assert_eq!(results[1]["attempts"], json!(0));
+    assert_eq!(results[1]["time_spent"], json!(12));
+
+    app.stop(false).await;
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_server, LoginExt},
+};
+use http::StatusCode;
+use serde_json::json;
+use shared::domain::{
+    jig::{
+        version::{JigVersionDiffResponse, JigVersionListResponse, JigVersionResponse},
+        JigId, JigResponse,
+    },
+    CreateResponse,
+};
+
+#[actix_rt::test]
+async fn publish_diff_and_restore() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    // publish the fixture draft as-is, then a second draft with a new description
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/draft",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/draft",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let draft: CreateResponse<JigId> = resp.json().await?;
+
+    let resp = client
+        .patch(&format!("http://0.0.0.0:{}/v1/jig/{}", port, draft.id.0))
+        .json(&json!({
+            "description": "second version",
+        }))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let resp = client
+        .put(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/draft",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/version",
+            port
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: JigVersionListResponse = resp.json().await?;
+
+    assert_eq!(
+        body.versions
+            .iter()
+            .map(|it| it.version)
+            .collect::<Vec<_>>(),
+        vec![2, 1]
+    );
+
+    let (second, first) = (body.versions[0].id.0, body.versions[1].id.0);
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/version/{}",
+            port, first
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigVersionResponse = resp.json().await?;
+
+    assert!(body.snapshot.modules.is_empty());
+
+    let first_description = body.snapshot.description;
+
+    let resp = client
+        .get(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/version/{}/diff/{}",
+            port, first, second
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigVersionDiffResponse = resp.json().await?;
+
+    assert_eq!(body.changed_fields, vec!["description".to_owned()]);
+    assert!(body.modules.is_empty());
+
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/version/{}/restore",
+            port, first
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let draft: CreateResponse<JigId> = resp.json().await?;
+
+    let resp = client
+        .get(&format!("http://0.0.0.0:{}/v1/jig/{}", port, draft.id.0))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    let body: JigResponse = resp.json().await?;
+
+    assert_eq!(body.jig.description, first_description);
+
+    // restoring over the existing draft doesn't create a new one
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/0cc084bc-7c83-11eb-9f77-e3218dffb008/version/{}/restore",
+            port, second
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body: CreateResponse<JigId> = resp.json().await?;
+
+    assert_eq!(body.id, draft.id);
+
+    // versions of other jigs can't be restored
+    let resp = client
+        .post(&format!(
+            "http://0.0.0.0:{}/v1/jig/3a71522a-cd77-11eb-8dc1-af3e35f7c743/version/{}/restore",
+            port, first
+        ))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    app.stop(false).await;
+
+    Ok(())
+}
@@ -2,12 +2,14 @@ mod animation;
 mod audio;
 mod auth;
 mod category;
+mod class;
 mod fixture;
 mod helpers;
 mod image;
 mod jig;
 mod locale;
 mod meta;
+mod search;
 mod session;
 mod user;
 
@@ -0,0 +1,158 @@
+use http::StatusCode;
+use shared::domain::{
+    admin::{AdminSearchHealthResponse, SearchBackend},
+    search::{RecentSearchResponse, SearchSuggestResponse, SearchSuggestionKind},
+};
+use shared::search::normalize;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_db, initialize_server, LoginExt},
+};
+
+async fn suggestions(
+    client: &reqwest::Client,
+    port: u16,
+    q: &str,
+) -> anyhow::Result<Vec<(String, SearchSuggestionKind)>> {
+    let body: SearchSuggestResponse = client
+        .get(&format!("http://0.0.0.0:{}/v1/search/suggest", port))
+        .query(&[("q", q)])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(body
+        .suggestions
+        .into_iter()
+        .map(|it| (it.text, it.kind))
+        .collect())
+}
+
+#[actix_rt::test]
+async fn suggest_recent() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::CategoryNesting]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    client
+        .get(&format!("http://0.0.0.0:{}/v1/jig", port))
+        .query(&[("q", "parenting")])
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(
+        suggestions(&client, port, "par").await?,
+        vec![
+            ("parenting".to_owned(), SearchSuggestionKind::Recent),
+            ("Parent".to_owned(), SearchSuggestionKind::Category),
+        ]
+    );
+
+    let body: RecentSearchResponse = client
+        .get(&format!("http://0.0.0.0:{}/v1/search/recent", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(body.queries, vec!["parenting".to_owned()]);
+
+    let resp = client
+        .delete(&format!("http://0.0.0.0:{}/v1/search/recent", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    assert_eq!(
+        suggestions(&client, port, "par").await?,
+        vec![("Parent".to_owned(), SearchSuggestionKind::Category)]
+    );
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn health_postgres() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User, Fixture::Jig]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let body: AdminSearchHealthResponse = client
+        .get(&format!("http://0.0.0.0:{}/v1/admin/search/health", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    assert_eq!(body.backend, SearchBackend::Postgres);
+    assert_eq!(body.jigs.db_count, 5);
+    assert_eq!(body.jigs.pending_sync, None);
+    assert_eq!(body.jigs.index_count, None);
+    assert_eq!(body.images.db_count, 0);
+
+    // there's no index to sync to.
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/admin/search/reindex", port))
+        .json(&serde_json::json!({ "entities": ["jig"] }))
+        .login()
+        .send()
+        .await?;
+
+    assert_eq!(resp.status(), StatusCode::NOT_IMPLEMENTED);
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn normalize_matches_sql() -> anyhow::Result<()> {
+    let (_, db) = initialize_db(&[]).await;
+
+    let texts = [
+        "Parenting",
+        "שַׁבָּת שָׁלוֹם",
+        "בְּרֵאשִׁ֖ית בָּרָ֣א",
+        "בית־ספר",
+        "תנ״ך",
+        "ג׳ירפה",
+        "ךםןףץ",
+        "Mixed עִבְרִית and English",
+    ];
+
+    for text in &texts {
+        // `search_normalize` leaves lowercasing to `to_tsvector`.
+        let sql: String = sqlx::query_scalar("select lower(search_normalize($1))")
+            .bind(text)
+            .fetch_one(&db)
+            .await?;
+
+        assert_eq!(
+            sql,
+            normalize(text),
+            "{:?} was normalized differently",
+            text
+        );
+    }
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ use crate::{
 
 mod color;
 mod font;
+mod saved_search;
 
 #[actix_rt::test]
 async fn get_profile() -> anyhow::Result<()> {
@@ -0,0 +1,211 @@
+use chrono::{Duration, TimeZone, Utc};
+use http::StatusCode;
+use ji_cloud_api::db::saved_search;
+use shared::domain::{
+    jig::{JigId, JigSearchQuery},
+    user::saved_search::{
+        SavedSearchCreateRequest, SavedSearchId, SavedSearchListResponse, SavedSearchUpdateRequest,
+    },
+    CreateResponse,
+};
+
+use uuid::Uuid;
+
+use crate::{
+    fixture::Fixture,
+    helpers::{initialize_db, initialize_server, LoginExt},
+};
+
+async fn list(client: &reqwest::Client, port: u16) -> anyhow::Result<SavedSearchListResponse> {
+    let body = client
+        .get(&format!("http://0.0.0.0:{}/v1/user/me/saved-search", port))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(body)
+}
+
+#[actix_rt::test]
+async fn create_update_delete() -> anyhow::Result<()> {
+    let app = initialize_server(&[Fixture::User]).await;
+
+    let port = app.port();
+
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(&format!("http://0.0.0.0:{}/v1/user/me/saved-search", port))
+        .login()
+        .json(&SavedSearchCreateRequest {
+            name: "weekly".to_owned(),
+            query: JigSearchQuery {
+                q: "shabbat".to_owned(),
+                language: Some("en".to_owned()),
+                ..JigSearchQuery::default()
+            },
+            email_alerts: false,
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let CreateResponse { id }: CreateResponse<SavedSearchId> = resp.json().await?;
+
+    let body = list(&client, port).await?;
+
+    assert_eq!(body.saved_searches.len(), 1);
+    assert_eq!(body.saved_searches[0].id, id);
+    assert_eq!(body.saved_searches[0].name, "weekly");
+    assert_eq!(body.saved_searches[0].query.q, "shabbat");
+    assert_eq!(body.saved_searches[0].query.language.as_deref(), Some("en"));
+    assert!(!body.saved_searches[0].email_alerts);
+
+    let resp = client
+        .patch(&format!(
+            "http://0.0.0.0:{}/v1/user/me/saved-search/{}",
+            port, id.0
+        ))
+        .login()
+        .json(&SavedSearchUpdateRequest {
+            name: Some("shabbat".to_owned()),
+            email_alerts: Some(true),
+            ..SavedSearchUpdateRequest::default()
+        })
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body = list(&client, port).await?;
+
+    assert_eq!(body.saved_searches[0].name, "shabbat");
+    assert_eq!(body.saved_searches[0].query.q, "shabbat");
+    assert!(body.saved_searches[0].email_alerts);
+
+    let resp = client
+        .delete(&format!(
+            "http://0.0.0.0:{}/v1/user/me/saved-search/{}",
+            port, id.0
+        ))
+        .login()
+        .send()
+        .await?
+        .error_for_status()?;
+
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+
+    let body = list(&client, port).await?;
+
+    assert!(body.saved_searches.is_empty());
+
+    app.stop(false).await;
+
+    Ok(())
+}
+
+const USER_ID: Uuid = Uuid::from_u128(0x1f241e1b_b537_493f_a230_075cb16315be);
+
+#[actix_rt::test]
+async fn claim_due() -> anyhow::Result<()> {
+    let (_, db) = initialize_db(&[Fixture::User]).await;
+
+    let long_ago = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+
+    // a user without an email.
+    let no_email_id = Uuid::from_u128(0x6c8b7e3a_ee30_11eb_9a03_0242ac130003);
+    sqlx::query(r#"insert into "user" (id, created_at) values ($1, now())"#)
+        .bind(no_email_id)
+        .execute(&db)
+        .await?;
+
+    let query = JigSearchQuery::default();
+
+    let due = saved_search::create(&db, USER_ID, "due", &query, true).await?;
+    let no_alerts = saved_search::create(&db, USER_ID, "no alerts", &query, false).await?;
+    let no_email = saved_search::create(&db, no_email_id, "no email", &query, true).await?;
+    saved_search::create(&db, USER_ID, "not due", &query, true).await?;
+
+    sqlx::query("update user_saved_search set last_checked_at = $2 where id = any($1)")
+        .bind(vec![due.0, no_alerts.0, no_email.0])
+        .bind(long_ago)
+        .execute(&db)
+        .await?;
+
+    let cutoff = Utc::now() - Duration::seconds(config::SAVED_SEARCH_DIGEST_PERIOD_SECS);
+
+    let claimed = saved_search::claim_due(&db, cutoff, 10).await?;
+
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].id, due);
+    assert_eq!(claimed[0].email, "test@test.test");
+    assert_eq!(claimed[0].since, long_ago);
+
+    // it's been checked now.
+    assert!(saved_search::claim_due(&db, cutoff, 10).await?.is_empty());
+
+    saved_search::unclaim(&db, due, claimed[0].since).await?;
+
+    let claimed = saved_search::claim_due(&db, cutoff, 10).await?;
+
+    assert_eq!(claimed.len(), 1);
+    assert_eq!(claimed[0].id, due);
+
+    Ok(())
+}
+
+#[actix_rt::test]
+async fn new_jigs_mark_sent() -> anyhow::Result<()> {
+    let (_, db) = initialize_db(&[Fixture::User, Fixture::Jig]).await;
+
+    let id = saved_search::create(&db, USER_ID, "new", &JigSearchQuery::default(), true).await?;
+
+    let since = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+
+    // published after `since`.
+    let first = Uuid::from_u128(0xce4edd68_d4a8_11eb_b8bc_0242ac130003);
+    let second = Uuid::from_u128(0xbdc17474_d4a8_11eb_b8bc_0242ac130003);
+    // scheduled to be published, and never published.
+    let scheduled = Uuid::from_u128(0xd52b9ff8_cd74_11eb_8dc1_b760927dc672);
+    let unpublished = Uuid::from_u128(0x0cc084bc_7c83_11eb_9f77_e3218dffb008);
+
+    let hits = [first, scheduled, second, unpublished];
+
+    let ids = |jigs: Vec<(JigId, String)>| jigs.into_iter().map(|(id, _)| id.0).collect::<Vec<_>>();
+
+    // in the order of the hits.
+    assert_eq!(
+        ids(saved_search::new_jigs(&db, id, &hits, since).await?),
+        vec![first, second]
+    );
+
+    saved_search::mark_sent(&db, id, &[first]).await?;
+
+    assert_eq!(
+        ids(saved_search::new_jigs(&db, id, &hits, since).await?),
+        vec![second]
+    );
+
+    // marking them again is fine.
+    saved_search::mark_sent(&db, id, &[first, second]).await?;
+
+    assert!(saved_search::new_jigs(&db, id, &hits, since)
+        .await?
+        .is_empty());
+
+    // jigs published before the search was last checked aren't new.
+    let other =
+        saved_search::create(&db, USER_ID, "other", &JigSearchQuery::default(), true).await?;
+
+    assert!(saved_search::new_jigs(&db, other, &hits, Utc::now())
+        .await?
+        .is_empty());
+
+    Ok(())
+}
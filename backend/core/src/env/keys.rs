@@ -37,6 +37,12 @@ pub mod s3 {
     pub const DISABLE: &str = "S3_LOCAL_DISABLE_CLIENT";
 }
 
+pub mod local_storage {
+    /// Directory to store media in, instead of S3 / Google Cloud Storage. Only used on `local`.
+    /// Is optional. If set, media is stored in (and served from) this directory, and uploads are processed as soon as they're uploaded.
+    pub const DIRECTORY: &str = "LOCAL_STORAGE_DIR";
+}
+
 pub mod email {
     /// Disable email client locally (avoiding the warnings for missing secrets)
     /// If specified in a way that maps to `true` (currently "true", "1", "y"), all mailing related services,
@@ -64,6 +70,10 @@ pub mod email {
     /// Is optional. If missing, password resetting will be disabled,
     /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
     pub const PASSWORD_RESET_TEMPLATE: &str = "EMAIL_PASSWORD_RESET_TEMPLATE";
+
+    /// Email client template ID for digests of new jigs matching saved searches.
+    /// Is optional. If missing, saved search digests won't be sent.
+    pub const SAVED_SEARCH_DIGEST_TEMPLATE: &str = "EMAIL_SAVED_SEARCH_DIGEST_TEMPLATE";
 }
 
 #[cfg(feature = "db")]
@@ -11,6 +11,7 @@ use std::str::FromStr;
 use std::{
     convert::TryInto,
     env::VarError,
+    path::PathBuf,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -180,6 +181,18 @@ pub struct S3Settings {
     pub secret_access_key: String,
 }
 
+/// Settings for storing media in a local directory, instead of S3 / Google Cloud Storage.
+pub struct LocalStorageSettings {
+    /// The target the API is running for, media can only be stored locally when it's [`RemoteTarget::Local`].
+    pub remote_target: RemoteTarget,
+
+    /// The directory media is stored in.
+    pub directory: PathBuf,
+
+    /// The url of the API, which serves the stored media and accepts uploads.
+    pub api_url: String,
+}
+
 /// Settings to initialize a algolia client.
 #[derive(Clone, Debug)]
 pub struct AlgoliaSettings {
@@ -231,6 +244,10 @@ pub struct EmailClientSettings {
     /// Is optional. If missing, password resetting will be disabled,
     /// all related routes will return "501 - Not Implemented" and a warning will be emitted.
     pub password_reset_template: Option<String>,
+
+    /// Email client template ID for digests of new jigs matching saved searches.
+    /// Is optional. If missing, saved search digests won't be sent.
+    pub saved_search_digest_template: Option<String>,
 }
 
 // TODO: unify google services clients' auth tokens and project_id requirements
@@ -423,6 +440,25 @@ impl SettingsManager {
         }
     }
 
+    /// Load the settings for storing media locally.
+    ///
+    /// Only available on `local`, where it takes priority over s3.
+    pub async fn local_storage_settings(&self) -> anyhow::Result<Option<LocalStorageSettings>> {
+        if self.remote_target != RemoteTarget::Local {
+            return Ok(None);
+        }
+
+        let directory = self
+            .get_optional_secret(keys::local_storage::DIRECTORY)
+            .await?;
+
+        Ok(directory.map(|directory| LocalStorageSettings {
+            remote_target: self.remote_target,
+            directory: directory.into(),
+            api_url: self.remote_target.api_url(),
+        }))
+    }
+
     /// Load the key required for initializing sentry (for the api)
     pub async fn sentry_api_key(&self) -> anyhow::Result<Option<String>> {
         self.get_optional_secret(keys::SENTRY_DSN_API)
@@ -528,6 +564,10 @@ impl SettingsManager {
             .get_varying_secret(keys::email::PASSWORD_RESET_TEMPLATE)
             .await?;
 
+        let saved_search_digest_template = self
+            .get_varying_secret(keys::email::SAVED_SEARCH_DIGEST_TEMPLATE)
+            .await?;
+
         let (api_key, sender_email) = match (api_key, sender_email) {
             (Some(api_key), Some(sender_email)) => (api_key, sender_email),
             _ => return Ok(None),
@@ -538,6 +578,7 @@ impl SettingsManager {
             sender_email,
             signup_verify_template,
             password_reset_template,
+            saved_search_digest_template,
         }))
     }
 
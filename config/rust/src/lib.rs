@@ -27,6 +27,21 @@ pub const IMAGE_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 10; // 10 MB
 // Animations are reasonably expected to be larger than normal images?
 pub const ANIMATION_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 15; // 15 MB
 
+// spritesheets are played as WebGL textures, which can't (portably) be bigger than this on either side
+pub const SPRITESHEET_MAX_SIZE: u32 = 4096;
+
+// uploaded audio can be any format ffmpeg reads (like WAV), which can be much bigger than the MP3 it becomes
+pub const AUDIO_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 30; // 30 MB
+
+// how many points the waveforms of uploaded audio have
+pub const AUDIO_WAVEFORM_POINTS: usize = 100;
+
+// how long each `ffmpeg` run processing uploaded audio can take before it's killed
+pub const AUDIO_PROCESSING_TIMEOUT_SECS: u64 = 60;
+
+// JIG archives include every image and audio file the JIG uses
+pub const JIG_ARCHIVE_BODY_SIZE_LIMIT: usize = 1024 * 1024 * 100; // 100 MB
+
 pub const REMOTE_DB_USER: &str = "postgres";
 pub const REMOTE_DB_NAME: &str = "jicloud";
 pub const SQL_PROXY_PORT: u16 = 6432; //must match the port number in build-utils/package.json where cloud-sql-proxy is launched
@@ -37,6 +52,50 @@ pub const DB_INSTANCE_RELEASE: &str = "ji-cloud:europe-west1:ji-cloud-002";
 
 pub const MEDIA_UPLOAD_TIMEOUT_SECS: u64 = 300;
 
+// how often unused media is garbage collected, and how old it has to be first
+pub const MEDIA_GC_INTERVAL_SECS: u64 = 60 * 60; // 1 hour
+pub const MEDIA_GC_GRACE_PERIOD_SECS: i64 = 60 * 60 * 24 * 7; // 7 days
+
+// how many of each kind of unused media the garbage collector deletes at a time
+pub const MEDIA_GC_BATCH_SIZE: i64 = 100;
+
+// how often scheduled drafts are checked for being due
+pub const SCHEDULER_INTERVAL_SECS: u64 = 30;
+
+// a player's plays of a jig only count once in this long
+pub const JIG_PLAY_DEDUPE_SECS: i64 = 60 * 60; // 1 hour
+
+// saved search digests look at (at most) this many of the most relevant results
+pub const SAVED_SEARCH_MAX_HITS: u16 = 1000;
+
+// search suggestions returned when no limit is given, and the most that can be asked for
+pub const SEARCH_SUGGEST_DEFAULT_LIMIT: u16 = 10;
+pub const SEARCH_SUGGEST_MAX_LIMIT: u16 = 50;
+
+// related jigs/images returned when no limit is given, and the most that can be asked for
+pub const RELATED_DEFAULT_LIMIT: u16 = 10;
+pub const RELATED_MAX_LIMIT: u16 = 50;
+
+// how many bits (out of 64) the perceptual hashes of two images can differ by
+// for them to be similar, and for them to be near-duplicates (re-saved or resized copies)
+pub const IMAGE_SIMILAR_MAX_DISTANCE: u32 = 10;
+pub const IMAGE_DUPLICATE_MAX_DISTANCE: u32 = 4;
+
+// near-duplicate images reported when no limit is given, and the most that can be asked for
+pub const IMAGE_DUPLICATES_DEFAULT_LIMIT: u16 = 100;
+pub const IMAGE_DUPLICATES_MAX_LIMIT: u16 = 1000;
+
+// how many recent searches are kept per user
+pub const RECENT_SEARCH_LIMIT: i64 = 50;
+
+// how often saved searches are checked for being due a digest, and how often each one gets a digest (at most)
+pub const SAVED_SEARCH_DIGEST_INTERVAL_SECS: u64 = 60 * 60; // 1 hour
+pub const SAVED_SEARCH_DIGEST_PERIOD_SECS: i64 = 60 * 60 * 24 * 7; // 7 days
+
+// how far before its last check a saved search looks for newly published jigs, to catch the ones
+// that were published by then but hadn't made it into the search index yet
+pub const SAVED_SEARCH_SYNC_GRACE_SECS: i64 = 60 * 60 * 24; // 1 day
+
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum RemoteTarget {
     Local,
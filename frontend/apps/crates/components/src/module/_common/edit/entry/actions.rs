@@ -1,6 +1,8 @@
 use crate::module::_common::edit::history::state::HistoryState;
 use super::state::*;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+use std::cell::{Cell, RefCell};
+use std::convert::TryFrom;
 use shared::{
     api::endpoints::{ApiEndpoint, self, jig::module::*}, 
     domain::{
@@ -104,7 +106,7 @@ where
 pub type HistoryStateImpl<RawData> = HistoryState<RawData, Box<dyn Fn(RawData)>, Box<dyn Fn(RawData)>>;
 //pub type HistorySaveFn<RawData> = impl Fn(RawData);
 
-pub fn save_history<RawData, Mode, Step>(skip_for_debug: bool, save_loader: Rc<AsyncLoader>, jig_id: JigId, module_id: ModuleId) -> Box<dyn Fn(RawData)>
+pub fn save_history<RawData, Mode, Step>(skip_for_debug: bool, save_loader: Rc<AsyncLoader>, jig_id: JigId, module_id: ModuleId, revision: Rc<Cell<Option<u32>>>, history: Weak<RefCell<Option<Rc<HistoryStateImpl<RawData>>>>>) -> Box<dyn Fn(RawData)>
 where
     RawData: BodyExt<Mode, Step> + 'static,
     Mode: ModeExt + 'static,
@@ -112,12 +114,14 @@ where
 {
     Box::new(move |raw_data:RawData| {
         if !skip_for_debug {
-            save(raw_data, save_loader.clone(), jig_id, module_id);
+            save(raw_data, save_loader.clone(), jig_id, module_id, revision.clone(), history.clone());
         }
     })
 }
 
-pub fn save<RawData, Mode, Step>(raw_data: RawData, save_loader: Rc<AsyncLoader>, jig_id: JigId, module_id: ModuleId)
+/// Saves the module, conditional on the revision it was loaded at (if known)
+/// If someone else saved it in the meantime, the history is handed the server's version
+pub fn save<RawData, Mode, Step>(raw_data: RawData, save_loader: Rc<AsyncLoader>, jig_id: JigId, module_id: ModuleId, revision: Rc<Cell<Option<u32>>>, history: Weak<RefCell<Option<Rc<HistoryStateImpl<RawData>>>>>)
 where
     RawData: BodyExt<Mode, Step> + 'static ,
     Mode: ModeExt + 'static,
@@ -135,8 +139,45 @@ where
             index: None,
             body: Some(body), 
         });
-        api_with_auth_empty::<EmptyError, _>(&path, Update::METHOD, req).await; //.expect_ji("error saving module!");
-        log::info!("SAVED!");
+
+        //a save that's replaced while in flight may or may not have reached the server
+        //so the revision stays unknown until this one finishes
+        let current = match revision.take() {
+            Some(current) => Some(current),
+            None => {
+                //the last save was cut off, ask the server where it ended up
+                let path = Get::PATH
+                    .replace("{id}",&jig_id.0.to_string())
+                    .replace("{module_id}",&module_id.0.to_string());
+
+                api_with_auth::<ModuleResponse, EmptyError, ()>(&path, Get::METHOD, None).await
+                    .ok()
+                    .map(|resp| resp.module.revision)
+            }
+        };
+
+        match api_with_auth_if_match::<ModuleResponse, _>(&path, Update::METHOD, current, req).await {
+            Ok(new_revision) => {
+                revision.set(new_revision);
+                log::info!("SAVED!");
+            },
+            Err(Some(resp)) => {
+                log::warn!("module was changed elsewhere, loading the latest version");
+                revision.set(Some(resp.module.revision));
+                if let (Ok(server_raw), Some(history)) = (RawData::try_from(resp.module.body), history.upgrade()) {
+                    if let Some(history) = history.borrow().as_ref() {
+                        history.on_conflict(server_raw);
+                    }
+                }
+            },
+            Err(None) => {
+                //the server turned it down, so it's still at the revision this save was made against
+                log::error!("failed to save module");
+                if revision.get().is_none() {
+                    revision.set(current);
+                }
+            }
+        }
     });
 }
 //doesn't compile, gotta box for now: https://github.com/rust-lang/rust/issues/65442
@@ -10,7 +10,7 @@ use uuid::Uuid;
 use dominator::{DomBuilder, Dom, html, events, clone, apply_methods, with_node};
 use wasm_bindgen::prelude::*;
 use web_sys::AudioContext;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::convert::{TryFrom, TryInto};
 use std::future::Future;
@@ -61,7 +61,8 @@ where
     pub(super) opts: StateOpts<RawData>,
     pub(super) raw_loader: AsyncLoader,
     pub(super) save_loader: Rc<AsyncLoader>,
-    pub(super) history: RefCell<Option<Rc<HistoryStateImpl<RawData>>>>,
+    pub(super) history: Rc<RefCell<Option<Rc<HistoryStateImpl<RawData>>>>>,
+    pub(super) revision: Rc<Cell<Option<u32>>>,
     pub(super) raw_loaded: Mutable<bool>,
     pub(super) page_body_switcher: AsyncLoader,
     pub(super) reset_from_history_loader: AsyncLoader,
@@ -154,7 +155,8 @@ where
             opts,
             jig: RefCell::new(None),
             phase: Mutable::new(Rc::new(Phase::Init)), 
-            history: RefCell::new(None),
+            history: Rc::new(RefCell::new(None)),
+            revision: Rc::new(Cell::new(None)),
             raw_loaded: Mutable::new(false),
             raw_loader: AsyncLoader::new(),
             save_loader: Rc::new(AsyncLoader::new()),
@@ -207,7 +209,11 @@ where
                         display_score: false,
                         theme: ThemeId::default(),
                         audio_background: None,
-                        audio_effects: AudioEffects::default() 
+                        audio_effects: AudioEffects::default(),
+                        revision: 0,
+                        plays: 0,
+                        likes: 0,
+                        views: 0,
                     });
                 }
 
@@ -227,6 +233,7 @@ where
 
                         match api_with_auth::<ModuleResponse, EmptyError, ()>(&path, Get::METHOD, None).await {
                             Ok(resp) => {
+                                _self.revision.set(Some(resp.module.revision));
                                 let body = resp.module.body;
                                 (body.try_into().unwrap_ji(), InitSource::Load)
                             },
@@ -244,6 +251,8 @@ where
                         _self.save_loader.clone(),
                         _self.opts.jig_id.clone(),
                         _self.opts.module_id.clone(),
+                        _self.revision.clone(),
+                        Rc::downgrade(&_self.history),
                     ),
                     Self::reset_from_history(_self.clone(), init_from_raw.clone())
                 ));
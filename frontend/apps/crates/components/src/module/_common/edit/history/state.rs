@@ -112,6 +112,26 @@ where
         self.push(value);
     }
 
+    /// Called when a save was rejected because someone else changed it first
+    /// The server's value becomes the current state (without saving it back)
+    /// but the local changes are kept one undo away, so no work is lost
+    /// (undoing will save them over the server's value)
+    pub fn on_conflict(&self, server_value:T) {
+        let mut history = self.history.lock_mut();
+        let mut cursor = self.cursor.lock_mut();
+
+        //Delete everything after this point in history
+        while history.len() > 0 && *cursor < history.len()-1 {
+            history.remove(history.len()-1);
+        }
+
+        history.push_cloned(server_value.clone());
+
+        *cursor += 1;
+
+        (self.on_undoredo)(server_value);
+    }
+
     /// Helper to save without pushing new state
     pub fn save_current_modify<M: FnOnce(&mut T)>(&self, modify:M) {
         let mut value = self.get_current();
@@ -215,7 +215,11 @@ where
                             display_score: false,
                             theme: ThemeId::default(),
                             audio_background: None,
-                            audio_effects: AudioEffects::default() 
+                            audio_effects: AudioEffects::default(),
+                            revision: 0,
+                            plays: 0,
+                            likes: 0,
+                            views: 0,
                         })
                     } else {
                         let path = endpoints::jig::Get::PATH.replace("{id}",&_self.opts.jig_id.0.to_string());
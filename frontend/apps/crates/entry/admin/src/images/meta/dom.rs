@@ -65,6 +65,7 @@ impl ImageMetaPage {
                                     is_premium: None,
                                     is_published: None,
                                     kind: None,
+                                    transliterate: false,
                                 };
                                 let route:String = Route::Admin(AdminRoute::ImageSearch(Some(query))).into();
                                 dominator::routing::go_to_url(&route);
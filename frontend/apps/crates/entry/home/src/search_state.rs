@@ -54,6 +54,8 @@ impl SearchSelected {
             affiliations: self.affiliations.lock_ref().to_owned().into_iter().collect(),
             categories: self.categories.lock_ref().to_owned().into_iter().collect(),
             goals: self.goals.lock_ref().to_owned().into_iter().collect(),
+            language: self.language.lock_ref().to_owned(),
+            transliterate: true,
             page: Some(0),
             ..Default::default()
         }
@@ -44,6 +44,10 @@ pub fn get_jig() -> Jig {
         theme: ThemeId::Chalkboard,
         audio_background: None,
         audio_effects: AudioEffects::default(),
+        revision: 0,
+        plays: 0,
+        likes: 0,
+        views: 0,
     }
 
 }
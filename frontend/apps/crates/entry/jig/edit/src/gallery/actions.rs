@@ -26,6 +26,9 @@ async fn load_jigs(state: Rc<State>) {
         is_published,
         author_id: Some(UserOrMe::Me),
         page: None,
+        sort: None,
+        language: None,
+        module_kinds: Vec::new(),
     });
 
     match api_with_auth::<JigBrowseResponse, EmptyError, _>(&Browse::PATH, Browse::METHOD, req).await {
@@ -0,0 +1,38 @@
+use std::rc::Rc;
+
+use dominator::clone;
+use shared::{
+    api::{ApiEndpoint, endpoints::class},
+    domain::class::assignment::{AssignmentJoinRequest, AssignmentJoinResponse},
+    error::EmptyError,
+};
+use utils::{prelude::*, routes::{JigRoute, Route}, storage};
+
+use super::state::State;
+
+pub fn join(state: Rc<State>) {
+    state.error.set(false);
+
+    let req = AssignmentJoinRequest {
+        code: state.code.borrow().trim().to_string(),
+        name: state.name.borrow().trim().to_string(),
+    };
+
+    if req.code.is_empty() || req.name.is_empty() {
+        state.error.set(true);
+        return;
+    }
+
+    state.loader.load(clone!(state => async move {
+        match api_no_auth::<AssignmentJoinResponse, EmptyError, _>(class::assignment::Join::PATH, class::assignment::Join::METHOD, Some(req)).await {
+            Ok(resp) => {
+                storage::save_csrf_token(&resp.csrf);
+
+                Route::Jig(JigRoute::Play(resp.jig_id, None, Some(resp.assignment_id))).redirect();
+            },
+            Err(_) => {
+                state.error.set(true);
+            },
+        }
+    }));
+}
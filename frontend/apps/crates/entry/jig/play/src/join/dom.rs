@@ -0,0 +1,51 @@
+use std::rc::Rc;
+
+use dominator::{Dom, clone, html};
+use utils::events;
+
+use super::{actions, state::State};
+
+const STR_CODE_LABEL: &'static str = "Class code";
+const STR_NAME_LABEL: &'static str = "Your name";
+const STR_SUBMIT: &'static str = "Start";
+
+pub fn render(state: Rc<State>) -> Dom {
+    html!("empty-fragment", {
+        .child(html!("window-loader-block", {
+            .property_signal("visible", state.loader.is_loading())
+        }))
+        .child(html!("jig-play-join", {
+            .property_signal("error", state.error.signal())
+            .children(&mut [
+                html!("input-wrapper", {
+                    .property("slot", "code")
+                    .property("label", STR_CODE_LABEL)
+                    .child(html!("input", {
+                        .event(clone!(state => move |evt: events::Input| {
+                            state.error.set(false);
+                            *state.code.borrow_mut() = evt.value().unwrap_or_default();
+                        }))
+                    }))
+                }),
+                html!("input-wrapper", {
+                    .property("slot", "name")
+                    .property("label", STR_NAME_LABEL)
+                    .child(html!("input", {
+                        .event(clone!(state => move |evt: events::Input| {
+                            *state.name.borrow_mut() = evt.value().unwrap_or_default();
+                        }))
+                    }))
+                }),
+                html!("button-rect", {
+                    .property("slot", "submit")
+                    .property("color", "red")
+                    .property("size", "medium")
+                    .text(STR_SUBMIT)
+                    .event(clone!(state => move |_: events::Click| {
+                        actions::join(Rc::clone(&state));
+                    }))
+                }),
+            ])
+        }))
+    })
+}
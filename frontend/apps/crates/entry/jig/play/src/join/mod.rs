@@ -0,0 +1,3 @@
+pub mod dom;
+pub mod state;
+mod actions;
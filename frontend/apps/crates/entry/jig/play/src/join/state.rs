@@ -0,0 +1,22 @@
+use std::cell::RefCell;
+
+use dominator_helpers::futures::AsyncLoader;
+use futures_signals::signal::Mutable;
+
+pub struct State {
+    pub loader: AsyncLoader,
+    pub code: RefCell<String>,
+    pub name: RefCell<String>,
+    pub error: Mutable<bool>,
+}
+
+impl State {
+    pub fn new() -> Self {
+        Self {
+            loader: AsyncLoader::new(),
+            code: RefCell::new(String::new()),
+            name: RefCell::new(String::new()),
+            error: Mutable::new(false),
+        }
+    }
+}
@@ -8,6 +8,7 @@ static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 mod templates;
 mod router;
 mod player;
+mod join;
 mod debug;
 
 use cfg_if::cfg_if;
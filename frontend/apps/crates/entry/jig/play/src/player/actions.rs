@@ -2,8 +2,8 @@ use std::rc::Rc;
 
 use dominator::clone;
 use futures_signals::signal::SignalExt;
-use shared::{api::{ApiEndpoint, endpoints::jig}, domain::jig::JigResponse, error::EmptyError};
-use utils::{iframe::{IframeAction, JigToModuleMessage, ModuleToJigMessage}, prelude::{SETTINGS, api_with_auth}, routes::Route, unwrap::UnwrapJiExt};
+use shared::{api::{ApiEndpoint, endpoints::{class, jig}}, domain::{CreateResponse, jig::{JigResponse, player::{JigPlayerModuleResultRequest, JigPlayerSessionId}}}, error::EmptyError};
+use utils::{iframe::{IframeAction, JigToModuleMessage, ModuleResult, ModuleToJigMessage}, prelude::{SETTINGS, api_with_auth, api_with_auth_empty}, routes::Route, unwrap::UnwrapJiExt};
 use wasm_bindgen_futures::spawn_local;
 use super::{timer::Timer, state::State};
 
@@ -17,12 +17,93 @@ pub fn load_jig(state: Rc<State>) {
                 // state.active_module.set(Some(resp.jig.modules[0].clone()));
                 state.jig.set(Some(resp.jig));
                 state.active_module.set(1);
+                start_session(Rc::clone(&state));
+                count_play(Rc::clone(&state));
             },
             Err(_) => {},
         }
     }));
 }
 
+pub fn start_session(state: Rc<State>) {
+    spawn_local(clone!(state => async move {
+        let (path, method) = match state.assignment_id {
+            Some(_) => (class::assignment::CreateSession::PATH.to_string(), class::assignment::CreateSession::METHOD),
+            None => (jig::player::CreateSession::PATH.replace("{id}", &state.jig_id.0.to_string()), jig::player::CreateSession::METHOD),
+        };
+
+        match api_with_auth::<CreateResponse<JigPlayerSessionId>, EmptyError, ()>(&path, method, None).await {
+            Ok(resp) => {
+                state.session_id.set(Some(resp.id));
+            },
+            Err(_) => {
+                log::error!("failed to start play session");
+            },
+        }
+    }));
+}
+
+/// Counts a play of the jig, and a view when it's a user (rather than an assignment student) playing it
+pub fn count_play(state: Rc<State>) {
+    spawn_local(clone!(state => async move {
+        let path = jig::Play::PATH.replace("{id}", &state.jig_id.0.to_string());
+
+        if let Err(_) = api_with_auth_empty::<EmptyError, ()>(&path, jig::Play::METHOD, None).await {
+            log::error!("failed to count play");
+        }
+
+        if state.assignment_id.is_none() {
+            let path = jig::View::PATH.replace("{id}", &state.jig_id.0.to_string());
+
+            if let Err(_) = api_with_auth_empty::<EmptyError, ()>(&path, jig::View::METHOD, None).await {
+                log::error!("failed to count view");
+            }
+        }
+    }));
+}
+
+pub fn send_module_result(state: Rc<State>, result: ModuleResult) {
+    let session_id = match state.session_id.get() {
+        Some(session_id) => session_id,
+        None => return,
+    };
+
+    let module_id = match &*state.jig.lock_ref() {
+        Some(jig) => match jig.modules.get(state.active_module.get()) {
+            Some(module) => module.id,
+            None => return,
+        },
+        None => return,
+    };
+
+    spawn_local(clone!(state => async move {
+        let (path, method) = match state.assignment_id {
+            Some(_) => {
+                let path = class::assignment::CreateModuleResult::PATH
+                    .replace("{session_id}", &session_id.0.to_string());
+                (path, class::assignment::CreateModuleResult::METHOD)
+            },
+            None => {
+                let path = jig::player::CreateModuleResult::PATH
+                    .replace("{id}", &state.jig_id.0.to_string())
+                    .replace("{session_id}", &session_id.0.to_string());
+                (path, jig::player::CreateModuleResult::METHOD)
+            },
+        };
+
+        let req = JigPlayerModuleResultRequest {
+            module_id,
+            score: result.score,
+            attempts: result.attempts,
+            time_spent: result.time_spent,
+        };
+
+        if let Err(_) = api_with_auth_empty::<EmptyError, _>(&path, method, Some(req)).await {
+            log::error!("failed to record module result");
+        }
+    }));
+}
+
 pub fn start_timer(state: Rc<State>, time: u32) {
     let timer = Timer::new(time);
 
@@ -65,5 +146,8 @@ pub fn on_iframe_message(state: Rc<State>, message: ModuleToJigMessage) {
         ModuleToJigMessage::StartTimer(time) => {
             start_timer(Rc::clone(&state), time);
         },
+        ModuleToJigMessage::ModuleResult(result) => {
+            send_module_result(Rc::clone(&state), result);
+        },
     };
 }
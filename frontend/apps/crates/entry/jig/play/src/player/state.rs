@@ -2,7 +2,7 @@ use std::{cell::RefCell, rc::Rc};
 
 use awsm_web::loaders::helpers::AsyncLoader;
 use futures_signals::signal::Mutable;
-use shared::domain::jig::{Jig, JigId, module::ModuleId};
+use shared::domain::{class::assignment::AssignmentId, jig::{Jig, JigId, module::ModuleId, player::JigPlayerSessionId}};
 use serde::{Serialize, Deserialize};
 use web_sys::HtmlIFrameElement;
 
@@ -11,27 +11,32 @@ use super::timer::Timer;
 pub struct State {
     pub is_teacher: bool,
     pub jig_id: JigId,
+    pub assignment_id: Option<AssignmentId>,
     pub jig: Mutable<Option<Jig>>,
     pub loader: AsyncLoader,
     pub active_module: Mutable<usize>,
     pub module_id: Mutable<Option<ModuleId>>, // needed?
     pub timer: Mutable<Option<Timer>>,
     pub points: Mutable<u32>,
+    pub session_id: Mutable<Option<JigPlayerSessionId>>,
     pub iframe: Rc<RefCell<Option<HtmlIFrameElement>>>,
 
 }
 
 impl State {
-    pub fn new(jig_id: JigId, module_id: Option<ModuleId>) -> Self {
+    pub fn new(jig_id: JigId, module_id: Option<ModuleId>, assignment_id: Option<AssignmentId>) -> Self {
         Self {
-            is_teacher: true,
+            // students joining an assignment don't get the teacher's sidebar
+            is_teacher: assignment_id.is_none(),
             jig_id,
+            assignment_id,
             jig: Mutable::new(None),
             loader: AsyncLoader::new(),
             active_module: Mutable::new(0),
             module_id: Mutable::new(None),
             timer: Mutable::new(None),
             points: Mutable::new(0),
+            session_id: Mutable::new(None),
             // background_music: Mutable::new(None),
             iframe: Rc::new(RefCell::new(None)),
         }
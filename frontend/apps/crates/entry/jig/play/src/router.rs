@@ -2,7 +2,10 @@ use utils::routes::{Route, JigRoute};
 use std::rc::Rc;
 use futures_signals::signal::{SignalExt, Signal};
 use dominator::{Dom, html};
-use crate::player::{dom::render as player_render, state::State as PlayerState};
+use crate::{
+    join::{dom::render as join_render, state::State as JoinState},
+    player::{dom::render as player_render, state::State as PlayerState},
+};
 
 pub struct Router {
 }
@@ -23,7 +26,8 @@ impl Router {
                 match route {
                     Route::Jig(route) => {
                         match route {
-                            JigRoute::Play(jig_id, module_id) => Some(player_render(Rc::new(PlayerState::new(jig_id, module_id)))),
+                            JigRoute::Play(jig_id, module_id, assignment_id) => Some(player_render(Rc::new(PlayerState::new(jig_id, module_id, assignment_id)))),
+                            JigRoute::Join => Some(join_render(Rc::new(JoinState::new()))),
                             _ => None
                         }
                     },
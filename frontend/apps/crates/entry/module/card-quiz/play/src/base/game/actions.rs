@@ -27,7 +27,14 @@ impl Game {
     }
 
     pub fn check(&self, pair_id: usize) -> bool {
-        pair_id == self.current.lock_ref().target.pair_id
+        let is_correct = pair_id == self.current.lock_ref().target.pair_id;
+
+        self.base.attempts.set(self.base.attempts.get() + 1);
+        if is_correct {
+            self.base.score.set(self.base.score.get() + 1);
+        }
+
+        is_correct
     }
 
 }
@@ -11,6 +11,7 @@ use wasm_bindgen::prelude::*;
 use std::collections::HashMap;
 use components::collision::stickers_traces::pixels::{get_hit_index, StickerHitSource, StickerBoundsKind, debug_render_hit_trace};
 use wasm_bindgen_futures::spawn_local;
+use utils::iframe::ModuleResult;
 
 cfg_if::cfg_if! {
     if #[cfg(debug_assertions)] {
@@ -52,11 +53,23 @@ impl PlayState {
     pub fn evaluate(&self, item: Rc<InteractiveItem>) {
         let game = self.game.clone();
 
+        let items:Vec<Rc<InteractiveItem>> = self.items
+            .iter()
+            .filter_map(|item| {
+                match item {
+                    PlayItem::Interactive(item) => Some(item.clone()),
+                    _ => None
+                }
+            })
+            .collect();
+
         spawn_local(async move {
             let mut move_back = false;
             if let Some(target_index) = item.target_index.borrow().as_ref() {
                 let target_index = *target_index;
 
+                game.base.attempts.set(game.base.attempts.get() + 1);
+
                 let mut is_correct = false;
 
                 if let Some(hit_source) = item.get_hit_source(Some(SourceTransformOverride::Current)) {
@@ -72,6 +85,11 @@ impl PlayState {
                         if index == target_index {
                             log::info!("GOT A WINNER!");
                             is_correct = true;
+
+                            // only the drop that completes the last item sends the result
+                            if !item.completed.replace(true) && items.iter().all(|item| item.completed.get()) {
+                                ModuleResult::new(Some(items.len() as u32), game.base.attempts.get(), game.base.started_at).send_to_jig();
+                            }
                         }
                     }
                 }
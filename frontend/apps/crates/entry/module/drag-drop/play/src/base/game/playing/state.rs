@@ -1,4 +1,4 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::{Cell, RefCell}};
 use crate::base::game::state::*;
 use dominator::clone;
 use futures_signals::{
@@ -91,6 +91,8 @@ pub struct InteractiveItem {
     pub drag: Mutable<Option<Rc<Drag>>>,
     pub size: Mutable<Option<(f64, f64)>>,
     pub target_index: RefCell<Option<usize>>,
+    /// set once it's been dropped on its target
+    pub completed: Cell<bool>,
 }
 
 pub enum SourceTransformOverride {
@@ -109,6 +111,7 @@ impl InteractiveItem {
             drag: Mutable::new(None),
             size: Mutable::new(None),
             target_index: RefCell::new(None),
+            completed: Cell::new(false),
         })
     }
 
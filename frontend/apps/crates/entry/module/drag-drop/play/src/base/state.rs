@@ -2,7 +2,7 @@ use shared::domain::jig::{Jig, JigId, module::{ModuleId, body::{Instructions, Th
 use components::{audio_mixer::AudioMixer, module::_common::play::prelude::*};
 use utils::prelude::*;
 use web_sys::AudioContext;
-use std::rc::Rc;
+use std::{rc::Rc, cell::Cell};
 
 pub struct Base {
     pub jig_id: JigId,
@@ -15,6 +15,8 @@ pub struct Base {
     pub backgrounds: Backgrounds,
     pub items: Vec<Item>,
     pub target_areas: Vec<TargetArea>,
+    pub started_at: f64,
+    pub attempts: Cell<u32>,
 }
 
 impl Base {
@@ -44,6 +46,8 @@ impl Base {
             backgrounds: content.backgrounds,
             items: content.items,
             target_areas: content.target_areas,
+            started_at: js_sys::Date::now(),
+            attempts: Cell::new(0),
         })
     }
 }
@@ -1,5 +1,6 @@
 use crate::base::state::Base;
 use std::rc::Rc;
+use utils::iframe::ModuleResult;
 
 pub struct Ending {
     pub base: Rc<Base>,
@@ -7,6 +8,9 @@ pub struct Ending {
 
 impl Ending {
     pub fn new(base: Rc<Base>) -> Self {
+        // every card flipped through counts, so there's nothing to score
+        ModuleResult::new(None, base.attempts.get(), base.started_at).send_to_jig();
+
         Self { 
             base,
         }
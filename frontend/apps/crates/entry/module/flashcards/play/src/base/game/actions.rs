@@ -18,6 +18,9 @@ use crate::base::{
 
 impl Game {
     pub fn next(&self) {
+        //each card that's been flipped through counts as viewed
+        self.base.attempts.set(self.base.attempts.get() + 1);
+
         if let Some(next) = get_current(&mut self.deck.borrow_mut(), &mut self.rng.borrow_mut()) {
             self.current.set(next);
         } else {
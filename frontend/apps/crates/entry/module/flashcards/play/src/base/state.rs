@@ -7,7 +7,7 @@ use futures_signals::{
 };
 use std::{
     rc::Rc,
-    cell::RefCell
+    cell::{Cell, RefCell}
 };
 use rand::prelude::*;
 use components::module::{
@@ -34,7 +34,9 @@ pub struct Base {
     pub audio_mixer: AudioMixer,
     pub settings: PlayerSettings,
     pub raw_pairs: Vec<CardPair>,
-    pub phase: Mutable<Phase>
+    pub phase: Mutable<Phase>,
+    pub started_at: f64,
+    pub attempts: Cell<u32>,
 }
 
 #[derive(Clone)]
@@ -70,6 +72,8 @@ impl Base {
             settings: content.player_settings,
             raw_pairs: content.base.pairs,
             phase: Mutable::new(Phase::Init),
+            started_at: js_sys::Date::now(),
+            attempts: Cell::new(0),
         });
 
         _self.phase.set(Phase::Playing(Rc::new(Game::new(_self.clone()))));
@@ -18,7 +18,11 @@ impl CardDrag {
             let mut found_match = false;
 
             if let Some(choice) = choice {
+                let base = &self.game.base;
+                base.attempts.set(base.attempts.get() + 1);
+
                 if choice.pair_id == self.pair_id {
+                    base.score.set(base.score.get() + 1);
                     found_match = true;
                     choice.phase.set(TopPhase::Landed);
                 } else {
@@ -20,7 +20,7 @@ use futures_signals::{
 };
 use std::{
     rc::Rc,
-    cell::RefCell
+    cell::{Cell, RefCell}
 };
 use rand::prelude::*;
 use components::module::{
@@ -47,7 +47,10 @@ pub struct Base {
     pub audio_mixer: AudioMixer,
     pub settings: PlayerSettings,
     pub raw_pairs: Vec<CardPair>,
-    pub phase: Mutable<Phase>
+    pub phase: Mutable<Phase>,
+    pub started_at: f64,
+    pub score: Cell<u32>,
+    pub attempts: Cell<u32>,
 }
 
 #[derive(Clone)]
@@ -83,6 +86,9 @@ impl Base {
             settings: content.player_settings,
             raw_pairs: content.base.pairs,
             phase: Mutable::new(Phase::Init),
+            started_at: js_sys::Date::now(),
+            score: Cell::new(0),
+            attempts: Cell::new(0),
         });
 
         _self.phase.set(Phase::Playing(Game::new(_self.clone())));
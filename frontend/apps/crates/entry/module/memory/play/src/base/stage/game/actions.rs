@@ -34,6 +34,8 @@ pub fn card_click(state: Rc<Base>, id: usize) -> Option<(usize, usize)> {
     }
 }
 pub fn evaluate(state: Rc<Base>, id_1: usize, id_2: usize) {
+    state.attempts.set(state.attempts.get() + 1);
+
     spawn_local(async move {
         if state.pair_lookup[id_1] == id_2 {
             let mut found_pairs = state.found_pairs.borrow_mut();
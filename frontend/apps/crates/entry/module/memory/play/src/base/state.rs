@@ -20,7 +20,7 @@ use futures_signals::{
 };
 use std::{
     rc::Rc,
-    cell::RefCell
+    cell::{Cell, RefCell}
 };
 use rand::prelude::*;
 use components::module::{
@@ -33,6 +33,9 @@ use std::future::Future;
 use futures::future::join_all;
 use gloo_timers::future::TimeoutFuture;
 use components::audio_mixer::AudioMixer;
+use dominator::clone;
+use utils::iframe::ModuleResult;
+use wasm_bindgen_futures::spawn_local;
 
 pub struct Base {
     pub jig_id: JigId,
@@ -46,7 +49,9 @@ pub struct Base {
     pub flip_state: Mutable<FlipState>,
     pub found_pairs: RefCell<Vec<(usize, usize)>>, 
     pub instructions: Instructions,
-    pub audio_mixer: AudioMixer
+    pub audio_mixer: AudioMixer,
+    pub started_at: f64,
+    pub attempts: Cell<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -103,7 +108,7 @@ impl Base {
             cards.shuffle(&mut rng);
         }
 
-        Rc::new(Self {
+        let _self = Rc::new(Self {
             jig_id,
             module_id,
             mode: content.base.mode,
@@ -116,7 +121,18 @@ impl Base {
             found_pairs: RefCell::new(Vec::new()),
             instructions: content.base.instructions,
             audio_mixer,
-        })
+            started_at: js_sys::Date::now(),
+            attempts: Cell::new(0),
+        });
+
+        spawn_local(clone!(_self => async move {
+            if _self.all_cards_ended_future().await {
+                let found_pairs = _self.found_pairs.borrow().len() as u32;
+                ModuleResult::new(Some(found_pairs), _self.attempts.get(), _self.started_at).send_to_jig();
+            }
+        }));
+
+        _self
     }
 
 
@@ -1,4 +1,4 @@
-use std::{rc::Rc, cell::RefCell};
+use std::{rc::Rc, cell::{Cell, RefCell}};
 use crate::base::game::state::*;
 use futures_signals::{
     map_ref,
@@ -15,11 +15,14 @@ use shared::domain::jig::module::body::{
 };
 use web_sys::AudioContext;
 use std::collections::HashSet;
+use utils::iframe::ModuleResult;
 
 pub struct PlayState {
     pub game: Rc<Game>,
     pub traces: Vec<Rc<PlayTrace>>,
-    pub selected_set: RefCell<HashSet<usize>>
+    pub selected_set: RefCell<HashSet<usize>>,
+    pub started_at: f64,
+    pub taps: Cell<u32>,
 }
 
 impl PlayState {
@@ -34,7 +37,9 @@ impl PlayState {
         Rc::new(Self {
             game,
             traces,
-            selected_set: RefCell::new(HashSet::new())
+            selected_set: RefCell::new(HashSet::new()),
+            started_at: js_sys::Date::now(),
+            taps: Cell::new(0),
         })
     }
 
@@ -50,7 +55,8 @@ impl PlayState {
         // mark the selected set
         let mut selected_set = self.selected_set.borrow_mut();
 
-        selected_set.insert(index);
+        let is_new = selected_set.insert(index);
+        self.taps.set(self.taps.get() + 1);
 
         let n_selected = selected_set.len();
 
@@ -69,6 +75,11 @@ impl PlayState {
                 log::warn!("TODO: GOING TO NEXT MODULE!");
             }
         }
+
+        // the result is sent once, when the target (or every trace, if there isn't one) is first reached
+        if is_new && n_selected == n_target.unwrap_or(self.traces.len()) {
+            ModuleResult::new(Some(n_selected as u32), self.taps.get(), self.started_at).send_to_jig();
+        }
     }
 }
 
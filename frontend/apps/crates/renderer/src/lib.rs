@@ -8,6 +8,7 @@ mod init;
 mod render;
 mod texture;
 mod sprite;
+mod spritesheet;
 mod primitive;
 mod material;
 
@@ -4,4 +4,5 @@ pub use crate::material::*;
 pub use crate::primitive::*;
 pub use crate::geom::variants::*;
 pub use crate::sprite::*;
+pub use crate::spritesheet::*;
 pub use crate::texture::*;
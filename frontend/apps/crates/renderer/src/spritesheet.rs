@@ -0,0 +1,56 @@
+/*
+    Spritesheets are played by moving the sprite's texture coordinates
+    over the sheet, one frame at a time, at the animation's frame rate.
+    The frame rate can be scaled with `speed` (e.g. to slow an animation down)
+*/
+use shared::domain::animation::SpritesheetFrames;
+
+pub struct SpritesheetPlayback {
+    pub frames: SpritesheetFrames,
+    pub sheet_width: u32,
+    pub sheet_height: u32,
+    pub is_looping: bool,
+    pub speed: f64,
+    elapsed_ms: f64,
+}
+
+impl SpritesheetPlayback {
+    pub fn new(frames: SpritesheetFrames, sheet_width: u32, sheet_height: u32, is_looping: bool) -> Self {
+        Self {
+            frames,
+            sheet_width,
+            sheet_height,
+            is_looping,
+            speed: 1.0,
+            elapsed_ms: 0.0,
+        }
+    }
+
+    pub fn advance(&mut self, delta_ms: f64) {
+        self.elapsed_ms += delta_ms * self.speed;
+    }
+
+    pub fn restart(&mut self) {
+        self.elapsed_ms = 0.0;
+    }
+
+    pub fn current_frame(&self) -> u32 {
+        self.frames.frame_at(self.elapsed_ms, self.is_looping)
+    }
+
+    /// The current frame's texture coordinates on the sheet,
+    /// as [left, top, width, height] from 0 to 1
+    pub fn uv_rect(&self) -> [f32; 4] {
+        let (x, y) = self.frames.frame_position(self.current_frame(), self.sheet_width);
+
+        let sheet_width = self.sheet_width as f32;
+        let sheet_height = self.sheet_height as f32;
+
+        [
+            x as f32 / sheet_width,
+            y as f32 / sheet_height,
+            self.frames.frame_width as f32 / sheet_width,
+            self.frames.frame_height as f32 / sheet_height,
+        ]
+    }
+}
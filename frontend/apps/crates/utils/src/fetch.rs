@@ -288,6 +288,50 @@ where E: DeserializeOwned + Serialize, Payload: Serialize
     }
 }
 
+/// Like api_with_auth_empty, but only applies the update if the resource is still at `revision` (if given)
+/// On success, returns the new revision from the response's ETag (if there is one)
+/// If someone else updated it first, the error is the resource as it's currently stored
+/// Other errors are None
+pub async fn api_with_auth_if_match<T, Payload>(endpoint: &str, method:Method, revision: Option<u32>, data:Option<Payload>) -> Result<Option<u32>, Option<T>>
+where T: DeserializeOwned + Serialize, Payload: Serialize
+{
+    let (resp, status) = api_with_auth_if_match_status(endpoint, method, revision, data).await;
+
+    side_effect_error(status);
+
+    resp
+}
+pub async fn api_with_auth_if_match_status<T, Payload>(endpoint: &str, method:Method, revision: Option<u32>, data:Option<Payload>) -> (Result<Option<u32>, Option<T>>, u16)
+where T: DeserializeOwned + Serialize, Payload: Serialize
+{
+    let csrf = load_csrf_token().unwrap_ji();
+
+    let if_match = revision.map(|revision| format!("\"{}\"", revision));
+
+    let mut headers = vec![(CSRF_HEADER_NAME, csrf.as_str())];
+
+    if let Some(if_match) = if_match.as_deref() {
+        headers.push(("If-Match", if_match));
+    }
+
+    let (url, data) = api_get_query(endpoint, method, data);
+
+    let res = fetch_with_headers_and_data(&url, method.as_str(), true, &headers, data)
+        .await
+        .unwrap_ji();
+
+    let status = res.status();
+
+    if res.ok() {
+        let etag = res.headers().get("ETag").ok().flatten();
+        (Ok(etag.and_then(|etag| etag.trim_matches('"').parse().ok())), status)
+    } else if status == 412 {
+        (Err(Some(res.json_from_str().await.expect_ji(DESERIALIZE_ERR))), status)
+    } else {
+        (Err(None), status)
+    }
+}
+
 //really just used with login - see https://datatracker.ietf.org/doc/html/rfc7617#section-2
 pub async fn api_with_basic_token<T, E, Payload>(endpoint: &str, user_id:&str, password:&str, method:Method, data:Option<Payload>) -> Result<T, E> 
 where T: DeserializeOwned + Serialize, E: DeserializeOwned + Serialize, Payload: Serialize
@@ -104,4 +104,41 @@ pub enum JigToModuleMessage {
 pub enum ModuleToJigMessage {
     AddPoints(u32),
     StartTimer(u32),
+    ModuleResult(ModuleResult),
+}
+
+/// Sent by a module when it's been played through, to be recorded in the play session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleResult {
+    /// `None` for modules that can't be scored (only played through)
+    pub score: Option<u32>,
+    pub attempts: u32,
+    /// in seconds
+    pub time_spent: u32,
+}
+
+impl ModuleResult {
+    /// Creates a result, with the time spent measured from `started_at` (as given by `js_sys::Date::now()`)
+    pub fn new(score: Option<u32>, attempts: u32, started_at: f64) -> Self {
+        let time_spent = ((js_sys::Date::now() - started_at) / 1000.0).max(0.0) as u32;
+
+        Self {
+            score,
+            attempts,
+            time_spent,
+        }
+    }
+
+    /// Posts the result to the parent (jig player) window.
+    /// Does nothing if the module isn't running in an iframe
+    pub fn send_to_jig(self) {
+        let window = web_sys::window().unwrap_ji();
+
+        if let Ok(Some(parent)) = window.parent() {
+            if parent != window {
+                let msg = IframeAction::new(ModuleToJigMessage::ModuleResult(self));
+                let _ = parent.post_message(&msg.into(), "*");
+            }
+        }
+    }
 }
@@ -1,6 +1,6 @@
 use web_sys::{window, ImageData, HtmlImageElement, HtmlCanvasElement, CanvasRenderingContext2d, Blob};
 use awsm_web::canvas::{get_2d_context, Canvas2dContextOptions, CanvasToBlobFuture};
-use crate::{prelude::*, path::image_lib_url};
+use crate::{prelude::*, path::{image_lib_url, image_lib_png_url}};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use futures::channel::oneshot::{channel, Receiver, Sender};
@@ -29,11 +29,18 @@ pub struct ImageEffect {
 
 impl ImageEffect {
     pub async fn new(src: Image) -> Self {
-        let url = image_lib_url(src.lib.clone(), PngImageFile::Resized, src.id.clone());
+        let url = image_lib_url(src.lib.clone(), PngImageFile::Resized, src.id.clone()).await;
         let img = match awsm_web::loaders::image::load(url).await {
             Ok(img) => img,
             Err(_) => {
-                panic!("could not load image!");
+                // older images only have the PNG
+                let url = image_lib_png_url(src.lib.clone(), PngImageFile::Resized, src.id.clone());
+                match awsm_web::loaders::image::load(url).await {
+                    Ok(img) => img,
+                    Err(_) => {
+                        panic!("could not load image!");
+                    }
+                }
             }
         };
 
@@ -1,13 +1,17 @@
 use super::settings::SETTINGS;
 use config::MEDIA_UI_PATH;
 use shared::{
-    media::{media_key,MediaLibrary, FileKind, PngImageFile},
+    media::{media_key,MediaLibrary, FileKind, ImageFileFormat, PngImageFile, SpritesheetFile},
     domain::image::ImageId,
+    domain::animation::AnimationId,
     domain::audio::AudioId,
 };
 use wasm_bindgen::prelude::*;
+use once_cell::sync::OnceCell;
 use crate::unwrap::UnwrapJiExt;
 
+static IMAGE_FILE_FORMAT:OnceCell<ImageFileFormat> = OnceCell::new();
+
 pub fn ui<T: AsRef<str>>(path:T) -> String {
     media_url(&format!("{}/{}", MEDIA_UI_PATH, path.as_ref()))
 }
@@ -18,12 +22,56 @@ pub fn audio_lib_url(library_kind: MediaLibrary, id:AudioId) -> String {
     uploads_url(&path)
 }
 
-pub fn image_lib_url(library_kind: MediaLibrary, img_kind: PngImageFile, id:ImageId) -> String {
+/// Url of the image in the best format the browser supports
+/// (images processed before there were other formats only have the PNG, see `image_lib_png_url`)
+pub async fn image_lib_url(library_kind: MediaLibrary, img_kind: PngImageFile, id:ImageId) -> String {
+    let format = match img_kind {
+        // the original is only stored as a PNG, unless it was uploaded as a JPEG or WebP
+        // (see `FileKind::ORIGINAL_IMAGE_FILES`, `<img-ji>` tries each)
+        PngImageFile::Original => ImageFileFormat::Png,
+        _ => image_file_format().await,
+    };
+
+    let path = media_key(library_kind, id.0, format.file_kind(img_kind));
+
+    uploads_url(&path)
+}
+
+pub fn image_lib_png_url(library_kind: MediaLibrary, img_kind: PngImageFile, id:ImageId) -> String {
     let path = media_key(library_kind, id.0, FileKind::ImagePng(img_kind));
 
     uploads_url(&path)
 }
 
+// 1x1 images to check that the browser can decode each format (PNG always can)
+const AVIF_PROBE:&str = "data:image/avif;base64,AAAAIGZ0eXBhdmlmAAAAAGF2aWZtaWYxbWlhZk1BMUIAAADybWV0YQAAAAAAAAAoaGRscgAAAAAAAAAAcGljdAAAAAAAAAAAAAAAAGxpYmF2aWYAAAAADnBpdG0AAAAAAAEAAAAeaWxvYwAAAABEAAABAAEAAAABAAABGgAAAB0AAAAoaWluZgAAAAAAAQAAABppbmZlAgAAAAABAABhdjAxQ29sb3IAAAAAamlwcnAAAABLaXBjbwAAABRpc3BlAAAAAAAAAAIAAAACAAAAEHBpeGkAAAAAAwgICAAAAAxhdjFDgQ0MAAAAABNjb2xybmNseAACAAIAAYAAAAAXaXBtYQAAAAAAAAABAAEEAQKDBAAAACVtZGF0EgAKCBgANogQEAwgMg8f8D///8WfhwB8+ErK42A=";
+const WEBP_PROBE:&str = "data:image/webp;base64,UklGRiIAAABXRUJQVlA4IBYAAAAwAQCdASoBAAEADsD+JaQAA3AAAAAA";
+
+// there's no way to ask which formats the browser can decode, so try decoding a tiny image in each
+// (canvases can't be used for this, browsers can decode formats they can't encode to)
+async fn image_file_format() -> ImageFileFormat {
+    if let Some(format) = IMAGE_FILE_FORMAT.get() {
+        return *format;
+    }
+
+    let mut format = ImageFileFormat::Png;
+
+    for (probe_format, probe) in [(ImageFileFormat::Avif, AVIF_PROBE), (ImageFileFormat::Webp, WEBP_PROBE)].iter() {
+        if awsm_web::loaders::image::load(probe.to_string()).await.is_ok() {
+            format = *probe_format;
+            break;
+        }
+    }
+
+    *IMAGE_FILE_FORMAT.get_or_init(|| format)
+}
+
+pub fn spritesheet_lib_url(library_kind: MediaLibrary, file: SpritesheetFile, id:AnimationId) -> String {
+    let path = media_key(library_kind, id.0, FileKind::Spritesheet(file));
+
+    uploads_url(&path)
+}
+
 pub fn uploads_url(path:&str) -> String {
     format!("{}/{}", SETTINGS.get().unwrap_ji().remote_target.uploads_url(), path)
 }
@@ -1,6 +1,7 @@
 use web_sys::Url;
 use wasm_bindgen::prelude::*;
 use shared::domain::{
+    class::assignment::AssignmentId,
     image::{ImageId, ImageSearchQuery}, 
     jig::{JigId, module::ModuleId, ModuleKind}, 
     search::CreateSearchKeyResponse, 
@@ -65,7 +66,9 @@ pub enum LegacyRoute {
 pub enum JigRoute {
     Gallery,
     Edit(JigId, JigEditRoute),
-    Play(JigId, Option<ModuleId>) 
+    /// The assignment is set when a student plays the jig after joining it
+    Play(JigId, Option<ModuleId>, Option<AssignmentId>),
+    Join,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -199,13 +202,16 @@ impl Route {
                     JigId(Uuid::from_str(jig_id).unwrap_ji()),
                     JigEditRoute::Module(ModuleId(Uuid::from_str(module_id).unwrap_ji()))
             )),
+            ["jig", "play", "join"] => Self::Jig(JigRoute::Join),
             ["jig", "play", jig_id] => Self::Jig(JigRoute::Play(
                     JigId(Uuid::from_str(jig_id).unwrap_ji()),
-                    None
+                    None,
+                    params.get("assignment").map(|id| AssignmentId(Uuid::from_str(&id).unwrap_ji()))
             )),
             ["jig", "play", jig_id, module_id] => Self::Jig(JigRoute::Play(
                     JigId(Uuid::from_str(jig_id).unwrap_ji()),
-                    Some(ModuleId(Uuid::from_str(module_id).unwrap_ji()))
+                    Some(ModuleId(Uuid::from_str(module_id).unwrap_ji())),
+                    params.get("assignment").map(|id| AssignmentId(Uuid::from_str(&id).unwrap_ji()))
             )),
                     
             ["legacy", "play", jig_id] => Self::Legacy(LegacyRoute::Play(jig_id.to_string(), None)),
@@ -307,13 +313,19 @@ impl From<&Route> for String {
                             JigEditRoute::Publish => format!("/jig/edit/{}/publish", jig_id.0.to_string()),
                         }
                     }
-                    JigRoute::Play(jig_id, module_id) => {
-                        if let Some(module_id) = module_id {
+                    JigRoute::Play(jig_id, module_id, assignment_id) => {
+                        let path = if let Some(module_id) = module_id {
                             format!("/jig/play/{}/{}", jig_id.0.to_string(), module_id.0.to_string())
                         } else {
                             format!("/jig/play/{}", jig_id.0.to_string())
+                        };
+
+                        match assignment_id {
+                            Some(assignment_id) => format!("{}?assignment={}", path, assignment_id.0.to_string()),
+                            None => path,
                         }
                     }
+                    JigRoute::Join => "/jig/play/join".to_string(),
                 }
             },
             Route::Legacy(route) => {
@@ -2,8 +2,11 @@ import "@elements/core/buttons/empty";
 import "@elements/core/buttons/rectangle";
 import "@elements/core/images/ui";
 import "@elements/core/images/ji";
+import "@elements/core/inputs/wrapper";
 import "@elements/core/overlays/anchored-overlay";
+import "@elements/core/window/window-loader-block";
 import "@elements/entry/jig/play/background-music";
+import "@elements/entry/jig/play/join";
 import "@elements/entry/jig/play/landing";
 import "@elements/entry/jig/play/move-button";
 import "@elements/entry/jig/play/play-button";
@@ -1,12 +1,18 @@
-import { LitElement, html, css, customElement, property } from "lit-element";
-import { imageLib, MediaLibOptions, MediaSizeOptions } from "@utils/path";
+import { LitElement, html, css, customElement, property, internalProperty } from "lit-element";
+import { imageLib, MediaFormatOptions, MediaLibOptions, MediaSizeOptions } from "@utils/path";
 import {sameOrigin} from "@utils/image";
 
+//the formats an original can be stored as, there's no way to tell which without trying them
+const ORIGINAL_FORMATS: MediaFormatOptions[] = ["png", "jpg", "webp"];
+
 @customElement("img-ji")
 export class _ extends LitElement {
     static get styles() {
         return [
             css`
+            picture {
+                display: contents;
+            }
             img {
                 display: inherit;
                 width: inherit;
@@ -35,6 +41,14 @@ export class _ extends LitElement {
     @property({hasChanged: () => true})
     id: string = "";
 
+    //the png the webp/avif sources failed to load for (images processed before they existed only have the png)
+    @internalProperty()
+    pngOnlySrc: string = "";
+
+    //the originals that failed to load, to try the next format
+    @internalProperty()
+    failedOriginals: string[] = [];
+
     onLoad(evt: Event) {
         const img = evt.currentTarget as HTMLImageElement;
         const width = img.naturalWidth;
@@ -50,19 +64,51 @@ export class _ extends LitElement {
     }
 
 
+    onError() {
+        const { lib, size, id } = this;
+
+        if (size === "original") {
+            const src = this.originalSrc();
+            if (!this.failedOriginals.includes(src)) {
+                this.failedOriginals = [...this.failedOriginals, src];
+            }
+        } else {
+            this.pngOnlySrc = imageLib({ lib, size, id });
+        }
+    }
+
+    originalSrc(): string {
+        const { lib, size, id } = this;
+
+        const srcs = ORIGINAL_FORMATS.map(format => imageLib({ lib, size, id, format }));
+
+        return srcs.find(src => !this.failedOriginals.includes(src)) ?? srcs[0];
+    }
+
     render() {
         const { lib, size, id, cacheBust, draggable } = this;
 
-        let src = imageLib({ lib, size, id });
+        const cb = cacheBust ? `?cb=${Date.now()}` : "";
+
+        const png = size === "original" ? this.originalSrc() : imageLib({ lib, size, id });
+        const src = png + cb;
 
-        if(cacheBust) {
-            src += `?cb=${Date.now()}`;
+        const img = sameOrigin(src)
+            ? html`<img .draggable=${draggable} .src="${src}" @load="${this.onLoad}" @error="${this.onError}" ></img>`
+            : html`<img .draggable=${draggable} .src="${src}" crossorigin="anonymous" @load="${this.onLoad}" @error="${this.onError}" ></img>`;
+
+        //the browser picks the first source it can decode, but doesn't fall back to the next one if it's missing
+        if (lib === "mock" || size === "original" || this.pngOnlySrc === png) {
+            return img;
         }
 
-        if (sameOrigin(src)) {
-            return html`<img .draggable=${draggable} .src="${src}" @load="${this.onLoad}" ></img>`;
-        } else {
-            return html`<img .draggable=${draggable} .src="${src}" crossorigin="anonymous" @load="${this.onLoad}" ></img>`;
-            }
+        const avif = imageLib({ lib, size, id, format: "avif" }) + cb;
+        const webp = imageLib({ lib, size, id, format: "webp" }) + cb;
+
+        return html`<picture>
+            <source type="image/avif" srcset="${avif}" />
+            <source type="image/webp" srcset="${webp}" />
+            ${img}
+        </picture>`;
     }
 }
@@ -0,0 +1,61 @@
+import { LitElement, html, css, customElement, property } from "lit-element";
+import { nothing } from "lit-html";
+
+const STR_HEADER = "Join your class";
+const STR_ERROR = "We couldn't find an assignment with that code";
+
+@customElement("jig-play-join")
+export class _ extends LitElement {
+    static get styles() {
+        return [
+            css`
+                :host {
+                    display: grid;
+                    place-content: center;
+                    height: 100vh;
+                    width: 100vw;
+                    background-color: var(--light-blue-3);
+                }
+                .card {
+                    display: grid;
+                    row-gap: 24px;
+                    width: 420px;
+                    padding: 32px;
+                    border-radius: 16px;
+                    background-color: #ffffff;
+                }
+                h1 {
+                    margin: 0;
+                    font-size: 32px;
+                    font-weight: 900;
+                    color: var(--dark-blue-4);
+                }
+                .error {
+                    color: var(--red-alert);
+                    font-size: 14px;
+                }
+                .submit {
+                    display: flex;
+                    justify-content: flex-end;
+                }
+            `,
+        ];
+    }
+
+    @property({ type: Boolean })
+    error: boolean = false;
+
+    render() {
+        return html`
+            <div class="card">
+                <h1>${STR_HEADER}</h1>
+                <slot name="code"></slot>
+                <slot name="name"></slot>
+                ${this.error ? html`<span class="error">${STR_ERROR}</span>` : nothing}
+                <div class="submit">
+                    <slot name="submit"></slot>
+                </div>
+            </div>
+        `;
+    }
+}
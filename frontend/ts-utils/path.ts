@@ -42,13 +42,17 @@ const sizeVariant = (size:MediaSizeOptions):string => {
     }
 }
 
-export const imageLib = ({lib, size, id}:{lib: MediaLibOptions, size: MediaSizeOptions, id: string}) => {
+// the resized image and thumbnail are also stored as webp and avif, images processed before then only have the png
+// the original is a png, unless it was uploaded as a jpg or webp, in which case it's stored as one
+export type MediaFormatOptions = "avif" | "webp" | "png" | "jpg";
+
+export const imageLib = ({lib, size, id, format = "png"}:{lib: MediaLibOptions, size: MediaSizeOptions, id: string, format?: MediaFormatOptions}) => {
     const prefix = imagePrefix(lib);
     const variant = sizeVariant(size);
 
     return lib === "mock" 
         ? mediaUi(`${prefix}/${variant}/${id}`)
-        : mediaUploads(`${prefix}/${id}/${variant}.png`);
+        : mediaUploads(`${prefix}/${id}/${variant}.${format}`);
 }
 
 interface LegacyMedia {
@@ -31,6 +31,9 @@ pub mod animation;
 /// Category endpoints.
 pub mod category;
 
+/// Class endpoints.
+pub mod class;
+
 /// Image endpoints.
 pub mod image;
 
@@ -1,7 +1,13 @@
 use super::ApiEndpoint;
 use crate::{
     api::Method,
-    domain::{admin::AdminListMediaResponse, session::NewSessionResponse},
+    domain::{
+        admin::{
+            AdminImageDuplicatesQuery, AdminImageDuplicatesResponse, AdminListMediaResponse,
+            AdminReindexRequest, AdminSearchHealthResponse,
+        },
+        session::NewSessionResponse,
+    },
     error::EmptyError,
 };
 
@@ -38,3 +44,41 @@ impl ApiEndpoint for ListMedia {
     const PATH: &'static str = "/v0/admin/media";
     const METHOD: Method = Method::Get;
 }
+
+/// Reindex content for search, as if it was all just updated.
+///
+/// Content gets synced in the background, see [`SearchHealth`] for how much is still pending.
+/// Records of content that doesn't exist anymore are deleted from the index in the background too.
+///
+/// # Errors
+/// [`NotImplemented`](http::StatusCode::NOT_IMPLEMENTED) if search isn't backed by Algolia (there's no index to sync).
+pub struct Reindex;
+impl ApiEndpoint for Reindex {
+    type Req = AdminReindexRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/admin/search/reindex";
+    const METHOD: Method = Method::Post;
+}
+
+/// Compare what's in the database with what's in the search indexes.
+pub struct SearchHealth;
+impl ApiEndpoint for SearchHealth {
+    type Req = ();
+    type Res = AdminSearchHealthResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/admin/search/health";
+    const METHOD: Method = Method::Get;
+}
+
+/// Report pairs of near-duplicate images in the global library, by how similar their perceptual hashes are.
+///
+/// Only images processed since perceptual hashes were added are compared.
+pub struct ImageDuplicates;
+impl ApiEndpoint for ImageDuplicates {
+    type Req = AdminImageDuplicatesQuery;
+    type Res = AdminImageDuplicatesResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/admin/image/duplicates";
+    const METHOD: Method = Method::Get;
+}
@@ -4,8 +4,8 @@ use crate::{
     api::{ApiEndpoint, Method},
     domain::{
         animation::{
-            AnimationCreateRequest, AnimationId, AnimationResponse, AnimationUploadRequest,
-            AnimationUploadResponse,
+            AnimationCreateRequest, AnimationId, AnimationResponse, AnimationSearchQuery,
+            AnimationSearchResponse, AnimationUploadRequest, AnimationUploadResponse,
         },
         CreateResponse,
     },
@@ -21,6 +21,16 @@ impl ApiEndpoint for Get {
     const PATH: &'static str = "/v1/animation/{id}";
     const METHOD: Method = Method::Get;
 }
+/// Search for animations.
+pub struct Search;
+impl ApiEndpoint for Search {
+    type Req = AnimationSearchQuery;
+    type Res = AnimationSearchResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/animation";
+    const METHOD: Method = Method::Get;
+}
+
 /// Create an animation.
 pub struct Create;
 impl ApiEndpoint for Create {
@@ -4,12 +4,16 @@ pub mod user {
         api::{ApiEndpoint, Method},
         domain::{
             audio::{
-                user::{UserAudioListResponse, UserAudioResponse},
+                user::{
+                    UserAudioListResponse, UserAudioResponse, UserAudioSearchQuery,
+                    UserAudioSearchResponse, UserAudioUpdateRequest,
+                },
                 AudioId,
             },
+            media::{MediaDeleteQuery, MediaUsageResponse},
             CreateResponse,
         },
-        error::EmptyError,
+        error::{EmptyError, MediaInUse},
     };
 
     /// List audio files.
@@ -22,6 +26,18 @@ pub mod user {
         const METHOD: Method = Method::Get;
     }
 
+    /// Search the user's audio files by name and tags.
+    ///
+    /// Note: only finds audio files the user created.
+    pub struct Search;
+    impl ApiEndpoint for Search {
+        type Req = UserAudioSearchQuery;
+        type Res = UserAudioSearchResponse;
+        type Err = EmptyError;
+        const PATH: &'static str = "/v1/user/me/audio/search";
+        const METHOD: Method = Method::Get;
+    }
+
     /// Get an audio file by ID.
     pub struct Get;
     impl ApiEndpoint for Get {
@@ -41,11 +57,29 @@ pub mod user {
         const METHOD: Method = Method::Post;
     }
 
+    /// Update an audio file's name and tags.
+    ///
+    /// Only the user who uploaded it can update it, it's [`NotFound`](http::StatusCode::NOT_FOUND) for anyone else.
+    pub struct Update;
+    impl ApiEndpoint for Update {
+        type Req = UserAudioUpdateRequest;
+        type Res = ();
+        type Err = EmptyError;
+        const PATH: &'static str = "/v1/user/me/audio/{id}";
+        const METHOD: Method = Method::Patch;
+    }
+
     /// Upload an audio file
     ///
     /// Notes:
     /// * can be used to update the raw data associated with the audio file.
     /// * handled through API server, unlike other media types which return a presigned URL.
+    /// * accepts WAV, OGG, WebM/Opus, M4A/MP4 and MP3,
+    ///   which is transcoded to MP3, trimmed of leading and trailing silence, and normalized.
+    ///
+    /// # Errors
+    /// * [`UnprocessableEntity`](http::StatusCode::UNPROCESSABLE_ENTITY) if the file isn't audio in one of those formats,
+    ///   is only silence, or takes too long to process.
     pub struct Upload;
     impl ApiEndpoint for Upload {
         // raw bytes
@@ -57,12 +91,25 @@ pub mod user {
     }
 
     /// Delete an audio file.
+    ///
+    /// # Errors
+    /// [`Conflict`](http::StatusCode::CONFLICT) if JIG modules still use the audio file and `force` isn't set, the JIGs are listed in the response.
     pub struct Delete;
     impl ApiEndpoint for Delete {
-        type Req = ();
+        type Req = MediaDeleteQuery;
         type Res = ();
-        type Err = EmptyError;
+        type Err = MediaInUse;
         const PATH: &'static str = "/v1/user/me/audio/{id}";
         const METHOD: Method = Method::Delete;
     }
+
+    /// List the JIG modules that use an audio file.
+    pub struct Usage;
+    impl ApiEndpoint for Usage {
+        type Req = ();
+        type Res = MediaUsageResponse;
+        type Err = EmptyError;
+        const PATH: &'static str = "/v1/user/me/audio/{id}/usage";
+        const METHOD: Method = Method::Get;
+    }
 }
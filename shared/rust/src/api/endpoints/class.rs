@@ -0,0 +1,81 @@
+use crate::{
+    api::Method,
+    domain::{
+        class::{
+            ClassBrowseQuery, ClassBrowseResponse, ClassCreateRequest, ClassId, ClassResponse,
+            ClassUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::ApiEndpoint;
+
+/// Endpoints for class assignments.
+pub mod assignment;
+
+/// Get a class by ID.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = ClassResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}";
+    const METHOD: Method = Method::Get;
+}
+
+/// Browse the classes created by the current user.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass`
+pub struct Browse;
+impl ApiEndpoint for Browse {
+    type Req = ClassBrowseQuery;
+    type Res = ClassBrowseResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/browse";
+    const METHOD: Method = Method::Get;
+}
+
+/// Create a class.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass`
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = ClassCreateRequest;
+    type Res = CreateResponse<ClassId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class";
+    const METHOD: Method = Method::Post;
+}
+
+/// Update a class.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = ClassUpdateRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}";
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a class, along with its assignments and their results.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}";
+    const METHOD: Method = Method::Delete;
+}
@@ -0,0 +1,150 @@
+use crate::{
+    api::Method,
+    domain::{
+        class::assignment::{
+            AssignmentCreateRequest, AssignmentId, AssignmentJoinRequest, AssignmentJoinResponse,
+            AssignmentListResponse, AssignmentResponse, AssignmentResultsResponse,
+            AssignmentUpdateRequest,
+        },
+        jig::player::{JigPlayerModuleResultRequest, JigPlayerSessionId},
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+use super::super::ApiEndpoint;
+
+/// Get an assignment by ID.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = AssignmentResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment/{assignment_id}";
+    const METHOD: Method = Method::Get;
+}
+
+/// List the assignments given to a class.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = AssignmentListResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment";
+    const METHOD: Method = Method::Get;
+}
+
+/// Assign a JIG to a class.
+///
+/// A code is generated for the assignment, which students use to [`Join`] it.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+///
+/// # Errors
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the class or the jig does not exist.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = AssignmentCreateRequest;
+    type Res = CreateResponse<AssignmentId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment";
+    const METHOD: Method = Method::Post;
+}
+
+/// Update an assignment.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = AssignmentUpdateRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment/{assignment_id}";
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete an assignment, along with its results.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment/{assignment_id}";
+    const METHOD: Method = Method::Delete;
+}
+
+/// Get the roster of an assignment: every student who joined it, along with their results.
+///
+/// # Authorization
+/// * One of `Admin`, or `ManageClass` for owned classes
+pub struct Results;
+impl ApiEndpoint for Results {
+    type Req = ();
+    type Res = AssignmentResultsResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/class/{id}/assignment/{assignment_id}/result";
+    const METHOD: Method = Method::Get;
+}
+
+/// Join an assignment as a student.
+///
+/// Doesn't require an account, the returned session can only be used to play the assignment's JIG.
+///
+/// # Flow
+/// 1. `POST /v1/assignment/join` (this route) with the code
+/// 2. [`POST /v1/assignment/play/session`](CreateSession)
+/// 3. [`POST /v1/assignment/play/session/{session_id}/module`](CreateModuleResult) for each module played
+///
+/// # Authorization
+/// * None
+///
+/// # Errors
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if there is no assignment with the given code.
+pub struct Join;
+impl ApiEndpoint for Join {
+    type Req = AssignmentJoinRequest;
+    type Res = AssignmentJoinResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/assignment/join";
+    const METHOD: Method = Method::Post;
+}
+
+/// Start a play session for the joined assignment's JIG.
+///
+/// # Authorization
+/// * Assignment session (see [`Join`])
+pub struct CreateSession;
+impl ApiEndpoint for CreateSession {
+    type Req = ();
+    type Res = CreateResponse<JigPlayerSessionId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/assignment/play/session";
+    const METHOD: Method = Method::Post;
+}
+
+/// Record the result of a module in an assignment play session.
+///
+/// # Authorization
+/// * Assignment session (see [`Join`])
+///
+/// # Errors
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the session doesn't belong to the student, or the module isn't part of the jig.
+pub struct CreateModuleResult;
+impl ApiEndpoint for CreateModuleResult {
+    type Req = JigPlayerModuleResultRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/assignment/play/session/{session_id}/module";
+    const METHOD: Method = Method::Post;
+}
@@ -2,11 +2,16 @@ use super::ApiEndpoint;
 use crate::domain::image::ImageUploadRequest;
 use crate::{
     api::Method,
-    domain::image::{
-        CreateResponse, ImageBrowseQuery, ImageBrowseResponse, ImageCreateRequest, ImageResponse,
-        ImageSearchQuery, ImageSearchResponse, ImageUpdateRequest, ImageUploadResponse,
+    domain::{
+        image::{
+            CreateResponse, ImageBrowseQuery, ImageBrowseResponse, ImageCreateRequest,
+            ImageRelatedQuery, ImageRelatedResponse, ImageResponse, ImageSearchQuery,
+            ImageSearchResponse, ImageSimilarQuery, ImageSimilarResponse, ImageUpdateRequest,
+            ImageUploadResponse,
+        },
+        media::{MediaDeleteQuery, MediaUsageResponse},
     },
-    error::{EmptyError, MetadataNotFound},
+    error::{EmptyError, MediaInUse, MetadataNotFound},
 };
 
 pub mod recent;
@@ -57,6 +62,9 @@ impl ApiEndpoint for Create {
 /// Upload an image
 /// Note: can be used to update the raw data associated with the image.
 ///
+/// The image can be a PNG, JPEG, GIF, WebP or SVG (SVGs are rasterized),
+/// the processed image is stored as a PNG, along with WebP and AVIF versions of the resized image and thumbnail.
+///
 /// Errors:
 /// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
 ///
@@ -84,11 +92,64 @@ impl ApiEndpoint for UpdateMetadata {
 }
 
 /// Delete an image.
+///
+/// # Errors
+/// [`Conflict`](http::StatusCode::CONFLICT) if JIG modules still use the image and `force` isn't set, the JIGs are listed in the response.
 pub struct Delete;
 impl ApiEndpoint for Delete {
-    type Req = ();
+    type Req = MediaDeleteQuery;
     type Res = ();
-    type Err = EmptyError;
+    type Err = MediaInUse;
     const PATH: &'static str = "/v1/image/{id}";
     const METHOD: Method = Method::Delete;
 }
+
+/// List the JIG modules that use an image.
+///
+/// Useful before deleting or replacing an image.
+///
+/// # Authorization
+/// * One of `Admin` or `ManageImage`
+pub struct Usage;
+impl ApiEndpoint for Usage {
+    type Req = ();
+    type Res = MediaUsageResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/image/{id}/usage";
+    const METHOD: Method = Method::Get;
+}
+
+/// Get published images related to an image, ranked by how many tags and styles they share with it.
+///
+/// # Authorization
+/// * Any logged in user
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the image doesn't exist.
+pub struct Related;
+impl ApiEndpoint for Related {
+    type Req = ImageRelatedQuery;
+    type Res = ImageRelatedResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/image/{id}/related";
+    const METHOD: Method = Method::Get;
+}
+
+/// Get images that look like an image from the global image library, to catch re-uploads of the same image.
+///
+/// Similarity is by perceptual hash, so re-saved, re-encoded and resized copies are found.
+/// Unpublished images are included.
+///
+/// # Authorization
+/// * One of `Admin` or `ManageImage`
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the image doesn't exist.
+pub struct Similar;
+impl ApiEndpoint for Similar {
+    type Req = ImageSimilarQuery;
+    type Res = ImageSimilarResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/image/{id}/similar";
+    const METHOD: Method = Method::Get;
+}
@@ -5,14 +5,16 @@ use crate::{
     domain::{
         image::{
             user::{
-                UserImageListResponse, UserImageResponse, UserImageUploadRequest,
-                UserImageUploadResponse,
+                UserImageListResponse, UserImageResponse, UserImageSearchQuery,
+                UserImageSearchResponse, UserImageSimilarResponse, UserImageUpdateRequest,
+                UserImageUploadRequest, UserImageUploadResponse,
             },
-            ImageId,
+            ImageId, ImageSimilarQuery,
         },
+        media::{MediaDeleteQuery, MediaUsageResponse},
         CreateResponse,
     },
-    error::EmptyError,
+    error::{EmptyError, MediaInUse},
 };
 
 /// List user library images.
@@ -25,6 +27,18 @@ impl ApiEndpoint for List {
     const METHOD: Method = Method::Get;
 }
 
+/// Search the user's library images by name and tags.
+///
+/// Note: only finds images the user created.
+pub struct Search;
+impl ApiEndpoint for Search {
+    type Req = UserImageSearchQuery;
+    type Res = UserImageSearchResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/image/search";
+    const METHOD: Method = Method::Get;
+}
+
 /// Get an user library image by ID.
 pub struct Get;
 impl ApiEndpoint for Get {
@@ -45,9 +59,24 @@ impl ApiEndpoint for Create {
     const METHOD: Method = Method::Post;
 }
 
+/// Update an user library image's name and tags.
+///
+/// Only the user who uploaded it can update it, it's [`NotFound`](http::StatusCode::NOT_FOUND) for anyone else.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = UserImageUpdateRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/image/{id}";
+    const METHOD: Method = Method::Patch;
+}
+
 /// Upload an image to the user image library.
 /// Note: can be used to update the raw data associated with the image.
 ///
+/// The image can be a PNG, JPEG, GIF, WebP or SVG (SVGs are rasterized),
+/// the processed image is stored as a PNG, along with WebP and AVIF versions of the resized image and thumbnail.
+///
 /// Errors:
 /// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
 ///
@@ -65,11 +94,37 @@ impl ApiEndpoint for Upload {
 }
 
 /// Delete an image from the user library.
+///
+/// # Errors
+/// [`Conflict`](http::StatusCode::CONFLICT) if JIG modules still use the image and `force` isn't set, the JIGs are listed in the response.
 pub struct Delete;
 impl ApiEndpoint for Delete {
-    type Req = ();
+    type Req = MediaDeleteQuery;
     type Res = ();
-    type Err = EmptyError;
+    type Err = MediaInUse;
     const PATH: &'static str = "/v1/user/me/image/{id}";
     const METHOD: Method = Method::Delete;
 }
+
+/// List the JIG modules that use an image from the user library.
+pub struct Usage;
+impl ApiEndpoint for Usage {
+    type Req = ();
+    type Res = MediaUsageResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/image/{id}/usage";
+    const METHOD: Method = Method::Get;
+}
+
+/// Get the user's library images that look like an image, most similar first.
+///
+/// # Errors
+/// [`NotFound`](http::StatusCode::NOT_FOUND) if the image doesn't exist.
+pub struct Similar;
+impl ApiEndpoint for Similar {
+    type Req = ImageSimilarQuery;
+    type Res = UserImageSimilarResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/image/{id}/similar";
+    const METHOD: Method = Method::Get;
+}
@@ -3,7 +3,8 @@ use crate::{
     domain::{
         jig::{
             JigBrowseQuery, JigBrowseResponse, JigCountResponse, JigCreateRequest, JigId,
-            JigResponse, JigSearchQuery, JigSearchResponse, JigUpdateRequest,
+            JigLikedResponse, JigRelatedQuery, JigRelatedResponse, JigResponse, JigSearchQuery,
+            JigSearchResponse, JigUpdateRequest,
         },
         CreateResponse,
     },
@@ -21,6 +22,15 @@ pub mod additional_resource;
 /// Endpoints for jig drafts.
 pub mod draft;
 
+/// Endpoints for jig play sessions.
+pub mod player;
+
+/// Endpoints for jig collaborators.
+pub mod collaborator;
+
+/// Endpoints for jig version history.
+pub mod version;
+
 /// Get a JIG by ID.
 ///
 /// # Authorization
@@ -95,10 +105,71 @@ impl ApiEndpoint for Clone {
     const METHOD: Method = Method::Post;
 }
 
+/// Export a JIG as a zip archive.
+///
+/// The response body is the archive itself, see [`archive`](crate::domain::jig::archive) for its layout.
+/// It includes every image and audio file the JIG's modules reference.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned Jigs, or Jigs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the jig does not exist.
+pub struct Export;
+impl ApiEndpoint for Export {
+    type Req = ();
+    // raw bytes
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/export";
+    const METHOD: Method = Method::Get;
+}
+
+/// Import a JIG from a zip archive created by [`Export`].
+///
+/// The request body is the archive itself.
+///
+/// The JIG and its modules are created with new IDs, owned by the requesting user.
+/// The archive's media is uploaded to the user library under new IDs, and the modules are updated to reference it.
+/// Metadata (goals, categories, etc) that doesn't exist in this environment is dropped.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig`
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['BadRequest'](http::StatusCode::BAD_REQUEST) if the body isn't a valid archive.
+///
+/// [`PayloadTooLarge`](http::StatusCode::PAYLOAD_TOO_LARGE) if the archive is too large.
+pub struct Import;
+impl ApiEndpoint for Import {
+    // raw bytes
+    type Req = ();
+    type Res = CreateResponse<JigId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/import";
+    const METHOD: Method = Method::Post;
+}
+
 /// Update a JIG.
 ///
+/// Note: this request can be conditional on `If-Match`, with the JIG's [`revision`](crate::domain::jig::Jig::revision) as the entity tag.
+/// On success the response's `ETag` is the JIG's new revision.
+///
 /// # Authorization
 /// * One of `Admin`, `AdminJig`,, or `ManageSelfJig` for owned Jigs
+/// * `ManageSelfJig` for Jigs the user is an editor of, unless `author_id` is being changed
+///
+/// # Errors
+/// [`PreconditionFailed`](http::StatusCode::PRECONDITION_FAILED) if the JIG's revision doesn't match `If-Match`.
+/// The body of the response is the JIG as currently stored, as a [`JigResponse`](crate::domain::jig::JigResponse).
 pub struct Update;
 impl ApiEndpoint for Update {
     type Req = JigUpdateRequest;
@@ -133,3 +204,93 @@ impl ApiEndpoint for Count {
     const PATH: &'static str = "/v1/jig/count";
     const METHOD: Method = Method::Get;
 }
+
+/// Like a JIG, as the current user. Liking a JIG more than once does nothing.
+///
+/// Likes of a draft count towards its live JIG.
+///
+/// # Authorization
+/// * Any logged in user
+pub struct Like;
+impl ApiEndpoint for Like {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/like";
+    const METHOD: Method = Method::Put;
+}
+
+/// Un-like a JIG, as the current user.
+///
+/// # Authorization
+/// * Any logged in user
+pub struct Unlike;
+impl ApiEndpoint for Unlike {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/like";
+    const METHOD: Method = Method::Delete;
+}
+
+/// Whether the current user likes a JIG.
+///
+/// # Authorization
+/// * Any logged in user
+pub struct Liked;
+impl ApiEndpoint for Liked {
+    type Req = ();
+    type Res = JigLikedResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/like";
+    const METHOD: Method = Method::Get;
+}
+
+/// Count a play of a JIG.
+///
+/// Plays of a draft count towards its live JIG. Each player only counts once an hour per JIG.
+///
+/// # Authorization
+/// * Any logged in user, or a student of an assignment for the JIG
+pub struct Play;
+impl ApiEndpoint for Play {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/play";
+    const METHOD: Method = Method::Post;
+}
+
+/// Count a view of a JIG by the current user. Each user only counts once per JIG.
+///
+/// Views of a draft count towards its live JIG.
+///
+/// # Authorization
+/// * Any logged in user
+pub struct View;
+impl ApiEndpoint for View {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/view";
+    const METHOD: Method = Method::Post;
+}
+
+/// Get published JIGs related to a JIG, for "more like this".
+///
+/// JIGs are ranked by how many categories, goals and age ranges they share with it,
+/// and whether they have the same language and author.
+///
+/// # Authorization
+/// * Any logged in user, or a student of an assignment for the JIG
+///
+/// # Errors
+/// * [`NotFound`](http::StatusCode::NOT_FOUND) if the JIG doesn't exist.
+pub struct Related;
+impl ApiEndpoint for Related {
+    type Req = JigRelatedQuery;
+    type Res = JigRelatedResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/related";
+    const METHOD: Method = Method::Get;
+}
@@ -0,0 +1,86 @@
+use super::super::ApiEndpoint;
+
+use crate::{
+    api::Method,
+    domain::jig::collaborator::{JigCollaboratorInviteRequest, JigCollaboratorListResponse},
+    error::EmptyError,
+};
+
+/// List the collaborators of a JIG, including pending invites.
+///
+/// Collaborators are shared between a JIG and its draft, so either ID can be used with these endpoints.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = JigCollaboratorListResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/collaborator";
+    const METHOD: Method = Method::Get;
+}
+
+/// Invite a user to collaborate on a JIG.
+///
+/// Inviting a user who was already invited changes their role.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the jig or a user with the given email does not exist.
+pub struct Invite;
+impl ApiEndpoint for Invite {
+    type Req = JigCollaboratorInviteRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/collaborator";
+    const METHOD: Method = Method::Post;
+}
+
+/// Accept an invite to collaborate on a JIG.
+///
+/// # Authorization
+/// Standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the user hasn't been invited to the jig.
+pub struct Accept;
+impl ApiEndpoint for Accept {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/collaborator/accept";
+    const METHOD: Method = Method::Post;
+}
+
+/// Remove a collaborator (or pending invite) from a JIG.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs
+/// * Standard, for removing yourself
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct Remove;
+impl ApiEndpoint for Remove {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/collaborator/{user_id}";
+    const METHOD: Method = Method::Delete;
+}
@@ -3,7 +3,7 @@ use super::super::ApiEndpoint;
 use crate::{
     api::Method,
     domain::{
-        jig::{JigDraftResponse, JigId},
+        jig::{JigDraftResponse, JigDraftScheduleRequest, JigId},
         CreateResponse,
     },
     error::EmptyError,
@@ -54,7 +54,7 @@ impl ApiEndpoint for Get {
 
 /// Move-publish a draft to the live jig.
 ///
-/// This deletes the draft jig.
+/// This deletes the draft jig, and records the newly published contents as a [`version`](super::version) of the live jig.
 ///
 /// # Errors
 /// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
@@ -72,3 +72,25 @@ impl ApiEndpoint for Publish {
     const PATH: &'static str = "/v1/jig/{id}/draft";
     const METHOD: Method = Method::Put;
 }
+
+/// Schedule a draft to be published to the live jig at a given time, or cancel the schedule.
+///
+/// When the time comes, the draft is published as if by [`Publish`] (by the user who scheduled it),
+/// and a notification is sent to the jig's topic.
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the jig or a draft does not exist.
+///
+/// ['BadRequest'](http::StatusCode::BAD_REQUEST) if the request is malformed or the jig is a draft.
+pub struct Schedule;
+impl ApiEndpoint for Schedule {
+    type Req = JigDraftScheduleRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/draft/schedule";
+    const METHOD: Method = Method::Put;
+}
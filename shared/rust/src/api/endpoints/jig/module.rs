@@ -49,6 +49,9 @@ impl ApiEndpoint for Create {
 
 /// Update a Module.
 ///
+/// Note: this request can be conditional on `If-Match`, with the module's [`revision`](crate::domain::jig::module::Module::revision) as the entity tag.
+/// On success the response's `ETag` is the module's new revision.
+///
 /// # Authorization
 /// Standard + [`UserScope::ManageJig`](crate::domain::user::UserScope)
 ///
@@ -60,6 +63,9 @@ impl ApiEndpoint for Create {
 /// [`InvalidRequest`](http::StatusCode::INVALID_REQUEST) if the given `id` is not a [`Uuid`](uuid::Uuid) or the request is missing/invalid.
 ///
 /// [`NotFound`](http::StatusCode::NOT_FOUND) if the jig or module does not exist.
+///
+/// [`PreconditionFailed`](http::StatusCode::PRECONDITION_FAILED) if the module's revision doesn't match `If-Match`.
+/// The body of the response is the module as currently stored, as a [`ModuleResponse`](crate::domain::jig::module::ModuleResponse).
 pub struct Update;
 impl ApiEndpoint for Update {
     type Req = ModuleUpdateRequest;
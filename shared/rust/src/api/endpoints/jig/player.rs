@@ -0,0 +1,71 @@
+use super::super::ApiEndpoint;
+
+use crate::{
+    api::Method,
+    domain::{
+        jig::player::{
+            JigPlayerModuleResultRequest, JigPlayerSessionId, JigPlayerSessionListQuery,
+            JigPlayerSessionListResponse,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// Start a play session for a JIG.
+///
+/// Returns the id of the session, which modules report their results against.
+///
+/// # Authorization
+/// Standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the jig does not exist.
+pub struct CreateSession;
+impl ApiEndpoint for CreateSession {
+    type Req = ();
+    type Res = CreateResponse<JigPlayerSessionId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/player/session";
+    const METHOD: Method = Method::Post;
+}
+
+/// Record the result of a module in a play session.
+///
+/// # Authorization
+/// Standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the session was started by a different user.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the session does not exist, or the module isn't part of the jig.
+pub struct CreateModuleResult;
+impl ApiEndpoint for CreateModuleResult {
+    type Req = JigPlayerModuleResultRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/player/session/{session_id}/module";
+    const METHOD: Method = Method::Post;
+}
+
+/// List the play sessions of a JIG, along with the results reported by each module.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct ListSessions;
+impl ApiEndpoint for ListSessions {
+    type Req = JigPlayerSessionListQuery;
+    type Res = JigPlayerSessionListResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/player/session";
+    const METHOD: Method = Method::Get;
+}
@@ -0,0 +1,101 @@
+use super::super::ApiEndpoint;
+
+use crate::{
+    api::Method,
+    domain::{
+        jig::{
+            version::{JigVersionDiffResponse, JigVersionListResponse, JigVersionResponse},
+            JigId,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// List the published versions of a JIG.
+///
+/// A version is recorded every time a draft is published.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = JigVersionListResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/version";
+    const METHOD: Method = Method::Get;
+}
+
+/// Get a published version of a JIG, including the bodies of all its modules.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the version does not exist for this jig.
+pub struct Get;
+impl ApiEndpoint for Get {
+    type Req = ();
+    type Res = JigVersionResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/version/{version_id}";
+    const METHOD: Method = Method::Get;
+}
+
+/// Compare two published versions of a JIG.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user collaborates on
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if either version does not exist for this jig.
+pub struct Diff;
+impl ApiEndpoint for Diff {
+    type Req = ();
+    type Res = JigVersionDiffResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/version/{from_id}/diff/{to_id}";
+    const METHOD: Method = Method::Get;
+}
+
+/// Restore a published version of a JIG into its draft.
+///
+/// The draft is created if it doesn't exist yet, otherwise its contents are replaced.
+/// The live jig is left untouched until the draft is published.
+///
+/// Returns the id of the draft jig, with [`Created`](http::StatusCode::CREATED) if the draft was created
+/// and [`OK`](http::StatusCode::OK) if an existing draft was replaced.
+///
+/// # Authorization
+/// * One of `Admin`, `AdminJig`, or `ManageSelfJig` for owned JIGs or JIGs the user is an editor of
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if authorization is not valid.
+///
+/// [`Forbidden`](http::StatusCode::FORBIDDEN) if the user does not have sufficient permission to perform the action.
+///
+/// ['NotFound'](http::StatusCode::NOT_FOUND) if the version does not exist for this jig.
+///
+/// ['BadRequest'](http::StatusCode::BAD_REQUEST) if the request is malformed or the jig is a draft.
+pub struct Restore;
+impl ApiEndpoint for Restore {
+    type Req = ();
+    type Res = CreateResponse<JigId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/jig/{id}/version/{version_id}/restore";
+    const METHOD: Method = Method::Post;
+}
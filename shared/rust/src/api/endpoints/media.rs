@@ -1,7 +1,9 @@
 use crate::{
     api::Method,
-    domain::media::{UrlCreatedResponse, WebMediaMetadataResponse, WebMediaUrlCreateRequest},
-    error::EmptyError,
+    domain::media::{
+        MediaDeleteQuery, UrlCreatedResponse, WebMediaMetadataResponse, WebMediaUrlCreateRequest,
+    },
+    error::{EmptyError, MediaInUse},
 };
 
 use super::ApiEndpoint;
@@ -48,11 +50,14 @@ impl ApiEndpoint for DeleteUrl {
 }
 
 /// Remove media from the web media library.
+///
+/// # Errors
+/// [`Conflict`](http::StatusCode::CONFLICT) if JIG modules still use the media and `force` isn't set, the JIGs are listed in the response.
 pub struct DeleteId;
 impl ApiEndpoint for DeleteId {
-    type Req = ();
+    type Req = MediaDeleteQuery;
     type Res = ();
-    type Err = EmptyError;
+    type Err = MediaInUse;
     const PATH: &'static str = "/v1/media/id/{id}";
     const METHOD: Method = Method::Delete;
 }
@@ -2,7 +2,10 @@
 
 use crate::{
     api::{ApiEndpoint, Method},
-    domain::search::{CreateSearchKeyResponse, WebImageSearchQuery, WebImageSearchResponse},
+    domain::search::{
+        CreateSearchKeyResponse, RecentSearchResponse, SearchSuggestQuery, SearchSuggestResponse,
+        WebImageSearchQuery, WebImageSearchResponse,
+    },
     error::EmptyError,
 };
 
@@ -41,3 +44,59 @@ impl ApiEndpoint for WebImageSearch {
     const PATH: &'static str = "/v1/search/web/image";
     const METHOD: Method = Method::Get;
 }
+
+/// Get completions for a partially typed search.
+///
+/// Suggestions are drawn from the user's recent searches, and the names of published jigs, published images, image tags and categories.
+///
+/// # Authorization
+///
+/// standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if missing/invalid auth was provided.
+/// [`BadRequest`](http::StatusCode::BAD_REQUEST) if the request was not provided in a proper format
+pub struct Suggest;
+impl ApiEndpoint for Suggest {
+    type Req = SearchSuggestQuery;
+    type Res = SearchSuggestResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/search/suggest";
+    const METHOD: Method = Method::Get;
+}
+
+/// List the user's recent searches.
+///
+/// Searches for jigs and images (on their first page) are recorded for the user that made them.
+///
+/// # Authorization
+///
+/// standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if missing/invalid auth was provided.
+pub struct ListRecent;
+impl ApiEndpoint for ListRecent {
+    type Req = ();
+    type Res = RecentSearchResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/search/recent";
+    const METHOD: Method = Method::Get;
+}
+
+/// Clear the user's recent searches.
+///
+/// # Authorization
+///
+/// standard
+///
+/// # Errors
+/// [`Unauthorized`](http::StatusCode::UNAUTHORIZED) if missing/invalid auth was provided.
+pub struct ClearRecent;
+impl ApiEndpoint for ClearRecent {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/search/recent";
+    const METHOD: Method = Method::Delete;
+}
@@ -15,6 +15,7 @@ use crate::{
 mod colors;
 mod fonts;
 mod profile;
+mod saved_search;
 
 pub use colors::{
     Create as CreateColor, Delete as DeleteColor, Get as GetColors, Update as UpdateColor,
@@ -26,6 +27,11 @@ pub use fonts::{
 
 pub use profile::{Get as Profile, Put as PutProfile};
 
+pub use saved_search::{
+    Create as CreateSavedSearch, Delete as DeleteSavedSearch, List as ListSavedSearches,
+    Update as UpdateSavedSearch,
+};
+
 /// Create a new user
 ///
 /// # Flow
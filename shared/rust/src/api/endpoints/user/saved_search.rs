@@ -0,0 +1,56 @@
+use super::ApiEndpoint;
+
+use crate::{
+    api::method::Method,
+    domain::{
+        user::saved_search::{
+            SavedSearchCreateRequest, SavedSearchId, SavedSearchListResponse,
+            SavedSearchUpdateRequest,
+        },
+        CreateResponse,
+    },
+    error::EmptyError,
+};
+
+/// Save a jig search.
+///
+/// If `email_alerts` is set, the user is periodically emailed a digest of newly published jigs matching it.
+/// Jigs are only ever included in one digest per saved search.
+pub struct Create;
+impl ApiEndpoint for Create {
+    type Req = SavedSearchCreateRequest;
+    type Res = CreateResponse<SavedSearchId>;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/saved-search";
+    const METHOD: Method = Method::Post;
+}
+
+/// List the user's saved searches.
+pub struct List;
+impl ApiEndpoint for List {
+    type Req = ();
+    type Res = SavedSearchListResponse;
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/saved-search";
+    const METHOD: Method = Method::Get;
+}
+
+/// Update a saved search.
+pub struct Update;
+impl ApiEndpoint for Update {
+    type Req = SavedSearchUpdateRequest;
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/saved-search/{id}";
+    const METHOD: Method = Method::Patch;
+}
+
+/// Delete a saved search.
+pub struct Delete;
+impl ApiEndpoint for Delete {
+    type Req = ();
+    type Res = ();
+    type Err = EmptyError;
+    const PATH: &'static str = "/v1/user/me/saved-search/{id}";
+    const METHOD: Method = Method::Delete;
+}
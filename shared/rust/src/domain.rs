@@ -16,6 +16,7 @@ pub mod admin;
 pub mod animation;
 pub mod audio;
 pub mod category;
+pub mod class;
 pub mod firebase;
 pub mod image;
 pub mod jig;
@@ -44,7 +45,7 @@ pub mod auth {
 use chrono::Utc;
 #[cfg(feature = "backend")]
 use paperclip::actix::Apiv2Schema;
-use ser::{csv_encode_uuids, deserialize_optional_field, from_csv};
+use ser::{csv_encode, csv_encode_uuids, deserialize_optional_field, from_csv};
 use uuid::Uuid;
 
 /// Serialize/Deserialize wrapper for Base64 encoded content.
@@ -4,7 +4,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::media::{MediaKind, MediaLibrary};
+use crate::{
+    domain::image::ImageId,
+    media::{MediaKind, MediaLibrary},
+};
 
 /// Response for [`ListMedia`](crate::api::endpoints::admin::ListMedia)
 /// Super unstable, may change at any time, for any reason.
@@ -40,3 +43,111 @@ pub struct AdminMediaItem {
     /// An arbitrary (ascii) string representing the current state of the media.
     pub file_etag: Option<String>,
 }
+
+/// A kind of content that's indexed for search.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub enum SearchIndexEntity {
+    /// Images in the global library.
+    Image,
+
+    /// Animations in the global library.
+    Animation,
+
+    /// Jigs.
+    Jig,
+}
+
+/// Request for [`Reindex`](crate::api::endpoints::admin::Reindex)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminReindexRequest {
+    /// What to reindex, everything if empty.
+    #[serde(default)]
+    pub entities: Vec<SearchIndexEntity>,
+}
+
+/// What's backing search.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub enum SearchBackend {
+    /// Algolia, which content gets synced to.
+    Algolia,
+
+    /// Postgres full text search, which searches the database directly (nothing is synced).
+    Postgres,
+}
+
+/// How in sync the search index for one [`SearchIndexEntity`] is with the database.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminIndexHealth {
+    /// How many there are in the database.
+    pub db_count: u64,
+
+    /// How many are waiting to be synced to the index.
+    ///
+    /// `None` for [`SearchBackend::Postgres`].
+    pub pending_sync: Option<u64>,
+
+    /// How many records the index has, which should be `db_count` once nothing is pending.
+    ///
+    /// `None` for [`SearchBackend::Postgres`].
+    pub index_count: Option<u64>,
+}
+
+/// Response for [`SearchHealth`](crate::api::endpoints::admin::SearchHealth)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminSearchHealthResponse {
+    /// What's backing search.
+    pub backend: SearchBackend,
+
+    /// The health of the image index.
+    pub images: AdminIndexHealth,
+
+    /// The health of the animation index.
+    pub animations: AdminIndexHealth,
+
+    /// The health of the jig index.
+    pub jigs: AdminIndexHealth,
+}
+
+/// Query for [`ImageDuplicates`](crate::api::endpoints::admin::ImageDuplicates)
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminImageDuplicatesQuery {
+    /// How many bits (out of 64) the perceptual hashes of two images can differ by for them to be reported.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_distance: Option<u32>,
+
+    /// The maximum number of pairs to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/// A pair of near-duplicate images.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminImageDuplicate {
+    /// One of the images.
+    pub image_id: ImageId,
+
+    /// The other image.
+    pub duplicate_id: ImageId,
+
+    /// How many bits (out of 64) their perceptual hashes differ by, `0` for images that look the same.
+    pub distance: u32,
+}
+
+/// Response for [`ImageDuplicates`](crate::api::endpoints::admin::ImageDuplicates)
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+pub struct AdminImageDuplicatesResponse {
+    /// The near-duplicate pairs, closest first.
+    pub duplicates: Vec<AdminImageDuplicate>,
+}
@@ -1,6 +1,7 @@
 //! Types for animations.
 
 use super::{meta::AnimationStyleId, Publish};
+use crate::media::SpritesheetFile;
 use chrono::{DateTime, Utc};
 #[cfg(feature = "backend")]
 use paperclip::actix::Apiv2Schema;
@@ -129,6 +130,55 @@ struct DbAnimation {
     pub updated_at: Option<DateTime<Utc>>,
 }
 
+/// Search for animations via the given query string.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AnimationSearchQuery {
+    /// The query string.
+    pub q: String,
+
+    /// Optionally filter by `kind`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<AnimationKind>,
+
+    /// The page number of the animations to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+
+    /// Optionally filter by `styles`
+    #[serde(default)]
+    #[serde(serialize_with = "super::csv_encode_uuids")]
+    #[serde(deserialize_with = "super::from_csv")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub styles: Vec<AnimationStyleId>,
+
+    /// Optionally filter by `is_premium`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_premium: Option<bool>,
+
+    /// Optionally filter by `is_published`
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_published: Option<bool>,
+}
+
+/// Response for successful search.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AnimationSearchResponse {
+    /// the animations returned.
+    pub animations: Vec<AnimationResponse>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of animations found
+    pub total_animation_count: u64,
+}
+
 // todo: # errors doc section
 /// Request to create a new animation.
 #[derive(Serialize, Deserialize, Debug)]
@@ -164,6 +214,99 @@ pub struct AnimationCreateRequest {
 pub struct AnimationUploadRequest {
     /// The size of the image to be uploaded in bytes.
     pub file_size: usize,
+
+    /// Which file of a [`Spritesheet`](AnimationKind::Spritesheet) animation is being uploaded,
+    /// the sheet and its frames are uploaded separately.
+    ///
+    /// If [`None`], a GIF is being uploaded, which gets converted to a spritesheet for spritesheet animations.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spritesheet_file: Option<SpritesheetFile>,
+}
+
+/// The frames of a [`Spritesheet`](AnimationKind::Spritesheet) animation, stored alongside its sheet.
+///
+/// Frames are all the same size, and are laid out on the sheet left to right, then top to bottom.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpritesheetFrames {
+    /// The width of a frame, in pixels.
+    pub frame_width: u32,
+
+    /// The height of a frame, in pixels.
+    pub frame_height: u32,
+
+    /// How many frames there are.
+    pub frame_count: u32,
+
+    /// How many frames to play per second.
+    pub frame_rate: f32,
+}
+
+impl SpritesheetFrames {
+    /// How many frames fit in a row of a sheet that's `sheet_width` pixels wide.
+    #[must_use]
+    pub const fn columns(&self, sheet_width: u32) -> u32 {
+        sheet_width / self.frame_width
+    }
+
+    /// The position of the top left of `frame` on a sheet that's `sheet_width` pixels wide.
+    #[must_use]
+    pub const fn frame_position(&self, frame: u32, sheet_width: u32) -> (u32, u32) {
+        let columns = self.columns(sheet_width);
+
+        (
+            (frame % columns) * self.frame_width,
+            (frame / columns) * self.frame_height,
+        )
+    }
+
+    /// The frame to show `elapsed_ms` milliseconds into the animation.
+    ///
+    /// Animations that don't loop stay on their last frame.
+    #[must_use]
+    pub fn frame_at(&self, elapsed_ms: f64, is_looping: bool) -> u32 {
+        let frame = (elapsed_ms.max(0.0) * f64::from(self.frame_rate) / 1000.0) as u32;
+
+        if is_looping {
+            frame % self.frame_count
+        } else {
+            frame.min(self.frame_count - 1)
+        }
+    }
+
+    /// Checks that the frames fit on a sheet that's `sheet_width` by `sheet_height` pixels,
+    /// without any empty rows at the end.
+    #[must_use]
+    pub fn fits(&self, sheet_width: u32, sheet_height: u32) -> bool {
+        if self.frame_width == 0 || self.frame_height == 0 || self.frame_count == 0 {
+            return false;
+        }
+
+        if sheet_width < self.frame_width || sheet_height < self.frame_height {
+            return false;
+        }
+
+        if !(self.frame_rate > 0.0 && self.frame_rate.is_finite()) {
+            return false;
+        }
+
+        if sheet_width % self.frame_width != 0 || sheet_height % self.frame_height != 0 {
+            return false;
+        }
+
+        let columns = self.columns(sheet_width);
+        let rows = sheet_height / self.frame_height;
+
+        // at most one frame per cell of the sheet
+        if u64::from(self.frame_count) > u64::from(columns) * u64::from(rows) {
+            return false;
+        }
+
+        let needed_rows = self.frame_count / columns + u32::from(self.frame_count % columns != 0);
+
+        rows == needed_rows
+    }
 }
 
 /// URL to upload an user library image, supports resumable uploading.
@@ -40,7 +40,60 @@ pub mod user {
     pub struct UserAudio {
         /// The audio file's ID.
         pub id: AudioId,
-        // more fields to be added
+
+        /// The name the user gave the audio file.
+        pub name: String,
+
+        /// The tags the user gave the audio file.
+        pub tags: Vec<String>,
+
+        /// How long the audio file is in milliseconds, [`None`] until it's uploaded.
+        pub duration_ms: Option<u32>,
+
+        /// The peak loudness (from 0 to 1) of evenly sized slices of the audio file, for drawing its waveform.
+        ///
+        /// Empty until it's uploaded.
+        pub waveform: Vec<f32>,
+    }
+
+    /// Request to update an audio file's name and tags.
+    #[derive(Serialize, Deserialize, Debug, Default)]
+    #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+    pub struct UserAudioUpdateRequest {
+        /// If `Some` change the audio file's name to this name.
+        #[serde(default)]
+        pub name: Option<String>,
+
+        /// If `Some` replace the audio file's tags with these.
+        #[serde(default)]
+        pub tags: Option<Vec<String>>,
+    }
+
+    /// Search the user's audio library via the given query string.
+    #[derive(Serialize, Deserialize, Clone, Debug, Default)]
+    #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+    pub struct UserAudioSearchQuery {
+        /// The query string, matched against the audio files' names and tags.
+        pub q: String,
+
+        /// The page number of the audio files to get.
+        #[serde(default)]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub page: Option<u32>,
+    }
+
+    /// Response for successful search.
+    #[derive(Serialize, Deserialize, Debug)]
+    #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+    pub struct UserAudioSearchResponse {
+        /// the audio files returned.
+        pub audio_files: Vec<UserAudioResponse>,
+
+        /// The number of pages found.
+        pub pages: u32,
+
+        /// The total number of audio files found
+        pub total_audio_count: u64,
     }
 }
 
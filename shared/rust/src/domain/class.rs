@@ -0,0 +1,90 @@
+//! Types for classes.
+
+pub mod assignment;
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Wrapper type around [`Uuid`](Uuid), represents the ID of a class.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ClassId(pub Uuid);
+
+/// Over the wire representation of a class.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct Class {
+    /// The ID of the class.
+    pub id: ClassId,
+
+    /// The class's name.
+    pub name: String,
+
+    /// The ID of the class's creator.
+    pub creator_id: Uuid,
+
+    /// When the class was created.
+    pub created_at: DateTime<Utc>,
+
+    /// When the class was last updated.
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Request to create a new class.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ClassCreateRequest {
+    /// The class's name.
+    pub name: String,
+}
+
+/// Request for updating a class.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ClassUpdateRequest {
+    /// The class's name.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// The response returned when a request for `GET`ing a class is successful.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ClassResponse {
+    /// The requested class.
+    pub class: Class,
+}
+
+/// Query for [`Browse`](crate::api::endpoints::class::Browse).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub struct ClassBrowseQuery {
+    /// The page number of the classes to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+/// Response for [`Browse`](crate::api::endpoints::class::Browse).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub struct ClassBrowseResponse {
+    /// The classes returned.
+    pub classes: Vec<Class>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of classes found.
+    pub total_class_count: u64,
+}
+
+into_uuid![ClassId];
@@ -0,0 +1,145 @@
+//! Types for assigning JIGs to a class.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    super::jig::{player::JigPlayerSession, JigId},
+    ClassId,
+};
+
+/// Wrapper type around [`Uuid`](Uuid), represents the ID of an assignment.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentId(pub Uuid);
+
+/// Wrapper type around [`Uuid`](Uuid), represents the ID of a student who joined an assignment.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentStudentId(pub Uuid);
+
+/// Over the wire representation of an assignment.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct Assignment {
+    /// The ID of the assignment.
+    pub id: AssignmentId,
+
+    /// The class the assignment was given to.
+    pub class_id: ClassId,
+
+    /// The JIG to be played.
+    pub jig_id: JigId,
+
+    /// The code students enter to join the assignment.
+    pub code: String,
+
+    /// When the assignment was created.
+    pub created_at: DateTime<Utc>,
+
+    /// When the assignment is due (if ever).
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Request to create a new assignment.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentCreateRequest {
+    /// The JIG to be played.
+    pub jig_id: JigId,
+
+    /// When the assignment is due (if ever).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Request for updating an assignment.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentUpdateRequest {
+    /// When the assignment is due.
+    ///
+    /// `Some(None)` means the assignment is never due.
+    #[serde(deserialize_with = "super::super::deserialize_optional_field")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub due_at: Option<Option<DateTime<Utc>>>,
+}
+
+/// The response returned when a request for `GET`ing an assignment is successful.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentResponse {
+    /// The requested assignment.
+    pub assignment: Assignment,
+}
+
+/// Response for [`List`](crate::api::endpoints::class::assignment::List).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentListResponse {
+    /// The class's assignments, most recent first.
+    pub assignments: Vec<Assignment>,
+}
+
+/// Request to join an assignment as a student.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentJoinRequest {
+    /// The assignment's code.
+    pub code: String,
+
+    /// The name the student will be listed under.
+    pub name: String,
+}
+
+/// Response for successfully joining an assignment.
+///
+/// Note: This response *also* includes a cookie.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentJoinResponse {
+    /// A transparent CSRF token to use for this Session.
+    pub csrf: String,
+
+    /// The assignment that was joined.
+    pub assignment_id: AssignmentId,
+
+    /// The JIG to be played.
+    pub jig_id: JigId,
+}
+
+/// A student who joined an assignment, along with their play sessions.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentStudent {
+    /// The ID of the student.
+    pub id: AssignmentStudentId,
+
+    /// The name the student joined with.
+    pub name: String,
+
+    /// When the student joined the assignment.
+    pub joined_at: DateTime<Utc>,
+
+    /// The student's play sessions, most recent first.
+    pub sessions: Vec<JigPlayerSession>,
+}
+
+/// Response for [`Results`](crate::api::endpoints::class::assignment::Results).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct AssignmentResultsResponse {
+    /// The students who joined the assignment, in the order they joined.
+    pub students: Vec<AssignmentStudent>,
+}
+
+into_uuid![AssignmentId, AssignmentStudentId];
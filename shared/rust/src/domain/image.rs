@@ -15,6 +15,7 @@ use paperclip::actix::Apiv2Schema;
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "backend")]
 use sqlx::postgres::PgRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Represents different kinds of images (which affects how the size is stored in the db)
@@ -212,6 +213,10 @@ pub struct ImageSearchQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_published: Option<bool>,
+
+    /// Optionally also match the Hebrew transliteration of latin words in `q` (so that `shabbat` finds `שבת`).
+    #[serde(default)]
+    pub transliterate: bool,
 }
 
 /// Response for successful search.
@@ -226,6 +231,32 @@ pub struct ImageSearchResponse {
 
     /// The total number of images found
     pub total_image_count: u64,
+
+    /// How many of the images found have each filter value.
+    #[serde(default)]
+    pub facets: ImageSearchFacets,
+}
+
+/// Counts of images found per filter value, see [`ImageSearchResponse::facets`].
+///
+/// Values that no images have are left out.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ImageSearchFacets {
+    /// Counts per image style.
+    pub styles: HashMap<ImageStyleId, u64>,
+
+    /// Counts per age range.
+    pub age_ranges: HashMap<AgeRangeId, u64>,
+
+    /// Counts per affiliation.
+    pub affiliations: HashMap<AffiliationId, u64>,
+
+    /// Counts per category.
+    pub categories: HashMap<CategoryId, u64>,
+
+    /// Counts per tag.
+    pub tags: HashMap<TagId, u64>,
 }
 
 /// Query for [`Browse`](crate::api::endpoints::image::Browse).
@@ -248,6 +279,43 @@ pub struct ImageBrowseQuery {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u32>,
 }
+
+/// Query for [`Related`](crate::api::endpoints::image::Related).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ImageRelatedQuery {
+    /// The maximum number of images to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/// Response for [`Related`](crate::api::endpoints::image::Related).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ImageRelatedResponse {
+    /// The related images, most related first.
+    pub images: Vec<ImageResponse>,
+}
+
+/// Query for [`Similar`](crate::api::endpoints::image::Similar) and [`user::Similar`](crate::api::endpoints::image::user::Similar).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ImageSimilarQuery {
+    /// The maximum number of images to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/// Response for [`Similar`](crate::api::endpoints::image::Similar).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct ImageSimilarResponse {
+    /// The similar images, most similar first.
+    pub images: Vec<ImageResponse>,
+}
+
 /// Response for [`Browse`](crate::api::endpoints::image::Browse).
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
@@ -28,9 +28,63 @@ pub struct UserImageResponse {
 pub struct UserImage {
     /// The image's ID.
     pub id: ImageId,
+
+    /// The name the user gave the image.
+    pub name: String,
+
+    /// The tags the user gave the image.
+    pub tags: Vec<String>,
     // more fields to be added
 }
 
+/// Request to update an user library image's name and tags.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct UserImageUpdateRequest {
+    /// If `Some` change the image's name to this name.
+    #[serde(default)]
+    pub name: Option<String>,
+
+    /// If `Some` replace the image's tags with these.
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+}
+
+/// Search the user's image library via the given query string.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct UserImageSearchQuery {
+    /// The query string, matched against the images' names and tags.
+    pub q: String,
+
+    /// The page number of the images to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+/// Response for successful search.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct UserImageSearchResponse {
+    /// the images returned.
+    pub images: Vec<UserImageResponse>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of images found
+    pub total_image_count: u64,
+}
+
+/// Response for [`Similar`](crate::api::endpoints::image::user::Similar).
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct UserImageSimilarResponse {
+    /// The similar images, most similar first.
+    pub images: Vec<UserImageResponse>,
+}
+
 /// Request to indicate the size of an user library image for upload.
 #[derive(Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
@@ -1,7 +1,11 @@
 //! Types for JIGs.
 
 pub mod additional_resource;
+pub mod archive;
+pub mod collaborator;
 pub mod module;
+pub mod player;
+pub mod version;
 
 use std::{fmt, str::FromStr};
 
@@ -14,7 +18,7 @@ use chrono::{DateTime, Utc};
 #[cfg(feature = "backend")]
 use paperclip::actix::Apiv2Schema;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 // avoid breaking Changes
@@ -199,6 +203,71 @@ pub struct Jig {
 
     /// Audio effects
     pub audio_effects: AudioEffects,
+
+    /// The jig's revision, which is bumped every time the jig is updated.
+    ///
+    /// Pass it back via `If-Match` to make an [`Update`](crate::api::endpoints::jig::Update) conditional.
+    pub revision: u32,
+
+    /// How many times the jig has been played.
+    pub plays: u64,
+
+    /// How many users like the jig.
+    pub likes: u64,
+
+    /// How many (unique) users have viewed the jig.
+    pub views: u64,
+}
+
+/// What to sort jigs by, most first.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+#[repr(i16)]
+pub enum JigSort {
+    /// Most recently edited first.
+    Recent = 0,
+
+    /// See [`Jig::plays`].
+    Plays = 1,
+
+    /// See [`Jig::likes`].
+    Likes = 2,
+
+    /// See [`Jig::views`].
+    Views = 3,
+}
+
+impl Default for JigSort {
+    fn default() -> Self {
+        Self::Recent
+    }
+}
+
+/// Query for [`Related`](crate::api::endpoints::jig::Related).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigRelatedQuery {
+    /// The maximum number of jigs to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/// Response for [`Related`](crate::api::endpoints::jig::Related).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigRelatedResponse {
+    /// The related jigs, most related first.
+    pub jigs: Vec<JigResponse>,
+}
+
+/// Response for whether the current user likes a jig.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigLikedResponse {
+    /// Whether the user likes the jig.
+    pub is_liked: bool,
 }
 
 /// Audio for background music
@@ -382,6 +451,23 @@ pub struct JigBrowseQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page: Option<u32>,
+
+    /// What to sort the jigs by, defaults to [`Recent`](JigSort::Recent).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<JigSort>,
+
+    /// Optionally filter by language.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Optionally filter by `module_kinds`, only jigs containing all of them match.
+    #[serde(default)]
+    #[serde(serialize_with = "super::csv_encode")]
+    #[serde(deserialize_with = "super::from_csv")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub module_kinds: Vec<ModuleKind>,
 }
 
 /// Response for [`Browse`](crate::api::endpoints::jig::Browse).
@@ -412,8 +498,6 @@ pub struct JigSearchQuery {
     pub page: Option<u32>,
 
     /// Optionally filter by `age_ranges`
-    ///
-    /// Note: Currently does nothing
     #[serde(default)]
     #[serde(serialize_with = "super::csv_encode_uuids")]
     #[serde(deserialize_with = "super::from_csv")]
@@ -421,8 +505,6 @@ pub struct JigSearchQuery {
     pub age_ranges: Vec<AgeRangeId>,
 
     /// Optionally filter by `affiliations`
-    ///
-    /// Note: Currently does nothing
     #[serde(default)]
     #[serde(serialize_with = "super::csv_encode_uuids")]
     #[serde(deserialize_with = "super::from_csv")]
@@ -452,6 +534,27 @@ pub struct JigSearchQuery {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub author: Option<Uuid>,
+
+    /// Optionally sort the results, relevance only breaks ties.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<JigSort>,
+
+    /// Optionally filter by language.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+
+    /// Optionally filter by `module_kinds`, only jigs containing all of them match.
+    #[serde(default)]
+    #[serde(serialize_with = "super::csv_encode")]
+    #[serde(deserialize_with = "super::from_csv")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub module_kinds: Vec<ModuleKind>,
+
+    /// Optionally also match the Hebrew transliteration of latin words in `q` (so that `shabbat` finds `שבת`).
+    #[serde(default)]
+    pub transliterate: bool,
 }
 
 /// Response for successful search.
@@ -466,6 +569,29 @@ pub struct JigSearchResponse {
 
     /// The total number of jigs found
     pub total_image_count: u64,
+
+    /// How many of the jigs found have each filter value.
+    #[serde(default)]
+    pub facets: JigSearchFacets,
+}
+
+/// Counts of jigs found per filter value, see [`JigSearchResponse::facets`].
+///
+/// Values that no jigs have are left out.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigSearchFacets {
+    /// Counts per age range.
+    pub age_ranges: HashMap<AgeRangeId, u64>,
+
+    /// Counts per affiliation.
+    pub affiliations: HashMap<AffiliationId, u64>,
+
+    /// Counts per category.
+    pub categories: HashMap<CategoryId, u64>,
+
+    /// Counts per goal.
+    pub goals: HashMap<GoalId, u64>,
 }
 
 /// Response for successfully finding the draft of a jig.
@@ -474,6 +600,21 @@ pub struct JigSearchResponse {
 pub struct JigDraftResponse {
     /// The ID of the jig
     pub id: JigId,
+
+    /// When the draft is scheduled to be published, if it is.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publish_at: Option<DateTime<Utc>>,
+}
+
+/// Request for scheduling a draft to be published.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigDraftScheduleRequest {
+    /// When to publish the draft, or [`None`] to cancel a scheduled publish.
+    ///
+    /// Any timezone offset is accepted, times in the past publish the draft as soon as possible.
+    pub publish_at: Option<DateTime<Utc>>,
 }
 
 /// Sets text direction for the jig
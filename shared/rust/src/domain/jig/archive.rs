@@ -0,0 +1,36 @@
+//! Types for exporting and importing a JIG as a zip archive.
+//!
+//! An archive contains:
+//! * `manifest.json` - a [`JigArchiveManifest`]
+//! * `modules/{index}.json` - each of the JIG's [`Module`](super::Module)s, including their bodies
+//! * the image and audio files referenced by the modules, stored under their [`media_key`](crate::media::media_key)
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{additional_resource::AdditionalResource, Jig};
+
+/// The version of the archive format written by [`Export`](crate::api::endpoints::jig::Export).
+pub const JIG_ARCHIVE_VERSION: u32 = 1;
+
+/// The name of the manifest file in an archive.
+pub const JIG_ARCHIVE_MANIFEST: &str = "manifest.json";
+
+/// The directory the modules are stored in in an archive.
+pub const JIG_ARCHIVE_MODULES_DIR: &str = "modules";
+
+/// The contents of `manifest.json` in an archive.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JigArchiveManifest {
+    /// The archive format version, see [`JIG_ARCHIVE_VERSION`].
+    pub version: u32,
+
+    /// When the archive was created.
+    pub exported_at: DateTime<Utc>,
+
+    /// The JIG at the time it was exported.
+    pub jig: Jig,
+
+    /// The JIG's additional resources.
+    pub additional_resources: Vec<AdditionalResource>,
+}
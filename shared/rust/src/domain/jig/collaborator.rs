@@ -0,0 +1,59 @@
+//! Types for sharing a JIG with other users.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// What a collaborator is allowed to do with a JIG.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[repr(i16)]
+pub enum JigCollaboratorRole {
+    /// The collaborator can edit and publish the JIG, but not delete it or manage its collaborators.
+    Editor = 0,
+
+    /// The collaborator can see the JIG's drafts, versions and play sessions, but not change anything.
+    Viewer = 1,
+}
+
+/// Over the wire representation of a collaborator on a JIG.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigCollaborator {
+    /// The ID of the collaborating user.
+    pub user_id: Uuid,
+
+    /// The collaborator's role.
+    pub role: JigCollaboratorRole,
+
+    /// The user who sent the invite ([`None`] if unknown).
+    pub invited_by: Option<Uuid>,
+
+    /// When the collaborator was invited.
+    pub invited_at: DateTime<Utc>,
+
+    /// When the collaborator accepted the invite ([`None`] if the invite is still pending).
+    pub accepted_at: Option<DateTime<Utc>>,
+}
+
+/// Request to invite a user to collaborate on a JIG.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigCollaboratorInviteRequest {
+    /// The email of the user to invite.
+    pub email: String,
+
+    /// The role the user will have once they accept.
+    pub role: JigCollaboratorRole,
+}
+
+/// Response for [`List`](crate::api::endpoints::jig::collaborator::List).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigCollaboratorListResponse {
+    /// The JIG's collaborators (including pending invites), in the order they were invited.
+    pub collaborators: Vec<JigCollaborator>,
+}
@@ -152,6 +152,11 @@ pub struct Module {
 
     /// Whether the module is complete or not.
     pub is_complete: bool,
+
+    /// The module's revision, which is bumped every time the module is updated.
+    ///
+    /// Pass it back via `If-Match` to make an [`Update`](crate::api::endpoints::jig::module::Update) conditional.
+    pub revision: u32,
 }
 
 /// Request to create a new `Module`.
@@ -0,0 +1,102 @@
+//! Types for recording JIG play sessions.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{module::ModuleId, JigId};
+
+/// Wrapper type around [`Uuid`](Uuid), represents the ID of a play session.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigPlayerSessionId(pub Uuid);
+
+/// The result of playing through a single module.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigPlayerModuleResult {
+    /// The module that was played.
+    pub module_id: ModuleId,
+
+    /// The score achieved in the module ([`None`] for modules that can't be scored).
+    pub score: Option<u32>,
+
+    /// How many attempts were made in the module.
+    pub attempts: u32,
+
+    /// How long was spent in the module, in seconds.
+    pub time_spent: u32,
+
+    /// When the result was recorded.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Over the wire representation of a play session.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigPlayerSession {
+    /// The ID of the session.
+    pub id: JigPlayerSessionId,
+
+    /// The JIG that was played.
+    pub jig_id: JigId,
+
+    /// The user who played the JIG ([`None`] if unknown).
+    pub user_id: Option<Uuid>,
+
+    /// When the session was started.
+    pub started_at: DateTime<Utc>,
+
+    /// The results reported by each module, in the order they were received.
+    pub module_results: Vec<JigPlayerModuleResult>,
+}
+
+/// Request to record the result of a module in a play session.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigPlayerModuleResultRequest {
+    /// The module that was played.
+    pub module_id: ModuleId,
+
+    /// The score achieved in the module ([`None`] for modules that can't be scored).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<u32>,
+
+    /// How many attempts were made in the module.
+    pub attempts: u32,
+
+    /// How long was spent in the module, in seconds.
+    pub time_spent: u32,
+}
+
+/// Query for [`ListSessions`](crate::api::endpoints::jig::player::ListSessions).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub struct JigPlayerSessionListQuery {
+    /// The page number of the sessions to get.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+}
+
+/// Response for [`ListSessions`](crate::api::endpoints::jig::player::ListSessions).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigPlayerSessionListResponse {
+    /// The sessions found, most recent first.
+    pub sessions: Vec<JigPlayerSession>,
+
+    /// The number of pages found.
+    pub pages: u32,
+
+    /// The total number of sessions found.
+    pub total_session_count: u64,
+}
+
+into_uuid![JigPlayerSessionId];
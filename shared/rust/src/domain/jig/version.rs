@@ -0,0 +1,155 @@
+//! Types for the published versions of a JIG.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    super::{
+        category::CategoryId,
+        meta::{AffiliationId, AgeRangeId, GoalId},
+    },
+    module::{body::ThemeId, Module},
+    AudioBackground, AudioEffects, JigId, TextDirection,
+};
+
+/// Wrapper type around [`Uuid`](Uuid), represents the ID of a published version of a JIG.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionId(pub Uuid);
+
+/// Over the wire representation of a published version of a JIG.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersion {
+    /// The ID of the version.
+    pub id: JigVersionId,
+
+    /// The (live) JIG this is a version of.
+    pub jig_id: JigId,
+
+    /// The version number, starting at `1` for the first publish.
+    pub version: u32,
+
+    /// When the version was published.
+    pub published_at: DateTime<Utc>,
+
+    /// The user who published the version ([`None`] if unknown).
+    pub published_by: Option<Uuid>,
+}
+
+/// The contents of a JIG at the time a version was published.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionSnapshot {
+    /// The JIG's name.
+    pub display_name: String,
+
+    /// The language the jig uses.
+    pub language: String,
+
+    /// Description of the jig.
+    pub description: String,
+
+    /// Text direction for the jig.
+    pub direction: TextDirection,
+
+    /// Whether to display the score for this jig.
+    pub display_score: bool,
+
+    /// Theme for this jig.
+    pub theme: ThemeId,
+
+    /// Background audio
+    pub audio_background: Option<AudioBackground>,
+
+    /// Audio effects
+    pub audio_effects: AudioEffects,
+
+    /// The goals of this jig.
+    pub goals: Vec<GoalId>,
+
+    /// The jig's categories.
+    pub categories: Vec<CategoryId>,
+
+    /// This jig's affiliations.
+    pub affiliations: Vec<AffiliationId>,
+
+    /// This jig's age ranges.
+    pub age_ranges: Vec<AgeRangeId>,
+
+    /// The URLs of the jig's additional resources.
+    pub additional_resources: Vec<String>,
+
+    /// The JIG's modules (including their bodies), in order.
+    ///
+    /// Note: module IDs refer to the modules as they were when published, and may no longer exist.
+    pub modules: Vec<Module>,
+}
+
+/// Response for [`List`](crate::api::endpoints::jig::version::List).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionListResponse {
+    /// The JIG's versions, most recent first.
+    pub versions: Vec<JigVersion>,
+}
+
+/// Response for [`Get`](crate::api::endpoints::jig::version::Get).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionResponse {
+    /// The requested version.
+    pub version: JigVersion,
+
+    /// The contents of the JIG at the time of the version.
+    pub snapshot: JigVersionSnapshot,
+}
+
+/// How a module changed between two versions.
+#[derive(Serialize, Deserialize, Copy, Clone, Eq, PartialEq, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub enum JigVersionModuleChange {
+    /// The module only exists in the newer version.
+    Added,
+
+    /// The module only exists in the older version.
+    Removed,
+
+    /// The module exists in both versions, but its kind, body or completion differs.
+    Modified,
+}
+
+/// A module that differs between two versions.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionModuleDiff {
+    /// The index of the module in the JIG.
+    pub index: u16,
+
+    /// How the module changed.
+    pub change: JigVersionModuleChange,
+}
+
+/// Response for [`Diff`](crate::api::endpoints::jig::version::Diff).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct JigVersionDiffResponse {
+    /// The version being compared from.
+    pub from: JigVersion,
+
+    /// The version being compared to.
+    pub to: JigVersion,
+
+    /// The names of the [`JigVersionSnapshot`] fields (other than `modules`) that differ.
+    pub changed_fields: Vec<String>,
+
+    /// The modules that differ, by index.
+    pub modules: Vec<JigVersionModuleDiff>,
+}
+
+into_uuid![JigVersionId];
@@ -9,6 +9,8 @@ use uuid::Uuid;
 
 use crate::media::MediaKind;
 
+use super::jig::{module::ModuleId, JigId};
+
 /// Response for adding a URL to the Web Media Library
 #[derive(Serialize, Deserialize, Debug)]
 #[cfg_attr(feature = "backend", derive(Apiv2Schema))]
@@ -49,3 +51,31 @@ pub struct WebMediaMetadataResponse {
     /// When this media was last updated, if ever.
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+/// Query for deleting media from any of the media libraries.
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct MediaDeleteQuery {
+    /// Delete the media even if JIG modules still use it, see [`MediaInUse`](crate::error::MediaInUse).
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// A module that uses a piece of media.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct MediaUsage {
+    /// The JIG the module belongs to (which may be a draft).
+    pub jig_id: JigId,
+
+    /// The module that references the media.
+    pub module_id: ModuleId,
+}
+
+/// Response for finding where a piece of media is used.
+#[derive(Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct MediaUsageResponse {
+    /// The modules that use the media, grouped by JIG.
+    pub usages: Vec<MediaUsage>,
+}
@@ -39,3 +39,66 @@ pub struct WebImageSearchResponse {
     /// the images returned.
     pub images: Vec<WebImageSearchItem>,
 }
+
+/// Query for [`Suggest`](crate::api::endpoints::search::Suggest).
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SearchSuggestQuery {
+    /// What has been typed so far, the last word is matched as a prefix.
+    pub q: String,
+
+    /// The maximum number of suggestions to return.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u16>,
+}
+
+/// Where a [`SearchSuggestion`] comes from.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub enum SearchSuggestionKind {
+    /// One of the user's recent searches.
+    Recent,
+
+    /// The name of a published jig.
+    Jig,
+
+    /// The name of a published image.
+    Image,
+
+    /// The name of an image tag.
+    Tag,
+
+    /// The name of a category.
+    Category,
+}
+
+/// A single completion of the query.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SearchSuggestion {
+    /// The text to search for.
+    pub text: String,
+
+    /// Where the suggestion comes from.
+    pub kind: SearchSuggestionKind,
+}
+
+/// Response for [`Suggest`](crate::api::endpoints::search::Suggest).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SearchSuggestResponse {
+    /// The suggestions, most relevant first.
+    ///
+    /// Recent searches come first, then jig, image, tag and category names.
+    pub suggestions: Vec<SearchSuggestion>,
+}
+
+/// Response for [`ListRecent`](crate::api::endpoints::search::ListRecent).
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct RecentSearchResponse {
+    /// The user's recent searches, most recent first.
+    pub queries: Vec<String>,
+}
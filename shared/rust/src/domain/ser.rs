@@ -1,4 +1,8 @@
-use serde::de::{DeserializeOwned, Visitor};
+use serde::{
+    de::{DeserializeOwned, Visitor},
+    ser::Error as _,
+    Serialize,
+};
 use std::{
     fmt::{self, Write},
     marker::PhantomData,
@@ -50,6 +54,25 @@ where
     serializer.serialize_str(&out)
 }
 
+/// Serializes `values` as "v1,v2,v3", for values that aren't ids (see [`csv_encode_uuids`]).
+pub(super) fn csv_encode<T: Serialize, S>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(vec![]);
+
+    writer.serialize(values).map_err(S::Error::custom)?;
+
+    let raw = writer.into_inner().map_err(S::Error::custom)?;
+
+    let s = std::str::from_utf8(&raw).map_err(S::Error::custom)?;
+
+    // the writer terminates the record with a newline.
+    serializer.serialize_str(s.trim_end())
+}
+
 pub(super) fn from_csv<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
 where
     D: serde::Deserializer<'de>,
@@ -1,5 +1,7 @@
 //! Types for users.
 
+pub mod saved_search;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -41,6 +43,9 @@ pub enum UserScope {
 
     /// The User can create/delete/modify audio files of their own.
     ManageAudio = 9,
+
+    /// The user can create/delete/modify classes (and their assignments) of their own.
+    ManageClass = 10,
 }
 
 impl TryFrom<i16> for UserScope {
@@ -56,6 +61,7 @@ impl TryFrom<i16> for UserScope {
             7 => Ok(Self::ManageEntry),
             8 => Ok(Self::ManageSelfJig),
             9 => Ok(Self::ManageAudio),
+            10 => Ok(Self::ManageClass),
             _ => anyhow::bail!("Scope {} is invalid"),
         }
     }
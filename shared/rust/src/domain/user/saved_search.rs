@@ -0,0 +1,86 @@
+//! Types for saved searches.
+
+use chrono::{DateTime, Utc};
+#[cfg(feature = "backend")]
+use paperclip::actix::Apiv2Schema;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::domain::jig::JigSearchQuery;
+
+/// Wrapper type around [`Uuid`], represents the ID of a saved search.
+#[derive(Copy, Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "backend", derive(sqlx::Type))]
+#[cfg_attr(feature = "backend", sqlx(transparent))]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SavedSearchId(pub Uuid);
+
+/// A jig search the user saved, to run again later or to be emailed about new jigs matching it.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SavedSearch {
+    /// The saved search's ID.
+    pub id: SavedSearchId,
+
+    /// The name the user gave the search.
+    pub name: String,
+
+    /// The search (query and filters).
+    ///
+    /// `page` and `sort` are ignored when looking for new jigs.
+    pub query: JigSearchQuery,
+
+    /// Whether the user gets emailed a digest of newly published jigs matching the search.
+    pub email_alerts: bool,
+
+    /// When the search was saved.
+    pub created_at: DateTime<Utc>,
+
+    /// When the search was last checked for newly published jigs.
+    pub last_checked_at: DateTime<Utc>,
+}
+
+/// Request to save a search.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SavedSearchCreateRequest {
+    /// The name to give the search.
+    pub name: String,
+
+    /// The search (query and filters).
+    pub query: JigSearchQuery,
+
+    /// Whether to get emailed a digest of newly published jigs matching the search.
+    #[serde(default)]
+    pub email_alerts: bool,
+}
+
+/// Request to update a saved search.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SavedSearchUpdateRequest {
+    /// If `Some`, rename the search.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// If `Some`, replace the search (query and filters).
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<JigSearchQuery>,
+
+    /// If `Some`, turn email alerts on or off.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email_alerts: Option<bool>,
+}
+
+/// Response for listing saved searches.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "backend", derive(Apiv2Schema))]
+pub struct SavedSearchListResponse {
+    /// The user's saved searches, most recently saved first.
+    pub saved_searches: Vec<SavedSearch>,
+}
+
+into_uuid![SavedSearchId];
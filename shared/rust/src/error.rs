@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::domain::meta::MetaKind;
+use crate::domain::{jig::JigId, meta::MetaKind};
 
 /// auth errors
 #[deprecated]
@@ -74,3 +74,10 @@ pub struct MetadataNotFound {
     /// The item's kind.
     pub kind: MetaKind,
 }
+
+/// The media is still used by JIG modules, so it wasn't deleted.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct MediaInUse {
+    /// The JIGs whose modules use the media.
+    pub jigs: Vec<JigId>,
+}
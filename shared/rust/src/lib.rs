@@ -23,3 +23,4 @@ pub mod api;
 pub mod domain;
 pub mod error;
 pub mod media;
+pub mod search;
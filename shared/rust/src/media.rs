@@ -45,8 +45,51 @@ pub enum PngImageFile {
     Thumbnail,
 }
 
+/// Formats the resized image and thumbnail of a processed image are stored in.
+///
+/// The original is only ever stored under the PNG's key
+/// (though lossy uploads keep their own format, see `image_ops::generate_images` in the API).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ImageFileFormat {
+    /// AVIF, the smallest, but the least supported
+    Avif,
+
+    /// WebP
+    Webp,
+
+    /// PNG, supported everywhere
+    Png,
+}
+
+impl ImageFileFormat {
+    /// Every format, from the smallest files to the largest (and from the least supported to the most).
+    pub const BEST_FIRST: [Self; 3] = [Self::Avif, Self::Webp, Self::Png];
+
+    /// Returns the kind of the file for `file` in this format.
+    #[must_use]
+    pub const fn file_kind(self, file: PngImageFile) -> FileKind {
+        match self {
+            Self::Avif => FileKind::ImageAvif(file),
+            Self::Webp => FileKind::ImageWebp(file),
+            Self::Png => FileKind::ImagePng(file),
+        }
+    }
+}
+
+/// Files of a spritesheet animation
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
+#[serde(rename_all = "camelCase")]
+pub enum SpritesheetFile {
+    /// The PNG sheet the frames are laid out on
+    Sheet,
+
+    /// The JSON describing the frames, see [`SpritesheetFrames`](crate::domain::animation::SpritesheetFrames)
+    Frames,
+}
+
 /// Media Libraries
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "backend", derive(sqlx::Type))]
 #[cfg_attr(feature = "backend", derive(paperclip::actix::Apiv2Schema))]
 #[repr(i16)]
@@ -111,18 +154,57 @@ pub enum FileKind {
     /// Files for a PNG Image
     ImagePng(PngImageFile),
 
-    // Spritesheet(Image,JSON),
+    /// WebP variants of the files for an Image
+    ImageWebp(PngImageFile),
+
+    /// AVIF variants of the files for an Image
+    ImageAvif(PngImageFile),
+
+    /// JPEG variants of the files for an Image (only the original of a JPEG upload is stored as one)
+    ImageJpeg(PngImageFile),
+
+    /// Files for a Spritesheet animation
+    Spritesheet(SpritesheetFile),
+
     /// File for Mp3 audio
     AudioMp3,
 }
 
 impl FileKind {
+    /// Every file stored for a processed image.
+    pub const IMAGE_FILES: &'static [Self] = &[
+        Self::ImagePng(PngImageFile::Original),
+        Self::ImageJpeg(PngImageFile::Original),
+        Self::ImageWebp(PngImageFile::Original),
+        Self::ImagePng(PngImageFile::Resized),
+        Self::ImagePng(PngImageFile::Thumbnail),
+        Self::ImageWebp(PngImageFile::Resized),
+        Self::ImageWebp(PngImageFile::Thumbnail),
+        Self::ImageAvif(PngImageFile::Resized),
+        Self::ImageAvif(PngImageFile::Thumbnail),
+    ];
+
+    /// The files the original of a processed image can be stored as, only one of which exists.
+    ///
+    /// Lossy uploads keep their format (a JPEG photo would be several times bigger as a PNG),
+    /// everything else is stored as a PNG.
+    pub const ORIGINAL_IMAGE_FILES: &'static [Self] = &[
+        Self::ImagePng(PngImageFile::Original),
+        Self::ImageJpeg(PngImageFile::Original),
+        Self::ImageWebp(PngImageFile::Original),
+    ];
+
     /// Returns the content type of the represented file
     #[must_use]
     pub const fn content_type(self) -> &'static str {
         match self {
             Self::AnimationGif => "image/gif",
             Self::ImagePng(_) => "image/png",
+            Self::ImageWebp(_) => "image/webp",
+            Self::ImageAvif(_) => "image/avif",
+            Self::ImageJpeg(_) => "image/jpeg",
+            Self::Spritesheet(SpritesheetFile::Sheet) => "image/png",
+            Self::Spritesheet(SpritesheetFile::Frames) => "application/json",
             Self::AudioMp3 => "audio/mp3",
         }
     }
@@ -134,6 +216,17 @@ impl FileKind {
             Self::ImagePng(PngImageFile::Original) => "original.png",
             Self::ImagePng(PngImageFile::Thumbnail) => "thumbnail.png",
             Self::ImagePng(PngImageFile::Resized) => "resized.png",
+            Self::ImageWebp(PngImageFile::Original) => "original.webp",
+            Self::ImageWebp(PngImageFile::Thumbnail) => "thumbnail.webp",
+            Self::ImageWebp(PngImageFile::Resized) => "resized.webp",
+            Self::ImageAvif(PngImageFile::Original) => "original.avif",
+            Self::ImageAvif(PngImageFile::Thumbnail) => "thumbnail.avif",
+            Self::ImageAvif(PngImageFile::Resized) => "resized.avif",
+            Self::ImageJpeg(PngImageFile::Original) => "original.jpg",
+            Self::ImageJpeg(PngImageFile::Thumbnail) => "thumbnail.jpg",
+            Self::ImageJpeg(PngImageFile::Resized) => "resized.jpg",
+            Self::Spritesheet(SpritesheetFile::Sheet) => "spritesheet.png",
+            Self::Spritesheet(SpritesheetFile::Frames) => "spritesheet.json",
             Self::AudioMp3 => "audio.mp3",
         }
     }
@@ -148,6 +241,17 @@ impl std::str::FromStr for FileKind {
             "original.png" => Ok(Self::ImagePng(PngImageFile::Original)),
             "thumbnail.png" => Ok(Self::ImagePng(PngImageFile::Thumbnail)),
             "resized.png" => Ok(Self::ImagePng(PngImageFile::Resized)),
+            "original.webp" => Ok(Self::ImageWebp(PngImageFile::Original)),
+            "thumbnail.webp" => Ok(Self::ImageWebp(PngImageFile::Thumbnail)),
+            "resized.webp" => Ok(Self::ImageWebp(PngImageFile::Resized)),
+            "original.avif" => Ok(Self::ImageAvif(PngImageFile::Original)),
+            "thumbnail.avif" => Ok(Self::ImageAvif(PngImageFile::Thumbnail)),
+            "resized.avif" => Ok(Self::ImageAvif(PngImageFile::Resized)),
+            "original.jpg" => Ok(Self::ImageJpeg(PngImageFile::Original)),
+            "thumbnail.jpg" => Ok(Self::ImageJpeg(PngImageFile::Thumbnail)),
+            "resized.jpg" => Ok(Self::ImageJpeg(PngImageFile::Resized)),
+            "spritesheet.png" => Ok(Self::Spritesheet(SpritesheetFile::Sheet)),
+            "spritesheet.json" => Ok(Self::Spritesheet(SpritesheetFile::Frames)),
             "audio.mp3" => Ok(Self::AudioMp3),
             _ => Err(anyhow::anyhow!("media type not recognized")),
         }
@@ -171,6 +275,9 @@ pub struct MediaKey {
 
 /// gives the key for some media with the given parameters
 /// this is *not* a full url, (for CDN it's missing the domain)
+///
+/// The resized image and thumbnail of a processed image are stored in every [`ImageFileFormat`],
+/// use [`ImageFileFormat::file_kind`] to get the key of the best one a client supports.
 #[must_use]
 pub fn media_key(library: MediaLibrary, id: Uuid, file_kind: FileKind) -> String {
     format!(
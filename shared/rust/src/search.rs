@@ -0,0 +1,133 @@
+//! Normalizes text for search, so that the way Hebrew is written doesn't change what it matches.
+//!
+//! Both the indexed text and the query have to go through [`normalize`].
+//! Postgres builds its search documents itself, with the `search_normalize` SQL function, which has to be kept in sync with [`normalize`].
+
+/// Normalizes `text` for search:
+/// * niqqud and cantillation marks are removed (`שַׁבָּת` becomes `שבת`)
+/// * final letter forms are replaced by the regular ones (`ך` becomes `כ`, `ם` becomes `מ`, etc)
+/// * Hebrew punctuation (maqaf, sof pasuq, etc) becomes a space, geresh and gershayim are removed
+/// * everything is lowercased
+#[must_use]
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter_map(|c| match c {
+            // maqaf, paseq, sof pasuq, nun hafukha
+            '\u{05BE}' | '\u{05C0}' | '\u{05C3}' | '\u{05C6}' => Some(' '),
+            // cantillation marks, niqqud, geresh and gershayim
+            '\u{0591}'..='\u{05C7}' | '\u{05F3}' | '\u{05F4}' => None,
+            'ך' => Some('כ'),
+            'ם' => Some('מ'),
+            'ן' => Some('נ'),
+            'ף' => Some('פ'),
+            'ץ' => Some('צ'),
+            c => Some(c),
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Hebrew letters for latin digraphs, checked before single letters.
+const DIGRAPHS: &[(&str, char)] = &[
+    ("sh", 'ש'),
+    ("ch", 'ח'),
+    ("kh", 'ח'),
+    ("tz", 'צ'),
+    ("ts", 'צ'),
+    ("th", 'ת'),
+    ("ph", 'פ'),
+];
+
+fn transliterate_consonant(c: char) -> Option<char> {
+    let letter = match c {
+        'b' | 'v' => 'ב',
+        'g' | 'j' => 'ג',
+        'd' => 'ד',
+        'h' => 'ה',
+        'w' => 'ו',
+        'z' => 'ז',
+        't' => 'ת',
+        'y' => 'י',
+        'k' | 'c' => 'כ',
+        'l' => 'ל',
+        'm' => 'מ',
+        'n' => 'נ',
+        's' | 'x' => 'ס',
+        'f' | 'p' => 'פ',
+        'q' => 'ק',
+        'r' => 'ר',
+        _ => return None,
+    };
+
+    Some(letter)
+}
+
+/// Transliterates a single (lowercase, ascii) word.
+fn transliterate_word(word: &str) -> String {
+    // doubled letters are written once (`shabbat`).
+    let mut chars: Vec<char> = word.chars().collect();
+    chars.dedup();
+    let word: String = chars.into_iter().collect();
+
+    let mut out = String::new();
+    let mut rest = word.as_str();
+
+    while let Some(c) = rest.chars().next() {
+        let is_start = out.is_empty();
+
+        if let Some(&(digraph, letter)) = DIGRAPHS.iter().find(|(it, _)| rest.starts_with(it)) {
+            out.push(letter);
+            rest = &rest[digraph.len()..];
+            continue;
+        }
+
+        rest = &rest[c.len_utf8()..];
+        let is_end = rest.is_empty();
+
+        match c {
+            // a word starting with a vowel starts with an aleph.
+            'a' | 'e' if is_start => out.push('א'),
+            'i' if is_start => out.push_str("אי"),
+            'o' | 'u' if is_start => out.push_str("או"),
+
+            // a word ending with `a` usually ends with a heh (`challa`).
+            'a' if is_end => out.push('ה'),
+            'a' | 'e' => {}
+            'i' => out.push('י'),
+            'o' | 'u' => out.push('ו'),
+
+            c => out.extend(transliterate_consonant(c)),
+        }
+    }
+
+    out
+}
+
+/// Best effort transliteration of the latin words in `text` into Hebrew letters, so that `shabbat` can find `שבת`.
+///
+/// Hebrew doesn't (usually) write vowels, so the result is only meant to be matched against [`normalize`]d text.
+///
+/// Returns [`None`] if there's nothing to transliterate.
+#[must_use]
+pub fn transliterate(text: &str) -> Option<String> {
+    let mut changed = false;
+
+    let words: Vec<String> = text
+        .split_whitespace()
+        .map(|word| {
+            let word = word.to_lowercase();
+            if word.chars().all(|c| c.is_ascii_alphabetic()) {
+                changed = true;
+                transliterate_word(&word)
+            } else {
+                word
+            }
+        })
+        .collect();
+
+    if changed {
+        Some(words.join(" "))
+    } else {
+        None
+    }
+}